@@ -0,0 +1,156 @@
+// Compares the decoder against copying the unconsumed input, and typed parsing into borrowed
+// views against typed parsing into owned NMEA.
+//
+// Run with `cargo bench --bench parser`.  Criterion isn't a dependency so this times each case
+// with std::time::Instant and reports the mean time per iteration.
+
+use bytes::{Bytes, BytesMut};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use tokio_util::codec::Decoder;
+use where_am_i::gps::{Driver, Generic};
+use where_am_i::nmea::{parser::Parser, view, Codec, NMEAView, ParseProfile, NMEA};
+
+const ITERATIONS: u32 = 2_000;
+
+// One epoch from a u-blox receiver
+const EPOCH: &[&[u8]] = &[
+    b"$GNRMC,025134.00,A,4735.28870,N,12217.96310,W,0.012,,160322,,,D*75\r\n",
+    b"$GNVTG,,T,,M,0.012,N,0.022,K,D*3B\r\n",
+    b"$GNGGA,025134.00,4735.28870,N,12217.96310,W,2,12,0.67,27.1,M,-17.3,M,,0000*46\r\n",
+    b"$GNGSA,A,3,13,15,05,18,23,24,29,10,20,,,,1.21,0.67,1.01*1A\r\n",
+    b"$GNGSA,A,3,85,72,65,87,88,71,,,,,,,1.21,0.67,1.01*1D\r\n",
+    b"$GPGSV,3,1,12,05,44,305,42,10,15,147,32,13,29,267,37,15,46,222,44*71\r\n",
+    b"$GPGSV,3,2,12,18,61,066,45,20,09,311,25,23,31,081,40,24,33,183,39*73\r\n",
+    b"$GPGSV,3,3,12,29,20,233,36,30,02,336,,45,33,203,40,46,31,206,39*79\r\n",
+    b"$GLGSV,2,1,08,65,33,050,37,71,32,197,37,72,73,296,40,73,05,327,*6F\r\n",
+    b"$GLGSV,2,2,08,85,24,047,35,86,28,110,,87,48,190,36,88,27,264,32*6D\r\n",
+    b"$GNGLL,4735.28870,N,12217.96310,W,025134.00,A,D*6B\r\n",
+];
+
+fn input(epochs: usize) -> Vec<u8> {
+    let mut input = vec![];
+
+    for _ in 0..epochs {
+        for sentence in EPOCH {
+            input.extend_from_slice(sentence);
+        }
+    }
+
+    input
+}
+
+fn bench<F: FnMut()>(name: &str, mut f: F) -> Duration {
+    // warm up
+    for _ in 0..ITERATIONS / 10 {
+        f();
+    }
+
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        f();
+    }
+
+    let mean = start.elapsed() / ITERATIONS;
+
+    println!("{:<32} {:>12?}", name, mean);
+
+    mean
+}
+
+fn compare(name: &str, before: Duration, after: Duration) {
+    println!(
+        "{:<32} {:>11.2}x\n",
+        name,
+        before.as_secs_f64() / after.as_secs_f64()
+    );
+}
+
+// The decoder before it borrowed from the buffer, the unconsumed input is copied back after
+// every message
+fn copying_decode(parser: &Parser, buf: &mut BytesMut) -> usize {
+    let mut count = 0;
+
+    loop {
+        let bytes = buf.split_to(buf.len());
+
+        match parser.parse(&bytes, Duration::ZERO) {
+            Ok((rest, nmea)) => {
+                buf.extend_from_slice(&Bytes::copy_from_slice(rest));
+                black_box(nmea);
+                count += 1;
+            }
+            Err(_) => {
+                buf.extend_from_slice(&Bytes::copy_from_slice(&bytes));
+                return count;
+            }
+        }
+    }
+}
+
+fn decode(codec: &mut Codec, buf: &mut BytesMut) -> usize {
+    let mut count = 0;
+
+    while let Some(nmea) = codec.decode(buf).unwrap() {
+        black_box(nmea);
+        count += 1;
+    }
+
+    count
+}
+
+fn bench_decode(epochs: usize) {
+    let driver = Driver::Generic(Generic::default());
+    let parser = Parser::new(driver.clone());
+    let mut codec = Codec::new(driver);
+    let input = input(epochs);
+    let sentences = epochs * EPOCH.len();
+
+    let before = bench(&format!("copying decode, {} epochs", epochs), || {
+        let mut buf = BytesMut::from(&input[..]);
+        assert_eq!(sentences, copying_decode(&parser, &mut buf));
+    });
+
+    let after = bench(&format!("decode, {} epochs", epochs), || {
+        let mut buf = BytesMut::from(&input[..]);
+        assert_eq!(sentences, decode(&mut codec, &mut buf));
+    });
+
+    compare("decode speedup", before, after);
+}
+
+fn bench_view() {
+    let parser = Parser::new(Driver::Generic(Generic::default()));
+    let input = input(1);
+
+    let before = bench("owned typed parse, 1 epoch", || {
+        let mut rest = &input[..];
+
+        while let Ok((r, nmea)) = parser.parse(rest, Duration::ZERO) {
+            assert!(!matches!(nmea, NMEA::ParseError(_) | NMEA::Unsupported(_)));
+            black_box(nmea);
+            rest = r;
+        }
+    });
+
+    let after = bench("borrowed typed parse, 1 epoch", || {
+        let mut rest = &input[..];
+
+        while let Ok((r, nmea)) = view(rest, ParseProfile::default()) {
+            if let NMEAView::Sentence(sentence) = nmea {
+                black_box(sentence.typed().unwrap());
+            }
+            rest = r;
+        }
+    });
+
+    compare("typed view speedup", before, after);
+}
+
+fn main() {
+    bench_decode(1);
+    bench_decode(10);
+    bench_decode(100);
+    bench_view();
+}
@@ -0,0 +1,20 @@
+use std::time::SystemTime;
+
+// The build time is the default pivot for GPS week number rollover correction
+fn main() {
+    let build_time = match std::env::var("SOURCE_DATE_EPOCH") {
+        Ok(epoch) => epoch,
+        Err(_) => SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            .to_string(),
+    };
+
+    println!("cargo:rustc-env=WHERE_AM_I_BUILD_TIME={}", build_time);
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    // Any rerun-if line replaces cargo's default of rerunning on every package change
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    println!("cargo:rerun-if-changed=src");
+}
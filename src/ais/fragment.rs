@@ -0,0 +1,79 @@
+use crate::nmea::{
+    parser::{talker, Result},
+    parser_util::*,
+    Talker,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while},
+    character::complete::one_of,
+    combinator::{map, opt, value},
+    sequence::{preceded, tuple},
+};
+use std::time::Duration;
+
+/// One `!AIVDM` or `!AIVDO` sentence carrying all or part of an AIS message
+#[derive(Clone, Debug, PartialEq)]
+pub struct AISFragment {
+    pub received: Option<Duration>,
+    pub talker: Talker,
+    /// VDO sentences report the receiver's own vessel
+    pub own_vessel: bool,
+    pub fragment_count: u32,
+    /// Fragment number, starting from 1
+    pub fragment: u32,
+    /// Sequential message ID linking fragments of a multi-sentence message
+    pub message_id: Option<u32>,
+    /// Radio channel, `A` or `B` (sometimes `1` or `2`)
+    pub channel: Option<char>,
+    /// 6-bit armored payload
+    pub payload: String,
+    /// Bits of padding at the end of the payload
+    pub fill_bits: u32,
+}
+
+pub(crate) fn fragment(input: &str) -> Result<&str, AISFragment> {
+    parse_message(
+        "VDM",
+        tuple((
+            talker,
+            alt((value(false, tag("VDM")), value(true, tag("VDO")))),
+            preceded(comma, uint32),
+            preceded(comma, uint32),
+            preceded(comma, opt(uint32)),
+            preceded(comma, opt(one_of("AB12"))),
+            preceded(comma, payload),
+            preceded(comma, map(one_of("012345"), |c| c as u32 - '0' as u32)),
+        )),
+        |(
+            talker,
+            own_vessel,
+            fragment_count,
+            fragment,
+            message_id,
+            channel,
+            payload,
+            fill_bits,
+        )| {
+            AISFragment {
+                received: None,
+                talker,
+                own_vessel,
+                fragment_count,
+                fragment,
+                message_id,
+                channel,
+                payload,
+                fill_bits,
+            }
+        },
+    )(input)
+}
+
+fn payload(input: &str) -> Result<&str, String> {
+    map(take_while(is_armored), |p: &str| p.to_string())(input)
+}
+
+fn is_armored(c: char) -> bool {
+    ('0'..='W').contains(&c) || ('`'..='w').contains(&c)
+}
@@ -0,0 +1,436 @@
+use crate::ais::Payload;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::time::Duration;
+
+/// A decoded AIS message
+#[derive(Clone, Debug, PartialEq)]
+pub struct AISMessage {
+    /// When the first fragment of the message was received
+    pub received: Option<Duration>,
+    /// VDO messages report the receiver's own vessel
+    pub own_vessel: bool,
+    pub message_type: u32,
+    pub repeat: u32,
+    pub mmsi: u32,
+    pub report: AISReport,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AISReport {
+    /// Types 1, 2, and 3
+    Position(AISPositionReport),
+    /// Type 4
+    BaseStation(AISBaseStationReport),
+    /// Type 5
+    StaticAndVoyage(AISStaticAndVoyage),
+    /// Type 18
+    ClassBPosition(AISClassBPositionReport),
+    /// Type 19
+    ExtendedClassBPosition(AISExtendedClassBPositionReport),
+    /// Type 21
+    AidToNavigation(AISAidToNavigationReport),
+    /// Type 24
+    StaticData(AISStaticDataReport),
+    /// A message type that isn't decoded, only the header is available
+    Unsupported,
+}
+
+/// Distances in meters from the position reference point to the edges of a vessel or aid
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AISDimensions {
+    pub to_bow: u32,
+    pub to_stern: u32,
+    pub to_port: u32,
+    pub to_starboard: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AISPositionReport {
+    /// Navigation status, 0 is under way using engine, 15 is not defined
+    pub status: u32,
+    /// Rate of turn indicator, None when not available
+    pub turn: Option<i32>,
+    /// Speed over ground in knots
+    pub speed: Option<f32>,
+    /// Position accuracy is better than 10 meters
+    pub accuracy: bool,
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+    /// Course over ground in degrees
+    pub course: Option<f32>,
+    /// True heading in degrees
+    pub heading: Option<u32>,
+    /// Second of the UTC minute of the report, 60 and higher are unavailable
+    pub second: u32,
+    pub maneuver: u32,
+    pub raim: bool,
+    pub radio: u32,
+}
+
+impl AISPositionReport {
+    /// Rate of turn in degrees per minute, negative when turning to port.  None when not
+    /// available or turning faster than 5° per 30 seconds.
+    pub fn rate_of_turn(&self) -> Option<f32> {
+        match self.turn {
+            Some(turn) if turn.abs() < 127 => {
+                let rate = (turn as f32 / 4.733).powi(2);
+
+                Some(rate.copysign(turn as f32))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AISBaseStationReport {
+    pub timestamp: Option<DateTime<Utc>>,
+    pub accuracy: bool,
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+    /// Type of position fixing device
+    pub epfd: u32,
+    pub raim: bool,
+    pub radio: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AISStaticAndVoyage {
+    pub ais_version: u32,
+    pub imo: u32,
+    pub callsign: String,
+    pub shipname: String,
+    pub shiptype: u32,
+    pub dimensions: AISDimensions,
+    pub epfd: u32,
+    /// Estimated time of arrival month, 0 when not available
+    pub eta_month: u32,
+    /// Estimated time of arrival day, 0 when not available
+    pub eta_day: u32,
+    /// Estimated time of arrival hour, 24 when not available
+    pub eta_hour: u32,
+    /// Estimated time of arrival minute, 60 when not available
+    pub eta_minute: u32,
+    /// Draught in meters
+    pub draught: f32,
+    pub destination: String,
+    /// Data terminal equipment is not ready
+    pub dte: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AISClassBPositionReport {
+    pub speed: Option<f32>,
+    pub accuracy: bool,
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+    pub course: Option<f32>,
+    pub heading: Option<u32>,
+    pub second: u32,
+    /// Carrier-sense unit rather than SOTDMA
+    pub cs: bool,
+    pub display: bool,
+    pub dsc: bool,
+    pub band: bool,
+    pub msg22: bool,
+    pub assigned: bool,
+    pub raim: bool,
+    pub radio: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AISExtendedClassBPositionReport {
+    pub speed: Option<f32>,
+    pub accuracy: bool,
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+    pub course: Option<f32>,
+    pub heading: Option<u32>,
+    pub second: u32,
+    pub shipname: String,
+    pub shiptype: u32,
+    pub dimensions: AISDimensions,
+    pub epfd: u32,
+    pub raim: bool,
+    pub dte: bool,
+    pub assigned: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AISAidToNavigationReport {
+    pub aid_type: u32,
+    /// Name including the name extension
+    pub name: String,
+    pub accuracy: bool,
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+    pub dimensions: AISDimensions,
+    pub epfd: u32,
+    pub second: u32,
+    pub off_position: bool,
+    pub raim: bool,
+    pub virtual_aid: bool,
+    pub assigned: bool,
+}
+
+/// Class B static data, sent as separate part A and part B messages
+#[derive(Clone, Debug, PartialEq)]
+pub enum AISStaticDataReport {
+    PartA {
+        shipname: String,
+    },
+    PartB {
+        shiptype: u32,
+        vendor_id: String,
+        model: u32,
+        serial: u32,
+        callsign: String,
+        /// Dimensions, None for auxiliary craft
+        dimensions: Option<AISDimensions>,
+        /// MMSI of the mother ship, only for auxiliary craft
+        mothership_mmsi: Option<u32>,
+    },
+}
+
+/// Decodes an assembled AIS payload.  Returns None if the payload is too short for its message
+/// type.
+pub fn decode(payload: &str, fill_bits: u32) -> Option<AISReport> {
+    let bits = Payload::new(payload, fill_bits);
+
+    let report = match bits.uint(0, 6) {
+        1..=3 if bits.len() >= 168 => AISReport::Position(position(&bits)),
+        4 if bits.len() >= 168 => AISReport::BaseStation(base_station(&bits)),
+        5 if bits.len() >= 420 => AISReport::StaticAndVoyage(static_and_voyage(&bits)),
+        18 if bits.len() >= 168 => AISReport::ClassBPosition(class_b_position(&bits)),
+        19 if bits.len() >= 312 => {
+            AISReport::ExtendedClassBPosition(extended_class_b_position(&bits))
+        }
+        21 if bits.len() >= 272 => AISReport::AidToNavigation(aid_to_navigation(&bits)),
+        24 if bits.len() >= 160 => AISReport::StaticData(static_data(&bits)?),
+        1..=5 | 18 | 19 | 21 | 24 => return None,
+        _ if bits.len() >= 38 => AISReport::Unsupported,
+        _ => return None,
+    };
+
+    Some(report)
+}
+
+/// Decodes the header and report of an assembled AIS payload
+pub(crate) fn message(
+    payload: &str,
+    fill_bits: u32,
+    own_vessel: bool,
+    received: Option<Duration>,
+) -> Option<AISMessage> {
+    let report = decode(payload, fill_bits)?;
+    let bits = Payload::new(payload, fill_bits);
+
+    Some(AISMessage {
+        received,
+        own_vessel,
+        message_type: bits.uint(0, 6),
+        repeat: bits.uint(6, 2),
+        mmsi: bits.uint(8, 30),
+        report,
+    })
+}
+
+fn position(bits: &Payload) -> AISPositionReport {
+    let turn = bits.int(42, 8);
+
+    AISPositionReport {
+        status: bits.uint(38, 4),
+        turn: if turn == -128 { None } else { Some(turn) },
+        speed: speed(bits.uint(50, 10)),
+        accuracy: bits.flag(60),
+        longitude: longitude(bits.int(61, 28)),
+        latitude: latitude(bits.int(89, 27)),
+        course: course(bits.uint(116, 12)),
+        heading: heading(bits.uint(128, 9)),
+        second: bits.uint(137, 6),
+        maneuver: bits.uint(143, 2),
+        raim: bits.flag(148),
+        radio: bits.uint(149, 19),
+    }
+}
+
+fn base_station(bits: &Payload) -> AISBaseStationReport {
+    let timestamp =
+        NaiveDate::from_ymd_opt(bits.uint(38, 14) as i32, bits.uint(52, 4), bits.uint(56, 5))
+            .and_then(|date| date.and_hms_opt(bits.uint(61, 5), bits.uint(66, 6), bits.uint(72, 6)))
+            .map(|time| DateTime::from_utc(time, Utc));
+
+    AISBaseStationReport {
+        timestamp,
+        accuracy: bits.flag(78),
+        longitude: longitude(bits.int(79, 28)),
+        latitude: latitude(bits.int(107, 27)),
+        epfd: bits.uint(134, 4),
+        raim: bits.flag(148),
+        radio: bits.uint(149, 19),
+    }
+}
+
+fn static_and_voyage(bits: &Payload) -> AISStaticAndVoyage {
+    AISStaticAndVoyage {
+        ais_version: bits.uint(38, 2),
+        imo: bits.uint(40, 30),
+        callsign: bits.text(70, 42),
+        shipname: bits.text(112, 120),
+        shiptype: bits.uint(232, 8),
+        dimensions: dimensions(bits, 240),
+        epfd: bits.uint(270, 4),
+        eta_month: bits.uint(274, 4),
+        eta_day: bits.uint(278, 5),
+        eta_hour: bits.uint(283, 5),
+        eta_minute: bits.uint(288, 6),
+        draught: bits.uint(294, 8) as f32 / 10.0,
+        destination: bits.text(302, 120),
+        dte: bits.flag(422),
+    }
+}
+
+fn class_b_position(bits: &Payload) -> AISClassBPositionReport {
+    AISClassBPositionReport {
+        speed: speed(bits.uint(46, 10)),
+        accuracy: bits.flag(56),
+        longitude: longitude(bits.int(57, 28)),
+        latitude: latitude(bits.int(85, 27)),
+        course: course(bits.uint(112, 12)),
+        heading: heading(bits.uint(124, 9)),
+        second: bits.uint(133, 6),
+        cs: bits.flag(141),
+        display: bits.flag(142),
+        dsc: bits.flag(143),
+        band: bits.flag(144),
+        msg22: bits.flag(145),
+        assigned: bits.flag(146),
+        raim: bits.flag(147),
+        radio: bits.uint(148, 20),
+    }
+}
+
+fn extended_class_b_position(bits: &Payload) -> AISExtendedClassBPositionReport {
+    AISExtendedClassBPositionReport {
+        speed: speed(bits.uint(46, 10)),
+        accuracy: bits.flag(56),
+        longitude: longitude(bits.int(57, 28)),
+        latitude: latitude(bits.int(85, 27)),
+        course: course(bits.uint(112, 12)),
+        heading: heading(bits.uint(124, 9)),
+        second: bits.uint(133, 6),
+        shipname: bits.text(143, 120),
+        shiptype: bits.uint(263, 8),
+        dimensions: dimensions(bits, 271),
+        epfd: bits.uint(301, 4),
+        raim: bits.flag(305),
+        dte: bits.flag(306),
+        assigned: bits.flag(307),
+    }
+}
+
+fn aid_to_navigation(bits: &Payload) -> AISAidToNavigationReport {
+    // up to 88 bits of name extension follow the fixed fields
+    let extension = bits.len().saturating_sub(272).min(88);
+    let name = bits.text(43, 120) + &bits.text(272, extension);
+
+    AISAidToNavigationReport {
+        aid_type: bits.uint(38, 5),
+        name,
+        accuracy: bits.flag(163),
+        longitude: longitude(bits.int(164, 28)),
+        latitude: latitude(bits.int(192, 27)),
+        dimensions: dimensions(bits, 219),
+        epfd: bits.uint(249, 4),
+        second: bits.uint(253, 6),
+        off_position: bits.flag(259),
+        raim: bits.flag(268),
+        virtual_aid: bits.flag(269),
+        assigned: bits.flag(270),
+    }
+}
+
+fn static_data(bits: &Payload) -> Option<AISStaticDataReport> {
+    let report = match bits.uint(38, 2) {
+        0 => AISStaticDataReport::PartA {
+            shipname: bits.text(40, 120),
+        },
+        1 if bits.len() >= 162 => {
+            let mmsi = bits.uint(8, 30);
+
+            // auxiliary craft report their mother ship instead of their dimensions
+            let (dimensions, mothership_mmsi) = match mmsi / 10_000_000 {
+                98 => (None, Some(bits.uint(132, 30))),
+                _ => (Some(dimensions(bits, 132)), None),
+            };
+
+            AISStaticDataReport::PartB {
+                shiptype: bits.uint(40, 8),
+                vendor_id: bits.text(48, 18),
+                model: bits.uint(66, 4),
+                serial: bits.uint(70, 20),
+                callsign: bits.text(90, 42),
+                dimensions,
+                mothership_mmsi,
+            }
+        }
+        _ => return None,
+    };
+
+    Some(report)
+}
+
+fn dimensions(bits: &Payload, start: usize) -> AISDimensions {
+    AISDimensions {
+        to_bow: bits.uint(start, 9),
+        to_stern: bits.uint(start + 9, 9),
+        to_port: bits.uint(start + 18, 6),
+        to_starboard: bits.uint(start + 24, 6),
+    }
+}
+
+// 1/10 knot, 1023 is not available
+fn speed(raw: u32) -> Option<f32> {
+    match raw {
+        1023 => None,
+        s => Some(s as f32 / 10.0),
+    }
+}
+
+// 1/10 degree, 3600 is not available
+fn course(raw: u32) -> Option<f32> {
+    match raw {
+        0..=3599 => Some(raw as f32 / 10.0),
+        _ => None,
+    }
+}
+
+// degrees, 511 is not available
+fn heading(raw: u32) -> Option<u32> {
+    match raw {
+        0..=359 => Some(raw),
+        _ => None,
+    }
+}
+
+// 1/10000 minute, 181 degrees is not available
+fn longitude(raw: i32) -> Option<f64> {
+    let degrees = raw as f64 / 600_000.0;
+
+    if degrees.abs() <= 180.0 {
+        Some(degrees)
+    } else {
+        None
+    }
+}
+
+// 1/10000 minute, 91 degrees is not available
+fn latitude(raw: i32) -> Option<f64> {
+    let degrees = raw as f64 / 600_000.0;
+
+    if degrees.abs() <= 90.0 {
+        Some(degrees)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,27 @@
+// For AIS messages from combined GPS/AIS receivers, encapsulated in !AIVDM and !AIVDO sentences
+
+pub(crate) mod fragment;
+mod message;
+mod payload;
+
+pub use fragment::AISFragment;
+pub use message::decode;
+pub use message::AISAidToNavigationReport;
+pub use message::AISBaseStationReport;
+pub use message::AISClassBPositionReport;
+pub use message::AISDimensions;
+pub use message::AISExtendedClassBPositionReport;
+pub use message::AISMessage;
+pub use message::AISPositionReport;
+pub use message::AISReport;
+pub use message::AISStaticAndVoyage;
+pub use message::AISStaticDataReport;
+pub use payload::Payload;
+
+pub(crate) use message::message;
+
+#[cfg(test)]
+mod test_fragment;
+
+#[cfg(test)]
+mod test_message;
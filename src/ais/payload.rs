@@ -0,0 +1,81 @@
+/// The bits of a de-armored AIS payload.
+///
+/// Fields past the end of the payload read as zero, some transmitters truncate the spare bits at
+/// the end of a message.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Payload {
+    sixbits: Vec<u8>,
+    len: usize,
+}
+
+impl Payload {
+    /// De-armors `payload`, dropping `fill_bits` from the end
+    pub fn new(payload: &str, fill_bits: u32) -> Self {
+        let sixbits: Vec<u8> = payload
+            .bytes()
+            .map(|c| {
+                let value = c.wrapping_sub(48);
+
+                if value > 40 {
+                    value - 8
+                } else {
+                    value
+                }
+            })
+            .map(|v| v & 0x3F)
+            .collect();
+
+        let len = (sixbits.len() * 6).saturating_sub(fill_bits as usize);
+
+        Payload { sixbits, len }
+    }
+
+    /// Number of bits in the payload
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn bit(&self, index: usize) -> u32 {
+        if index >= self.len {
+            return 0;
+        }
+
+        let sixbit = self.sixbits[index / 6];
+
+        ((sixbit >> (5 - index % 6)) & 1) as u32
+    }
+
+    /// Unsigned integer of `bits` bits starting at bit `start`
+    pub fn uint(&self, start: usize, bits: usize) -> u32 {
+        (start..start + bits).fold(0, |value, i| (value << 1) | self.bit(i))
+    }
+
+    /// Two's complement integer of `bits` bits starting at bit `start`
+    pub fn int(&self, start: usize, bits: usize) -> i32 {
+        let value = self.uint(start, bits);
+        let shift = 32 - bits;
+
+        ((value << shift) as i32) >> shift
+    }
+
+    pub fn flag(&self, start: usize) -> bool {
+        self.bit(start) == 1
+    }
+
+    /// Text of `bits / 6` 6-bit characters starting at bit `start`, without trailing `@` padding
+    /// or spaces
+    pub fn text(&self, start: usize, bits: usize) -> String {
+        let text: String = (0..bits / 6)
+            .map(|i| self.uint(start + i * 6, 6) as u8)
+            .map(|c| if c < 32 { c + 64 } else { c } as char)
+            .collect();
+
+        let end = text.find('@').unwrap_or(text.len());
+
+        text[..end].trim_end().to_string()
+    }
+}
@@ -0,0 +1,61 @@
+use crate::ais::fragment::fragment;
+use crate::nmea::{view, NMEAView, ParseProfile, Talker};
+
+#[test]
+fn test_fragment() {
+    let parsed =
+        fragment("AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0")
+            .unwrap()
+            .1;
+
+    assert_eq!(Talker::AIS, parsed.talker);
+    assert!(!parsed.own_vessel);
+    assert_eq!(2, parsed.fragment_count);
+    assert_eq!(1, parsed.fragment);
+    assert_eq!(Some(1), parsed.message_id);
+    assert_eq!(Some('A'), parsed.channel);
+    assert_eq!(
+        "55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8",
+        parsed.payload
+    );
+    assert_eq!(0, parsed.fill_bits);
+
+    let parsed = fragment("AIVDO,1,1,,,15M67FC000G?ufbE`FepT@3n00Sa,0")
+        .unwrap()
+        .1;
+
+    assert!(parsed.own_vessel);
+    assert_eq!(None, parsed.message_id);
+    assert_eq!(None, parsed.channel);
+}
+
+#[test]
+fn test_fragment_invalid() {
+    // fill bits are at most 5
+    assert!(fragment("AIVDM,1,1,,B,15M67FC000G?ufbE`FepT@3n00Sa,6").is_err());
+    // x is not a 6-bit armored character
+    assert!(fragment("AIVDM,1,1,,B,15M67FC000G?ufbExFepT@3n00Sa,0").is_err());
+    assert!(fragment("GPGGA,1,1,,B,15M67FC000G?ufbE`FepT@3n00Sa,0").is_err());
+}
+
+#[test]
+fn test_view_encapsulated() {
+    let input = b"!AIVDM,1,1,,B,15M67FC000G?ufbE`FepT@3n00Sa,0*5C\r\n$EIGAQ,RMC*2B\r\n";
+
+    let (rest, parsed) = view(input, ParseProfile::default()).unwrap();
+
+    match parsed {
+        NMEAView::Encapsulated(sentence) => {
+            assert_eq!("AIVDM", sentence.address());
+            assert_eq!(Some("15M67FC000G?ufbE`FepT@3n00Sa"), sentence.field(4));
+        }
+        other => panic!("expected encapsulated sentence, got {:?}", other),
+    }
+
+    assert_eq!(b"$EIGAQ,RMC*2B\r\n", rest);
+
+    // encapsulated sentences are not garbage
+    let (_, parsed) = view(b"xx!AIVDM", ParseProfile::default()).unwrap();
+
+    assert!(matches!(parsed, NMEAView::Invalid { data: b"xx", .. }));
+}
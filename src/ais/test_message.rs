@@ -0,0 +1,220 @@
+use crate::ais::{decode, message, AISDimensions, AISReport, AISStaticDataReport, Payload};
+use chrono::{TimeZone, Utc};
+
+#[test]
+fn test_payload() {
+    let payload = Payload::new("15M67FC000G?ufbE`FepT@3n00Sa", 0);
+
+    assert_eq!(168, payload.len());
+    assert_eq!(1, payload.uint(0, 6));
+    assert_eq!(366053209, payload.uint(8, 30));
+    assert_eq!(-73404971, payload.int(61, 28));
+
+    // past the end reads as zero
+    assert_eq!(0, payload.uint(168, 12));
+
+    let payload = Payload::new("88888888880", 2);
+
+    assert_eq!(64, payload.len());
+}
+
+#[test]
+fn test_position_report() {
+    let message = message("15M67FC000G?ufbE`FepT@3n00Sa", 0, false, None).unwrap();
+
+    assert_eq!(1, message.message_type);
+    assert_eq!(0, message.repeat);
+    assert_eq!(366053209, message.mmsi);
+
+    let report = match message.report {
+        AISReport::Position(p) => p,
+        other => panic!("expected position report, got {:?}", other),
+    };
+
+    assert_eq!(3, report.status);
+    assert_eq!(Some(0), report.turn);
+    assert_eq!(Some(0.0), report.rate_of_turn());
+    assert_eq!(Some(0.0), report.speed);
+    assert!(!report.accuracy);
+    assert_approx_eq!(-122.341618, report.longitude.unwrap(), 1e-6);
+    assert_approx_eq!(37.802118, report.latitude.unwrap(), 1e-6);
+    assert_eq!(Some(219.3), report.course);
+    assert_eq!(Some(1), report.heading);
+    assert_eq!(59, report.second);
+    assert_eq!(2281, report.radio);
+}
+
+#[test]
+fn test_base_station_report() {
+    let report = match decode("403OviQuMGCqWrRO9@E6fDo00@GO", 0).unwrap() {
+        AISReport::BaseStation(b) => b,
+        other => panic!("expected base station report, got {:?}", other),
+    };
+
+    assert_eq!(
+        Some(Utc.ymd(2007, 5, 14).and_hms(19, 57, 39)),
+        report.timestamp
+    );
+    assert!(report.accuracy);
+    assert_approx_eq!(-76.352361, report.longitude.unwrap(), 1e-5);
+    assert_approx_eq!(36.883766, report.latitude.unwrap(), 1e-5);
+    assert_eq!(7, report.epfd);
+    assert_eq!(67039, report.radio);
+}
+
+#[test]
+fn test_static_and_voyage() {
+    let payload = "55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp888888888880";
+
+    let report = match decode(payload, 2).unwrap() {
+        AISReport::StaticAndVoyage(s) => s,
+        other => panic!("expected static and voyage data, got {:?}", other),
+    };
+
+    assert_eq!(9134270, report.imo);
+    assert_eq!("3FOF8", report.callsign);
+    assert_eq!("EVER DIADEM", report.shipname);
+    assert_eq!(70, report.shiptype);
+    assert_eq!(
+        AISDimensions {
+            to_bow: 225,
+            to_stern: 70,
+            to_port: 1,
+            to_starboard: 31,
+        },
+        report.dimensions
+    );
+    assert_eq!(5, report.eta_month);
+    assert_eq!(15, report.eta_day);
+    assert_eq!(14, report.eta_hour);
+    assert_eq!(0, report.eta_minute);
+    assert_approx_eq!(12.2, report.draught);
+    assert_eq!("NEW YORK", report.destination);
+
+    // the second fragment is missing
+    assert_eq!(
+        None,
+        decode(
+            "55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8",
+            0
+        )
+    );
+}
+
+#[test]
+fn test_class_b_position_report() {
+    let message = message("B5NJ;PP005l4ot5Isbl03wsUkP06", 0, false, None).unwrap();
+
+    assert_eq!(18, message.message_type);
+    assert_eq!(367430530, message.mmsi);
+
+    let report = match message.report {
+        AISReport::ClassBPosition(b) => b,
+        other => panic!("expected class B position report, got {:?}", other),
+    };
+
+    assert_eq!(Some(0.0), report.speed);
+    assert_approx_eq!(-122.26732, report.longitude.unwrap(), 1e-6);
+    assert_approx_eq!(37.785035, report.latitude.unwrap(), 1e-6);
+    assert_eq!(Some(0.0), report.course);
+    assert_eq!(None, report.heading);
+    assert_eq!(55, report.second);
+    assert!(report.cs);
+    assert_eq!(917510, report.radio);
+}
+
+#[test]
+fn test_extended_class_b_position_report() {
+    let payload = "C5N3SRP0EnJGBm4>N`HDwwo062PaLELTBJ:V00000000S0D:R22P";
+
+    let report = match decode(payload, 0).unwrap() {
+        AISReport::ExtendedClassBPosition(b) => b,
+        other => panic!("expected extended class B position report, got {:?}", other),
+    };
+
+    assert_eq!(Some(8.7), report.speed);
+    assert_approx_eq!(-88.810918, report.longitude.unwrap(), 1e-5);
+    assert_approx_eq!(29.543478, report.latitude.unwrap(), 1e-5);
+    assert_eq!(Some(33.5), report.course);
+    assert_eq!(None, report.heading);
+    assert_eq!(46, report.second);
+    assert_eq!("CAPT.J.RIMES", report.shipname);
+    assert_eq!(70, report.shiptype);
+    assert_eq!(5, report.dimensions.to_bow);
+    assert_eq!(1, report.epfd);
+    assert!(report.dte);
+}
+
+#[test]
+fn test_aid_to_navigation_report() {
+    let payload = "E>kb9O0W0c@1:Wdh7:VQ2a@:;RWMN`nP;kCn000003vP150";
+
+    let report = match decode(payload, 4).unwrap() {
+        AISReport::AidToNavigation(a) => a,
+        other => panic!("expected aid to navigation report, got {:?}", other),
+    };
+
+    assert_eq!(1, report.aid_type);
+    assert_eq!("NAV BUOY NUMBER TWENT", report.name);
+    assert!(report.accuracy);
+    assert_approx_eq!(-70.5, report.longitude.unwrap(), 1e-6);
+    assert_approx_eq!(41.25, report.latitude.unwrap(), 1e-6);
+    assert_eq!(61, report.second);
+    assert!(report.virtual_aid);
+}
+
+#[test]
+fn test_static_data_report() {
+    match decode("H52KMeALThhT4m<000000000000", 2).unwrap() {
+        AISReport::StaticData(AISStaticDataReport::PartA { shipname }) => {
+            assert_eq!("WILLIAMS", shipname)
+        }
+        other => panic!("expected static data part A, got {:?}", other),
+    };
+
+    match decode("H52KMeDUCBD830qG45linm104220", 0).unwrap() {
+        AISReport::StaticData(AISStaticDataReport::PartB {
+            shiptype,
+            vendor_id,
+            model,
+            serial,
+            callsign,
+            dimensions,
+            mothership_mmsi,
+        }) => {
+            assert_eq!(37, shiptype);
+            assert_eq!("SRT", vendor_id);
+            assert_eq!(2, model);
+            assert_eq!(12345, serial);
+            assert_eq!("WDE4165", callsign);
+            assert_eq!(
+                Some(AISDimensions {
+                    to_bow: 8,
+                    to_stern: 4,
+                    to_port: 2,
+                    to_starboard: 2,
+                }),
+                dimensions
+            );
+            assert_eq!(None, mothership_mmsi);
+        }
+        other => panic!("expected static data part B, got {:?}", other),
+    };
+}
+
+#[test]
+fn test_unsupported() {
+    // type 8, binary broadcast
+    let message = message(
+        "85Mwp`1Kf3aCnsNvBWLi=wQuNhA5t43N`5nCuI=p<IBfVqnMgPGs",
+        0,
+        false,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(8, message.message_type);
+    assert_eq!(AISReport::Unsupported, message.report);
+
+    assert_eq!(None, decode("15M67", 0));
+}
@@ -42,12 +42,7 @@ async fn main() -> Result<()> {
 
     while let Ok(nmea) = rx.recv().await {
         match nmea {
-            NMEA::InvalidChecksum(cm) => error!(
-                "checksum match, given {}, calculated {} on {}",
-                cm.given, cm.calculated, cm.message
-            ),
             NMEA::ParseError(e) => error!("parse error: {}", e),
-            NMEA::ParseFailure(f) => error!("parse failure: {}", f),
             NMEA::Unsupported(n) => error!("unsupported: {}", n),
             n => info!("{:?}", n),
         }
@@ -0,0 +1,32 @@
+use crate::configuration::ConfigurationError;
+
+use serde::Deserialize;
+use std::convert::TryFrom;
+
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[serde(try_from = "BaudRateValue")]
+pub enum BaudRate {
+    /// Detect the baud rate from the sentences the receiver sends
+    Auto,
+    Rate(u32),
+}
+
+// A baud rate is written as a number or "auto"
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BaudRateValue {
+    Rate(u32),
+    Name(String),
+}
+
+impl TryFrom<BaudRateValue> for BaudRate {
+    type Error = ConfigurationError;
+
+    fn try_from(value: BaudRateValue) -> Result<Self, Self::Error> {
+        match value {
+            BaudRateValue::Rate(rate) => Ok(BaudRate::Rate(rate)),
+            BaudRateValue::Name(name) if name == "auto" => Ok(BaudRate::Auto),
+            BaudRateValue::Name(name) => Err(ConfigurationError::InvalidBaudRate(name)),
+        }
+    }
+}
@@ -2,6 +2,8 @@ use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum ConfigurationError {
+    #[error("baud rate {0} must be a number or auto")]
+    InvalidBaudRate(String),
     #[error("data bits {0} must be 8, 7, 6, or 5")]
     InvalidDataBits(char),
     #[error("flow control {0} must be H(ardware), S(oftware), or N(one)")]
@@ -12,6 +14,12 @@ pub enum ConfigurationError {
     InvalidLogFilter(String, tracing_subscriber::filter::ParseError),
     #[error("parity {0} must be N(one), O(dd), or E(ven)")]
     InvalidParity(char),
+    #[error("rollover epoch {0} must be build or a YYYY-MM-DD date")]
+    InvalidRolloverEpoch(String),
+    #[error("system {0} must be GPS, GLONASS, Galileo, BeiDou, QZSS, or NavIC")]
+    InvalidSystem(String),
+    #[error("timing sentence {0} must be ZDA, RMC, GGA, GNS, PUBX,04, or first")]
+    InvalidTimingSentence(String),
     #[error("parity {0} must be 1 or 2")]
     InvalidStopBits(char),
     #[error("invalid configuration file: {0}")]
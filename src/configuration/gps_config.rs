@@ -1,6 +1,15 @@
+use crate::configuration::BaudRate;
 use crate::configuration::ConfigurationError;
+use crate::configuration::MKTConfig;
 use crate::configuration::PpsConfig;
+use crate::configuration::QuectelConfig;
+use crate::configuration::RolloverEpoch;
+use crate::configuration::TimeQualityConfig;
+use crate::configuration::TimingSentence;
+use crate::configuration::UBloxConfig;
 use crate::gps::GpsType;
+use crate::nmea::ParseProfile;
+use crate::nmea::System;
 
 use serde::Deserialize;
 
@@ -19,15 +28,45 @@ pub struct GpsConfig {
     pub device: String,
     pub gps_type: GpsType,
     pub pps: Option<PpsConfig>,
-    pub baud_rate: Option<u32>,
+    pub baud_rate: Option<BaudRate>,
+    pub parse_profile: Option<ParseProfile>,
     pub framing: Option<String>,
     pub flow_control: Option<String>,
     pub timeout: Option<u32>,
     pub messages: Option<Vec<String>>,
     pub ntp_unit: Option<i32>,
+    pub quarantine: Option<String>,
+    pub rollover_epoch: Option<RolloverEpoch>,
+    pub leap_seconds_file: Option<String>,
+    pub systems: Option<Vec<System>>,
+    pub timing_sentence: Option<TimingSentence>,
+    pub time_quality: Option<TimeQualityConfig>,
+    pub ublox: Option<UBloxConfig>,
+    pub quectel: Option<QuectelConfig>,
+    pub mkt: Option<MKTConfig>,
 }
 
 impl GpsConfig {
+    pub fn auto_baud_rate(&self) -> bool {
+        matches!(self.baud_rate, Some(BaudRate::Auto))
+    }
+
+    pub fn parse_profile(&self) -> ParseProfile {
+        self.parse_profile.unwrap_or_default()
+    }
+
+    pub fn rollover_epoch(&self) -> RolloverEpoch {
+        self.rollover_epoch.unwrap_or_default()
+    }
+
+    pub fn timing_sentence(&self) -> TimingSentence {
+        self.timing_sentence.unwrap_or_default()
+    }
+
+    pub fn time_quality(&self) -> TimeQualityConfig {
+        self.time_quality.clone().unwrap_or_default()
+    }
+
     pub fn messages(&self) -> Vec<String> {
         match &self.messages {
             Some(m) => m.clone(),
@@ -43,7 +82,8 @@ impl TryFrom<GpsConfig> for SerialPortBuilder {
         let path = device.device;
         let mut baud_rate = 38400;
 
-        if let Some(b) = device.baud_rate {
+        // automatic detection starts from the default
+        if let Some(BaudRate::Rate(b)) = device.baud_rate {
             baud_rate = b;
         }
 
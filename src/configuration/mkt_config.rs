@@ -0,0 +1,20 @@
+use crate::gps::{MKTPPSMode, MKTRestart};
+
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct MKTConfig {
+    pub restart: Option<MKTRestart>,
+    pub fix_interval: Option<u32>,
+    pub pps_sync: Option<bool>,
+    pub pps: Option<MKTPPSMode>,
+    pub pps_pulse_width: Option<u32>,
+    pub baud_rate: Option<u32>,
+}
+
+impl MKTConfig {
+    /// PPS pulse width in milliseconds, defaults to 100
+    pub fn pps_pulse_width(&self) -> u32 {
+        self.pps_pulse_width.unwrap_or(100)
+    }
+}
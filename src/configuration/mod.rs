@@ -1,14 +1,28 @@
+mod baud_rate;
 mod configuration_error;
 mod gps_config;
 mod gpsd_config;
+mod mkt_config;
 mod pps_config;
 mod prometheus_config;
+mod quectel_config;
+mod rollover_epoch;
+mod time_quality_config;
+mod timing_sentence;
+mod ublox_config;
 
+pub use baud_rate::BaudRate;
 pub use configuration_error::ConfigurationError;
 pub use gps_config::GpsConfig;
 pub use gpsd_config::GpsdConfig;
+pub use mkt_config::MKTConfig;
 pub use pps_config::PpsConfig;
 pub use prometheus_config::PrometheusConfig;
+pub use quectel_config::QuectelConfig;
+pub use rollover_epoch::RolloverEpoch;
+pub use time_quality_config::TimeQualityConfig;
+pub use timing_sentence::TimingSentence;
+pub use ublox_config::UBloxConfig;
 
 use serde::Deserialize;
 use std::{convert::TryFrom, fs, path::Path};
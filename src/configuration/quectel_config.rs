@@ -0,0 +1,28 @@
+use crate::gps::QuectelPPSMode;
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct QuectelConfig {
+    pub fix_interval: Option<u32>,
+    pub rates: Option<BTreeMap<String, u32>>,
+    pub pps: Option<QuectelPPSMode>,
+    pub pps_pulse_width: Option<u32>,
+}
+
+impl QuectelConfig {
+    /// Output rate for an enabled message in fixes per sentence, defaults to every fix
+    pub fn rate(&self, message: &str) -> u32 {
+        self.rates
+            .as_ref()
+            .and_then(|rates| rates.get(message))
+            .copied()
+            .unwrap_or(1)
+    }
+
+    /// PPS pulse width in milliseconds, defaults to 100
+    pub fn pps_pulse_width(&self) -> u32 {
+        self.pps_pulse_width.unwrap_or(100)
+    }
+}
@@ -0,0 +1,32 @@
+use crate::configuration::ConfigurationError;
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::convert::TryFrom;
+
+/// Dates a receiver reports before the rollover epoch are moved forward by whole GPS week number
+/// rollovers
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(try_from = "String")]
+pub enum RolloverEpoch {
+    /// The date where_am_i was built
+    #[default]
+    Build,
+    Date(NaiveDate),
+}
+
+// A rollover epoch is written as "build" or a YYYY-MM-DD date
+impl TryFrom<String> for RolloverEpoch {
+    type Error = ConfigurationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value == "build" {
+            return Ok(RolloverEpoch::Build);
+        }
+
+        match NaiveDate::parse_from_str(&value, "%Y-%m-%d") {
+            Ok(date) => Ok(RolloverEpoch::Date(date)),
+            Err(_) => Err(ConfigurationError::InvalidRolloverEpoch(value)),
+        }
+    }
+}
@@ -1,5 +1,14 @@
 use crate::configuration::*;
 use crate::gps::GpsType;
+use crate::gps::MKTPPSMode;
+use crate::gps::MKTRestart;
+use crate::gps::QuectelPPSMode;
+use crate::gps::UBXPort;
+use crate::nmea::ParseProfile;
+use crate::nmea::System;
+use crate::ubx::cfg::{UBXConstellation, UBXDynamicModel, UBXLayer};
+
+use chrono::NaiveDate;
 
 use std::convert::TryFrom;
 use std::fs;
@@ -71,12 +80,22 @@ device = "/dev/pps1"
         device: "/dev/gps0".to_string(),
         gps_type: GpsType::UBloxNMEA,
         pps: Some(pps0),
-        baud_rate: Some(38400),
+        baud_rate: Some(BaudRate::Rate(38400)),
+        parse_profile: None,
         framing: None,
         flow_control: None,
         timeout: None,
         messages: Some(vec!["ZDA".to_string()]),
         ntp_unit: Some(2),
+        quarantine: None,
+        rollover_epoch: None,
+        leap_seconds_file: None,
+        systems: None,
+        timing_sentence: None,
+        time_quality: None,
+        ublox: None,
+        quectel: None,
+        mkt: None,
     };
 
     let pps1 = PpsConfig {
@@ -90,11 +109,21 @@ device = "/dev/pps1"
         gps_type: GpsType::Generic,
         pps: Some(pps1),
         baud_rate: None,
+        parse_profile: None,
         framing: None,
         flow_control: None,
         timeout: None,
         messages: None,
         ntp_unit: None,
+        quarantine: None,
+        rollover_epoch: None,
+        leap_seconds_file: None,
+        systems: None,
+        timing_sentence: None,
+        time_quality: None,
+        ublox: None,
+        quectel: None,
+        mkt: None,
     };
 
     let expected = Configuration {
@@ -137,12 +166,22 @@ ntp_unit = 2
         device: "/dev/gps0".to_string(),
         gps_type: GpsType::Generic,
         pps: None,
-        baud_rate: Some(38400),
+        baud_rate: Some(BaudRate::Rate(38400)),
+        parse_profile: None,
         framing: None,
         flow_control: None,
         timeout: None,
         messages: Some(vec!["ZDA".to_string()]),
         ntp_unit: Some(2),
+        quarantine: None,
+        rollover_epoch: None,
+        leap_seconds_file: None,
+        systems: None,
+        timing_sentence: None,
+        time_quality: None,
+        ublox: None,
+        quectel: None,
+        mkt: None,
     };
 
     let gpsd = GpsdConfig {
@@ -160,6 +199,197 @@ ntp_unit = 2
     assert_eq!(expected, config);
 }
 
+#[test]
+fn test_config_ublox() {
+    let (_, dir) = write(
+        r#"
+[[gps]]
+name = "GPS0"
+device = "/dev/gps0"
+gps_type = "ublox_nmea"
+
+[gps.ublox]
+port = "uart1"
+layers = [ "ram", "bbr" ]
+clear_layers = [ "flash" ]
+measurement_rate = 500
+navigation_rate = 2
+dynamic_model = "stationary"
+constellations = [ "gps", "galileo" ]
+    "#,
+    )
+    .unwrap();
+
+    let path = dir.path().join("where.toml");
+    let config = Configuration::load(path).unwrap();
+
+    let ublox = UBloxConfig {
+        port: Some(UBXPort::USART1),
+        layers: Some(vec![UBXLayer::RAM, UBXLayer::BBR]),
+        clear_layers: Some(vec![UBXLayer::Flash]),
+        measurement_rate: Some(500),
+        navigation_rate: Some(2),
+        dynamic_model: Some(UBXDynamicModel::Stationary),
+        constellations: Some(vec![UBXConstellation::GPS, UBXConstellation::Galileo]),
+    };
+
+    assert_eq!(Some(ublox), config.gps[0].ublox);
+}
+
+#[test]
+fn test_config_baud_rate_auto() {
+    let (_, dir) = write(
+        r#"
+[[gps]]
+name = "GPS0"
+device = "/dev/gps0"
+gps_type = "generic"
+baud_rate = "auto"
+    "#,
+    )
+    .unwrap();
+
+    let path = dir.path().join("where.toml");
+    let config = Configuration::load(path).unwrap();
+
+    assert_eq!(Some(BaudRate::Auto), config.gps[0].baud_rate);
+    assert!(config.gps[0].auto_baud_rate());
+}
+
+#[test]
+fn test_config_baud_rate_invalid() {
+    let (_, dir) = write(
+        r#"
+[[gps]]
+name = "GPS0"
+device = "/dev/gps0"
+gps_type = "generic"
+baud_rate = "fast"
+    "#,
+    )
+    .unwrap();
+
+    let path = dir.path().join("where.toml");
+
+    assert!(Configuration::load(path).is_err());
+}
+
+#[test]
+fn test_config_gps_type_auto() {
+    let (_, dir) = write(
+        r#"
+[[gps]]
+name = "GPS0"
+device = "/dev/gps0"
+gps_type = "auto"
+    "#,
+    )
+    .unwrap();
+
+    let path = dir.path().join("where.toml");
+    let config = Configuration::load(path).unwrap();
+
+    assert_eq!(GpsType::Auto, config.gps[0].gps_type);
+    assert_eq!("auto", config.gps[0].gps_type.name());
+}
+
+#[test]
+fn test_config_parse_profile() {
+    let (_, dir) = write(
+        r#"
+[[gps]]
+name = "GPS0"
+device = "/dev/gps0"
+gps_type = "generic"
+parse_profile = "nmea2"
+
+[[gps]]
+name = "GPS1"
+device = "/dev/gps1"
+gps_type = "generic"
+    "#,
+    )
+    .unwrap();
+
+    let path = dir.path().join("where.toml");
+    let config = Configuration::load(path).unwrap();
+
+    assert_eq!(ParseProfile::NMEA2, config.gps[0].parse_profile());
+    assert_eq!(ParseProfile::NMEA4, config.gps[1].parse_profile());
+}
+
+#[test]
+fn test_config_mkt() {
+    let (_, dir) = write(
+        r#"
+[[gps]]
+name = "GPS0"
+device = "/dev/gps0"
+gps_type = "mkt"
+
+[gps.mkt]
+restart = "full_cold"
+fix_interval = 200
+pps_sync = true
+pps = "always"
+baud_rate = 115200
+    "#,
+    )
+    .unwrap();
+
+    let path = dir.path().join("where.toml");
+    let config = Configuration::load(path).unwrap();
+
+    let mkt = MKTConfig {
+        restart: Some(MKTRestart::FullCold),
+        fix_interval: Some(200),
+        pps_sync: Some(true),
+        pps: Some(MKTPPSMode::Always),
+        pps_pulse_width: None,
+        baud_rate: Some(115200),
+    };
+
+    assert_eq!(GpsType::MKT, config.gps[0].gps_type);
+    assert_eq!(Some(mkt.clone()), config.gps[0].mkt);
+    assert_eq!(100, mkt.pps_pulse_width());
+}
+
+#[test]
+fn test_config_quectel() {
+    let (_, dir) = write(
+        r#"
+[[gps]]
+name = "GPS0"
+device = "/dev/gps0"
+gps_type = "quectel"
+
+[gps.quectel]
+fix_interval = 200
+pps = "fix_3d"
+pps_pulse_width = 50
+
+[gps.quectel.rates]
+GSV = 5
+    "#,
+    )
+    .unwrap();
+
+    let path = dir.path().join("where.toml");
+    let config = Configuration::load(path).unwrap();
+
+    let quectel = QuectelConfig {
+        fix_interval: Some(200),
+        rates: Some([("GSV".to_string(), 5)].into_iter().collect()),
+        pps: Some(QuectelPPSMode::Fix3D),
+        pps_pulse_width: Some(50),
+    };
+
+    assert_eq!(GpsType::Quectel, config.gps[0].gps_type);
+    assert_eq!(Some(quectel.clone()), config.gps[0].quectel);
+    assert_eq!(5, quectel.rate("GSV"));
+    assert_eq!(1, quectel.rate("GGA"));
+}
+
 #[test]
 fn test_try_from_serial_port_settings() {
     let gps = GpsConfig {
@@ -167,12 +397,22 @@ fn test_try_from_serial_port_settings() {
         device: "/dev/gps0".to_string(),
         gps_type: GpsType::Generic,
         pps: None,
-        baud_rate: Some(38400),
+        baud_rate: Some(BaudRate::Rate(38400)),
+        parse_profile: None,
         framing: Some("7O2".to_string()),
         flow_control: Some("H".to_string()),
         timeout: Some(10),
         messages: None,
         ntp_unit: None,
+        quarantine: None,
+        rollover_epoch: None,
+        leap_seconds_file: None,
+        systems: None,
+        timing_sentence: None,
+        time_quality: None,
+        ublox: None,
+        quectel: None,
+        mkt: None,
     };
 
     let settings = SerialPortBuilder::try_from(gps).unwrap();
@@ -195,11 +435,21 @@ fn test_try_from_serial_port_settings_default() {
         gps_type: GpsType::Generic,
         pps: None,
         baud_rate: None,
+        parse_profile: None,
         framing: None,
         flow_control: None,
         timeout: None,
         messages: None,
         ntp_unit: None,
+        quarantine: None,
+        rollover_epoch: None,
+        leap_seconds_file: None,
+        systems: None,
+        timing_sentence: None,
+        time_quality: None,
+        ublox: None,
+        quectel: None,
+        mkt: None,
     };
 
     let settings = SerialPortBuilder::try_from(gps).unwrap();
@@ -221,12 +471,22 @@ fn test_try_from_serial_port_settings_error() {
         device: "/dev/gps0".to_string(),
         gps_type: GpsType::Generic,
         pps: None,
-        baud_rate: Some(38400),
+        baud_rate: Some(BaudRate::Rate(38400)),
+        parse_profile: None,
         framing: Some("9N1".to_string()),
         flow_control: None,
         timeout: None,
         messages: None,
         ntp_unit: None,
+        quarantine: None,
+        rollover_epoch: None,
+        leap_seconds_file: None,
+        systems: None,
+        timing_sentence: None,
+        time_quality: None,
+        ublox: None,
+        quectel: None,
+        mkt: None,
     };
 
     match SerialPortBuilder::try_from(gps).err().unwrap() {
@@ -284,3 +544,215 @@ fn test_try_from_log_filter_error() {
         _ => assert!(false),
     };
 }
+
+#[test]
+fn test_config_quarantine() {
+    let (_, dir) = write(
+        r#"
+[[gps]]
+name = "GPS0"
+device = "/dev/gps0"
+gps_type = "generic"
+quarantine = "/var/log/where_am_i/gps0.bad"
+    "#,
+    )
+    .unwrap();
+
+    let path = dir.path().join("where.toml");
+    let config = Configuration::load(path).unwrap();
+
+    assert_eq!(
+        Some("/var/log/where_am_i/gps0.bad".to_string()),
+        config.gps[0].quarantine
+    );
+}
+
+#[test]
+fn test_config_rollover_epoch() {
+    let (_, dir) = write(
+        r#"
+[[gps]]
+name = "GPS0"
+device = "/dev/gps0"
+gps_type = "generic"
+rollover_epoch = "2019-04-07"
+
+[[gps]]
+name = "GPS1"
+device = "/dev/gps1"
+gps_type = "generic"
+rollover_epoch = "build"
+
+[[gps]]
+name = "GPS2"
+device = "/dev/gps2"
+gps_type = "generic"
+    "#,
+    )
+    .unwrap();
+
+    let path = dir.path().join("where.toml");
+    let config = Configuration::load(path).unwrap();
+
+    assert_eq!(
+        RolloverEpoch::Date(NaiveDate::from_ymd(2019, 4, 7)),
+        config.gps[0].rollover_epoch()
+    );
+    assert_eq!(RolloverEpoch::Build, config.gps[1].rollover_epoch());
+    assert_eq!(RolloverEpoch::Build, config.gps[2].rollover_epoch());
+}
+
+#[test]
+fn test_config_rollover_epoch_invalid() {
+    let (_, dir) = write(
+        r#"
+[[gps]]
+name = "GPS0"
+device = "/dev/gps0"
+gps_type = "generic"
+rollover_epoch = "last week"
+    "#,
+    )
+    .unwrap();
+
+    let path = dir.path().join("where.toml");
+
+    assert!(Configuration::load(path).is_err());
+}
+
+#[test]
+fn test_config_systems() {
+    let (_, dir) = write(
+        r#"
+[[gps]]
+name = "GPS0"
+device = "/dev/gps0"
+gps_type = "generic"
+systems = [ "GPS", "glonass", "BeiDou" ]
+    "#,
+    )
+    .unwrap();
+
+    let path = dir.path().join("where.toml");
+    let config = Configuration::load(path).unwrap();
+
+    assert_eq!(
+        Some(vec![System::GPS, System::GLONASS, System::BeiDuo]),
+        config.gps[0].systems
+    );
+}
+
+#[test]
+fn test_config_systems_invalid() {
+    let (_, dir) = write(
+        r#"
+[[gps]]
+name = "GPS0"
+device = "/dev/gps0"
+gps_type = "generic"
+systems = [ "GPS", "Compass" ]
+    "#,
+    )
+    .unwrap();
+
+    let path = dir.path().join("where.toml");
+
+    assert!(Configuration::load(path).is_err());
+}
+
+#[test]
+fn test_config_time_quality() {
+    let (_, dir) = write(
+        r#"
+[[gps]]
+name = "GPS0"
+device = "/dev/gps0"
+gps_type = "generic"
+
+[gps.time_quality]
+min_fix_mode = 3
+min_satellites = 4
+valid_status = true
+consistent_sentences = 3
+
+[[gps]]
+name = "GPS1"
+device = "/dev/gps1"
+gps_type = "generic"
+    "#,
+    )
+    .unwrap();
+
+    let path = dir.path().join("where.toml");
+    let config = Configuration::load(path).unwrap();
+
+    let time_quality = TimeQualityConfig {
+        min_fix_mode: Some(3),
+        min_satellites: Some(4),
+        valid_status: Some(true),
+        leap_seconds: None,
+        consistent_sentences: Some(3),
+    };
+
+    assert_eq!(time_quality, config.gps[0].time_quality());
+    assert!(time_quality.valid_status());
+    assert!(!time_quality.leap_seconds());
+
+    let time_quality = config.gps[1].time_quality();
+
+    assert_eq!(0, time_quality.min_fix_mode());
+    assert_eq!(0, time_quality.min_satellites());
+    assert!(!time_quality.valid_status());
+    assert!(!time_quality.leap_seconds());
+    assert_eq!(0, time_quality.consistent_sentences());
+}
+
+#[test]
+fn test_config_timing_sentence() {
+    let (_, dir) = write(
+        r#"
+[[gps]]
+name = "GPS0"
+device = "/dev/gps0"
+gps_type = "generic"
+timing_sentence = "PUBX,04"
+
+[[gps]]
+name = "GPS1"
+device = "/dev/gps1"
+gps_type = "generic"
+timing_sentence = "first"
+
+[[gps]]
+name = "GPS2"
+device = "/dev/gps2"
+gps_type = "generic"
+    "#,
+    )
+    .unwrap();
+
+    let path = dir.path().join("where.toml");
+    let config = Configuration::load(path).unwrap();
+
+    assert_eq!(TimingSentence::PUBX04, config.gps[0].timing_sentence());
+    assert_eq!(TimingSentence::First, config.gps[1].timing_sentence());
+    assert_eq!(TimingSentence::ZDA, config.gps[2].timing_sentence());
+}
+
+#[test]
+fn test_config_timing_sentence_invalid() {
+    let (_, dir) = write(
+        r#"
+[[gps]]
+name = "GPS0"
+device = "/dev/gps0"
+gps_type = "generic"
+timing_sentence = "GLL"
+    "#,
+    )
+    .unwrap();
+
+    let path = dir.path().join("where.toml");
+
+    assert!(Configuration::load(path).is_err());
+}
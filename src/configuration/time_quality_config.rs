@@ -0,0 +1,38 @@
+use serde::Deserialize;
+
+/// Gates that hold back time from NTP and gpsd `TOFF` until it is trustworthy
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct TimeQualityConfig {
+    pub min_fix_mode: Option<u32>,
+    pub min_satellites: Option<u32>,
+    pub valid_status: Option<bool>,
+    pub leap_seconds: Option<bool>,
+    pub consistent_sentences: Option<u32>,
+}
+
+impl TimeQualityConfig {
+    /// Minimum gpsd fix mode, 2 for a 2D fix or 3 for a 3D fix, defaults to 0 for any mode
+    pub fn min_fix_mode(&self) -> u32 {
+        self.min_fix_mode.unwrap_or(0)
+    }
+
+    /// Minimum satellites used in the fix, defaults to 0
+    pub fn min_satellites(&self) -> u32 {
+        self.min_satellites.unwrap_or(0)
+    }
+
+    /// Hold time while RMC, GGA, or GNS report an invalid fix, defaults to false
+    pub fn valid_status(&self) -> bool {
+        self.valid_status.unwrap_or(false)
+    }
+
+    /// Hold time while PUBX,04 reports the firmware default leap seconds, defaults to false
+    pub fn leap_seconds(&self) -> bool {
+        self.leap_seconds.unwrap_or(false)
+    }
+
+    /// Consecutive timing sentences that must agree with the system clock, defaults to 0
+    pub fn consistent_sentences(&self) -> u32 {
+        self.consistent_sentences.unwrap_or(0)
+    }
+}
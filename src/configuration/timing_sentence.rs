@@ -0,0 +1,35 @@
+use crate::configuration::ConfigurationError;
+
+use serde::Deserialize;
+use std::convert::TryFrom;
+
+/// The sentence whose time is sent to NTP and gpsd `TOFF`
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq)]
+#[serde(try_from = "String")]
+pub enum TimingSentence {
+    #[default]
+    ZDA,
+    RMC,
+    GGA,
+    GNS,
+    PUBX04,
+    /// The first ZDA, RMC, GGA, or GNS of each epoch
+    First,
+}
+
+// A timing sentence is written as its sentence type, "PUBX,04", or "first"
+impl TryFrom<String> for TimingSentence {
+    type Error = ConfigurationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "ZDA" => Ok(TimingSentence::ZDA),
+            "RMC" => Ok(TimingSentence::RMC),
+            "GGA" => Ok(TimingSentence::GGA),
+            "GNS" => Ok(TimingSentence::GNS),
+            "PUBX,04" => Ok(TimingSentence::PUBX04),
+            "first" => Ok(TimingSentence::First),
+            _ => Err(ConfigurationError::InvalidTimingSentence(value)),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::gps::UBXPort;
+use crate::ubx::cfg::{UBXConstellation, UBXDynamicModel, UBXLayer};
+
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct UBloxConfig {
+    pub port: Option<UBXPort>,
+    pub layers: Option<Vec<UBXLayer>>,
+    pub clear_layers: Option<Vec<UBXLayer>>,
+    pub measurement_rate: Option<u16>,
+    pub navigation_rate: Option<u16>,
+    pub dynamic_model: Option<UBXDynamicModel>,
+    pub constellations: Option<Vec<UBXConstellation>>,
+}
+
+impl UBloxConfig {
+    pub fn port(&self) -> UBXPort {
+        self.port.clone().unwrap_or(UBXPort::USART1)
+    }
+
+    pub fn layers(&self) -> Vec<UBXLayer> {
+        match &self.layers {
+            Some(l) => l.clone(),
+            None => vec![UBXLayer::RAM],
+        }
+    }
+
+    pub fn clear_layers(&self) -> Vec<UBXLayer> {
+        self.clear_layers.clone().unwrap_or_default()
+    }
+}
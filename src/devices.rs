@@ -79,13 +79,14 @@ async fn create_device(
 
     if let Some(ntp_unit) = gps_config.ntp_unit {
         let mut rx = gps.ntp_tx.subscribe();
+        let leap = gps.leap.clone();
         let local = tokio::task::LocalSet::new();
 
         local.spawn_local(async move {
             let mut ntp_shm = NtpShm::new(ntp_unit).unwrap();
 
             while let Ok(ts) = rx.recv().await {
-                ntp_shm.update_old(-1, 0, &ts);
+                ntp_shm.update_old(-1, *leap.borrow(), &ts);
             }
         });
 
@@ -95,6 +96,8 @@ async fn create_device(
         );
     }
 
+    let leap = gps.leap.clone();
+
     devices.insert(gps_config.name.clone(), Device::GPS(gps));
 
     if let Some(pps_config) = &gps_config.pps {
@@ -113,7 +116,7 @@ async fn create_device(
 
                 loop {
                     ntp_shm
-                        .update(&ntp_precision, 0, &mut current_timestamp)
+                        .update(&ntp_precision, &leap, &mut current_timestamp)
                         .await;
                 }
             });
@@ -0,0 +1,153 @@
+// Receiver type detection for gps_type = "auto"
+
+use crate::{
+    gps::{mkt::MKTReleasePoll, quectel::QuectelVersionPoll, GpsType, UBXPositionPoll},
+    nmea::{device::SerialCodec, NMEA},
+};
+use futures_util::{sink::SinkExt, stream::StreamExt};
+use std::time::Duration;
+use tokio::time::{timeout_at, Instant};
+use tracing::{debug, error, trace};
+
+/// How long to watch traffic for a sentence identifying the receiver
+pub const DETECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long to keep watching for a Quectel reply after an MKT reply
+pub const QUECTEL_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Receiver types detection can choose
+pub const GPS_TYPES: [GpsType; 6] = [
+    GpsType::Garmin,
+    GpsType::Generic,
+    GpsType::MKT,
+    GpsType::Quectel,
+    GpsType::SiRF,
+    GpsType::UBloxNMEA,
+];
+
+/// Watches traffic from the receiver and returns the type of the first sentence identifying it.
+///
+/// The receiver is also sent queries for u-blox, MKT, and Quectel receivers in case it doesn't
+/// announce itself.  `serial` must use a `Generic` driver so proprietary sentences are reported as
+/// `NMEA::Unsupported`.
+pub async fn detect(serial: &mut SerialCodec) -> Option<GpsType> {
+    probe(serial).await;
+
+    let mut deadline = Instant::now() + DETECT_TIMEOUT;
+    let mut detection = Detection::default();
+
+    loop {
+        match timeout_at(deadline, serial.next()).await {
+            Ok(Some(Ok(nmea))) => {
+                if let Some(gps_type) = detection.add(&nmea) {
+                    debug!("identified {:?} from {:?}", gps_type, nmea);
+                    return Some(gps_type);
+                }
+
+                if detection.mkt {
+                    deadline = deadline.min(Instant::now() + QUECTEL_TIMEOUT);
+                }
+            }
+            Ok(Some(Err(e))) => {
+                error!("error detecting receiver type: {:?}", e);
+                return detection.finish();
+            }
+            Ok(None) | Err(_) => return detection.finish(),
+        }
+    }
+}
+
+/// Chooses the receiver type from the sentences seen while detecting
+///
+/// MTK-based Quectel receivers answer the MKT probe too, so an MKT reply only settles the type
+/// when no Quectel reply follows it.
+#[derive(Debug, Default)]
+pub struct Detection {
+    mkt: bool,
+}
+
+impl Detection {
+    /// Returns the receiver type once `nmea` settles it
+    pub fn add(&mut self, nmea: &NMEA) -> Option<GpsType> {
+        match identify(nmea) {
+            Some(GpsType::MKT) => {
+                self.mkt = true;
+                None
+            }
+            gps_type => gps_type,
+        }
+    }
+
+    /// The receiver type when traffic ends before it is settled
+    pub fn finish(&self) -> Option<GpsType> {
+        match self.mkt {
+            true => Some(GpsType::MKT),
+            false => None,
+        }
+    }
+}
+
+// Sends queries that receivers answer with proprietary sentences
+async fn probe(serial: &mut SerialCodec) {
+    trace!("probing receiver type");
+
+    if let Err(e) = serial.send(UBXPositionPoll {}).await {
+        error!("unable to send u-blox probe: {:?}", e);
+    }
+
+    if let Err(e) = serial.send(MKTReleasePoll {}).await {
+        error!("unable to send MKT probe: {:?}", e);
+    }
+
+    if let Err(e) = serial.send(QuectelVersionPoll {}).await {
+        error!("unable to send Quectel probe: {:?}", e);
+    }
+}
+
+/// The receiver type that sends `nmea`, if it identifies one
+pub fn identify(nmea: &NMEA) -> Option<GpsType> {
+    match nmea {
+        NMEA::UBX(_) => Some(GpsType::UBloxNMEA),
+        NMEA::Unsupported(sentence) => identify_private(sentence),
+        NMEA::TXT(txt) => identify_text(&txt.text),
+        _ => None,
+    }
+}
+
+fn identify_private(sentence: &str) -> Option<GpsType> {
+    if sentence.starts_with("PUBX") {
+        Some(GpsType::UBloxNMEA)
+    } else if sentence.starts_with("PAIR") || sentence.starts_with("PQTM") {
+        Some(GpsType::Quectel)
+    } else if sentence.starts_with("PMTK705") && sentence.to_lowercase().contains("quectel") {
+        // MTK-based Quectel receivers name the module in their release
+        Some(GpsType::Quectel)
+    } else if sentence.starts_with("PMTK") {
+        Some(GpsType::MKT)
+    } else if sentence.starts_with("PSRF") {
+        Some(GpsType::SiRF)
+    } else if sentence.starts_with("PGRM") {
+        Some(GpsType::Garmin)
+    } else {
+        None
+    }
+}
+
+// Product strings from $xxTXT startup banners
+fn identify_text(text: &str) -> Option<GpsType> {
+    let text = text.to_lowercase();
+
+    if text.contains("u-blox") || text.starts_with("hw ubx") {
+        Some(GpsType::UBloxNMEA)
+    } else if text.contains("quectel") {
+        Some(GpsType::Quectel)
+    } else if text.contains("mtk") || text.contains("globaltop") {
+        Some(GpsType::MKT)
+    } else if text.contains("sirf") {
+        Some(GpsType::SiRF)
+    } else if text.contains("garmin") {
+        Some(GpsType::Garmin)
+    } else {
+        None
+    }
+}
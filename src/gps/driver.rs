@@ -1,4 +1,7 @@
+use crate::gps::Garmin;
 use crate::gps::Generic;
+use crate::gps::Quectel;
+use crate::gps::SiRF;
 use crate::gps::UBloxNMEA;
 use crate::gps::MKT;
 use crate::nmea::MessageSetting;
@@ -10,22 +13,31 @@ use nom::IResult;
 pub enum Driver {
     UBloxNMEA(UBloxNMEA),
     MKT(MKT),
+    Quectel(Quectel),
+    SiRF(SiRF),
+    Garmin(Garmin),
     Generic(Generic),
 }
 
 impl Driver {
     pub async fn configure(&self, serial: &mut SerialCodec, messages: &Vec<MessageSetting>) {
         match self {
+            Driver::Garmin(d) => d.configure(serial, messages).await,
             Driver::Generic(_) => (),
             Driver::MKT(d) => d.configure(serial, messages).await,
+            Driver::Quectel(d) => d.configure(serial, messages).await,
+            Driver::SiRF(d) => d.configure(serial, messages).await,
             Driver::UBloxNMEA(d) => d.configure(serial, messages).await,
         }
     }
 
     pub fn message_settings(&self, messages: &Vec<String>) -> Vec<MessageSetting> {
         match self {
+            Driver::Garmin(d) => d.message_settings(messages),
             Driver::Generic(_) => vec![],
             Driver::MKT(d) => d.message_settings(messages),
+            Driver::Quectel(d) => d.message_settings(messages),
+            Driver::SiRF(d) => d.message_settings(messages),
             Driver::UBloxNMEA(d) => d.message_settings(messages),
         }
     }
@@ -35,8 +47,11 @@ impl Driver {
         input: &'a str,
     ) -> IResult<&'a str, NMEA, nom::error::VerboseError<&'a str>> {
         match self {
+            Driver::Garmin(d) => d.parse_private(input),
             Driver::Generic(d) => d.parse_private(input),
             Driver::MKT(d) => d.parse_private(input),
+            Driver::Quectel(d) => d.parse_private(input),
+            Driver::SiRF(d) => d.parse_private(input),
             Driver::UBloxNMEA(d) => d.parse_private(input),
         }
     }
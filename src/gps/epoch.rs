@@ -0,0 +1,115 @@
+use crate::{gpsd::Tpv, nmea::System};
+use chrono::{DateTime, NaiveTime, Utc};
+use std::{collections::HashSet, time::Duration};
+
+/// A device that is quiet this long has finished sending the sentences of an epoch
+pub const EPOCH_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// A snapshot of one navigation epoch, every sentence sharing a UTC time of fix
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Fix {
+    /// UTC time of fix, None until a sentence with a time arrives
+    pub time: Option<NaiveTime>,
+    /// UTC date and time of fix, set when the epoch completes if the date is known
+    pub date_time: Option<DateTime<Utc>>,
+    /// Sentence types of the epoch in the order they arrived
+    pub sentences: Vec<&'static str>,
+    /// TPV report fields from the sentences of the epoch
+    pub tpv: Tpv,
+    pub hdop: Option<f32>,
+    pub pdop: Option<f32>,
+    pub tdop: Option<f32>,
+    pub vdop: Option<f32>,
+    /// Satellites used in the navigation solution
+    pub used_satellites: HashSet<(Option<System>, u32)>,
+    /// A GSV or GSA sentence arrived
+    pub sky_changed: bool,
+    /// The time of a timing sentence of the epoch was reported
+    pub timed: bool,
+}
+
+impl Fix {
+    // The last sentence type of the epoch and how many times it arrived
+    fn last(&self) -> Option<(&'static str, usize)> {
+        let last = *self.sentences.last()?;
+        let count = self.sentences.iter().filter(|s| **s == last).count();
+
+        Some((last, count))
+    }
+}
+
+/// Groups the sentences of a device by UTC time of fix into `Fix` snapshots.
+///
+/// An epoch completes when a sentence with a different time of fix arrives, when the device is
+/// quiet for `EPOCH_TIMEOUT`, or when the sentence that ended the two previous epochs arrives.
+#[derive(Debug, Default)]
+pub struct EpochAssembler {
+    /// The epoch being assembled
+    pub fix: Fix,
+    /// The last sentence of the previous epoch and how many times it arrived in it
+    candidate: Option<(&'static str, usize)>,
+    /// The learned last sentence of an epoch
+    end: Option<(&'static str, usize)>,
+}
+
+impl EpochAssembler {
+    /// Adds a sentence to the current epoch.  When `time` differs from the time of fix of the
+    /// current epoch the current epoch is completed and returned, and the sentence starts a new
+    /// one.
+    pub fn add(&mut self, sentence: &'static str, time: Option<NaiveTime>) -> Option<Fix> {
+        let complete = match (self.fix.time, time) {
+            (Some(current), Some(time)) if current != time => self.complete(true),
+            _ => None,
+        };
+
+        if time.is_some() {
+            self.fix.time = time;
+        }
+
+        self.fix.sentences.push(sentence);
+
+        complete
+    }
+
+    /// Completes the current epoch if its last sentence is the learned end of an epoch
+    pub fn end(&mut self) -> Option<Fix> {
+        if self.end.is_some() && self.fix.last() == self.end {
+            return self.complete(false);
+        }
+
+        None
+    }
+
+    /// Completes the current epoch after the device was quiet for `EPOCH_TIMEOUT`
+    pub fn timeout(&mut self) -> Option<Fix> {
+        self.complete(true)
+    }
+
+    /// The learned last sentence of an epoch and how many times it arrives in an epoch
+    pub fn learned_end(&self) -> Option<(&'static str, usize)> {
+        self.end
+    }
+
+    fn complete(&mut self, learn: bool) -> Option<Fix> {
+        if self.fix.sentences.is_empty() {
+            return None;
+        }
+
+        let fix = std::mem::take(&mut self.fix);
+
+        // the last sentence is learned when two epochs in a row end with it, and forgotten when an
+        // epoch ends with a different sentence
+        if learn {
+            let last = fix.last();
+
+            self.end = match self.candidate == last {
+                true => last,
+                false => None,
+            };
+
+            self.candidate = last;
+        }
+
+        Some(fix)
+    }
+}
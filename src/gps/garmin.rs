@@ -0,0 +1,400 @@
+// For Garmin devices
+
+use crate::{
+    gps::add_message,
+    nmea::{
+        device::SerialCodec,
+        parser::{op_mode, NavigationMode, OperationMode, Result},
+        parser_util::*,
+        ser_util, LatLon, MessageSetting, NMEA,
+    },
+};
+use chrono::naive::{NaiveDate, NaiveTime};
+use futures_util::sink::SinkExt;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, one_of},
+    combinator::{map, opt},
+    error::context,
+    sequence::{preceded, terminated, tuple},
+};
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Serialize,
+};
+use tracing::{debug, error, info};
+
+pub const OUTPUT_MESSAGES: [&str; 9] = [
+    "GGA", "GLL", "GSA", "GSV", "PGRME", "PGRMF", "PGRMT", "RMC", "VTG",
+];
+
+#[derive(Clone, Default, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Garmin {}
+
+impl Garmin {
+    pub async fn configure(&self, serial: &mut SerialCodec, messages: &[MessageSetting]) {
+        debug!("configuring Garmin with sentences {:?}", messages);
+
+        for message in messages {
+            // proprietary sentences are named in full, standard sentences need a talker
+            let sentence = match message.id.starts_with('P') {
+                true => message.id.clone(),
+                false => format!("GP{}", message.id),
+            };
+
+            let set = GarminSetOutput {
+                sentence,
+                enabled: message.enabled,
+            };
+
+            match serial.send(set).await {
+                Ok(_) => info!("setting {} to {}", message.id, message.enabled),
+                Err(e) => error!(
+                    "unable to set {} to {}: {:?}",
+                    message.id, message.enabled, e
+                ),
+            }
+        }
+    }
+
+    pub fn message_settings(&self, messages: &[String]) -> Vec<MessageSetting> {
+        let mut message_settings: Vec<MessageSetting> = vec![];
+
+        if messages.is_empty() {
+            for message in &OUTPUT_MESSAGES {
+                add_message(&mut message_settings, message, true);
+            }
+        } else {
+            for default in &OUTPUT_MESSAGES {
+                let enabled = messages.contains(&default.to_string());
+
+                add_message(&mut message_settings, default, enabled);
+            }
+        }
+
+        message_settings
+    }
+
+    pub fn parse_private<'a>(&self, input: &'a str) -> Result<&'a str, NMEA> {
+        context(
+            "PGRM",
+            map(
+                alt((
+                    map(grm_e, GarminData::ErrorEstimate),
+                    map(grm_f, GarminData::Fix),
+                    map(grm_t, GarminData::SensorStatus),
+                )),
+                NMEA::PGRM,
+            ),
+        )(input)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GarminData {
+    ErrorEstimate(GarminErrorEstimate),
+    Fix(GarminFix),
+    SensorStatus(GarminSensorStatus),
+}
+
+impl Serialize for GarminData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            GarminData::ErrorEstimate(e) => e.serialize(serializer),
+            GarminData::Fix(f) => f.serialize(serializer),
+            GarminData::SensorStatus(s) => s.serialize(serializer),
+        }
+    }
+}
+
+/// Estimated position errors in meters
+#[derive(Clone, Debug, PartialEq)]
+pub struct GarminErrorEstimate {
+    pub horizontal: Option<f32>,
+    pub vertical: Option<f32>,
+    pub spherical: Option<f32>,
+}
+
+impl Serialize for GarminErrorEstimate {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GarminErrorEstimate", 6)?;
+        state.serialize_field("horizontal", &self.horizontal)?;
+        state.serialize_field("horizontal_unit", "M")?;
+        state.serialize_field("vertical", &self.vertical)?;
+        state.serialize_field("vertical_unit", "M")?;
+        state.serialize_field("spherical", &self.spherical)?;
+        state.serialize_field("spherical_unit", "M")?;
+        state.end()
+    }
+}
+
+fn meters(input: &str) -> Result<&str, Option<f32>> {
+    terminated(opt(flt32), preceded(comma, opt(char('M'))))(input)
+}
+
+pub(crate) fn grm_e(input: &str) -> Result<&str, GarminErrorEstimate> {
+    parse_message(
+        "GRM E",
+        tuple((
+            preceded(tag("PGRME,"), terminated(meters, comma)),
+            terminated(meters, comma),
+            meters,
+        )),
+        |(horizontal, vertical, spherical)| GarminErrorEstimate {
+            horizontal,
+            vertical,
+            spherical,
+        },
+    )(input)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GarminFix {
+    pub week: u32,
+    /// Seconds since the start of the GPS week
+    pub seconds: u32,
+    pub date: Option<NaiveDate>,
+    pub time: Option<NaiveTime>,
+    pub leap_seconds: u32,
+    pub lat_lon: Option<LatLon>,
+    pub mode: OperationMode,
+    /// 0 for no fix, 1 for a 2D fix, 2 for a 3D fix
+    pub fix_type: u32,
+    /// Kilometers per hour
+    pub speed: Option<f32>,
+    /// Degrees
+    pub course: Option<f32>,
+    /// Rounded to an integer
+    pub pdop: Option<u32>,
+    /// Rounded to an integer
+    pub tdop: Option<u32>,
+}
+
+impl GarminFix {
+    pub fn navigation_mode(&self) -> NavigationMode {
+        match self.fix_type {
+            1 => NavigationMode::Fix2D,
+            2 => NavigationMode::Fix3D,
+            _ => NavigationMode::FixNone,
+        }
+    }
+}
+
+impl Serialize for GarminFix {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GarminFix", 12)?;
+        state.serialize_field("week", &self.week)?;
+        state.serialize_field("seconds", &self.seconds)?;
+        state.serialize_field("date", &self.date.as_ref().map(ser_util::date))?;
+        state.serialize_field("time", &self.time.map(|t| t.format("%H%M%S").to_string()))?;
+        state.serialize_field("leap_seconds", &self.leap_seconds)?;
+        state.serialize_field("lat_lon", &ser_util::latlon(&self.lat_lon))?;
+        state.serialize_field("mode", &self.mode)?;
+        state.serialize_field("fix_type", &self.fix_type)?;
+        state.serialize_field("speed", &self.speed)?;
+        state.serialize_field("course", &self.course)?;
+        state.serialize_field("pdop", &self.pdop)?;
+        state.serialize_field("tdop", &self.tdop)?;
+        state.end()
+    }
+}
+
+pub(crate) fn grm_f(input: &str) -> Result<&str, GarminFix> {
+    parse_message(
+        "GRM F",
+        tuple((
+            preceded(tag("PGRMF,"), terminated(uint32, comma)),
+            terminated(uint32, comma),
+            terminated(opt(date), comma),
+            terminated(opt(time), comma),
+            terminated(uint32, comma),
+            terminated(latlon, comma),
+            terminated(op_mode, comma),
+            terminated(uint32, comma),
+            terminated(opt(flt32), comma),
+            terminated(opt(flt32), comma),
+            terminated(opt(uint32), comma),
+            opt(uint32),
+        )),
+        |(
+            week,
+            seconds,
+            date,
+            time,
+            leap_seconds,
+            lat_lon,
+            mode,
+            fix_type,
+            speed,
+            course,
+            pdop,
+            tdop,
+        )| GarminFix {
+            week,
+            seconds,
+            date,
+            time,
+            leap_seconds,
+            lat_lon,
+            mode,
+            fix_type,
+            speed,
+            course,
+            pdop,
+            tdop,
+        },
+    )(input)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GarminTest {
+    Pass,
+    Fail,
+}
+
+impl Serialize for GarminTest {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_char(match self {
+            GarminTest::Pass => 'P',
+            GarminTest::Fail => 'F',
+        })
+    }
+}
+
+fn test_result(input: &str) -> Result<&str, Option<GarminTest>> {
+    opt(map(one_of("PF"), |c| match c {
+        'P' => GarminTest::Pass,
+        _ => GarminTest::Fail,
+    }))(input)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GarminRetention {
+    Retained,
+    Lost,
+}
+
+impl Serialize for GarminRetention {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_char(match self {
+            GarminRetention::Retained => 'R',
+            GarminRetention::Lost => 'L',
+        })
+    }
+}
+
+fn retention(input: &str) -> Result<&str, Option<GarminRetention>> {
+    opt(map(one_of("RL"), |c| match c {
+        'R' => GarminRetention::Retained,
+        _ => GarminRetention::Lost,
+    }))(input)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GarminSensorStatus {
+    pub product: String,
+    pub rom_checksum: Option<GarminTest>,
+    pub receiver_failure: Option<GarminTest>,
+    pub stored_data: Option<GarminRetention>,
+    pub real_time_clock: Option<GarminRetention>,
+    pub oscillator_drift: Option<GarminTest>,
+    pub data_collection: bool,
+    /// Board temperature in degrees Celsius
+    pub temperature: Option<f32>,
+    pub configuration: Option<GarminRetention>,
+}
+
+impl Serialize for GarminSensorStatus {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GarminSensorStatus", 9)?;
+        state.serialize_field("product", &self.product)?;
+        state.serialize_field("rom_checksum", &self.rom_checksum)?;
+        state.serialize_field("receiver_failure", &self.receiver_failure)?;
+        state.serialize_field("stored_data", &self.stored_data)?;
+        state.serialize_field("real_time_clock", &self.real_time_clock)?;
+        state.serialize_field("oscillator_drift", &self.oscillator_drift)?;
+        state.serialize_field(
+            "data_collection",
+            if self.data_collection { "C" } else { "" },
+        )?;
+        state.serialize_field("temperature", &self.temperature)?;
+        state.serialize_field("configuration", &self.configuration)?;
+        state.end()
+    }
+}
+
+pub(crate) fn grm_t(input: &str) -> Result<&str, GarminSensorStatus> {
+    parse_message(
+        "GRM T",
+        tuple((
+            preceded(tag("PGRMT,"), terminated(any, comma)),
+            terminated(test_result, comma),
+            terminated(test_result, comma),
+            terminated(retention, comma),
+            terminated(retention, comma),
+            terminated(test_result, comma),
+            terminated(opt(char('C')), comma),
+            terminated(opt(flt32), comma),
+            retention,
+        )),
+        |(
+            product,
+            rom_checksum,
+            receiver_failure,
+            stored_data,
+            real_time_clock,
+            oscillator_drift,
+            data_collection,
+            temperature,
+            configuration,
+        )| GarminSensorStatus {
+            product,
+            rom_checksum,
+            receiver_failure,
+            stored_data,
+            real_time_clock,
+            oscillator_drift,
+            data_collection: data_collection.is_some(),
+            temperature,
+            configuration,
+        },
+    )(input)
+}
+
+/// Enables or disables output of a sentence
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GarminSetOutput {
+    pub sentence: String,
+    pub enabled: bool,
+}
+
+impl Serialize for GarminSetOutput {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GarminSetOutput", 2)?;
+        state.serialize_field("sentence", &self.sentence)?;
+        state.serialize_field("enabled", &self.enabled)?;
+        state.end()
+    }
+}
@@ -1,17 +1,34 @@
 use crate::{
-    gpsd::{Response, Toff, Tpv},
-    nmea::*,
+    ais::AISFragment,
+    configuration::{GpsConfig, TimeQualityConfig, TimingSentence},
+    gps::{
+        gps_week, EpochAssembler, Fix, GarminData, GarminFix, LeapIndicator, LeapSecond,
+        LeapSeconds, LeapSecondsList, QuectelData, QuectelPVT, Rollover, UBXData,
+        UBXNavigationStatus, UBXPosition, UBXSatelliteStatus, UBXSatellites, UBXTime,
+        ROLLOVER_WEEKS,
+    },
+    gpsd::{satellite_system, Ais, Att, Gst, Response, Sky, SkySatellite, Toff},
+    nmea::{
+        parser::{MessageType, PositionMode, Signal},
+        *,
+    },
+    rtcm::RTCMFrame,
+    ubx::{UBXMessage, UBXNavRelPosNED, UBXNavTimeLS},
     TSSender, Timestamp,
 };
 use chrono::prelude::*;
 use lazy_static::lazy_static;
-use prometheus::{register_gauge_vec, GaugeVec};
+use prometheus::{
+    register_gauge_vec, register_histogram_vec, register_int_counter_vec, register_int_gauge_vec,
+    GaugeVec, HistogramVec, IntCounterVec, IntGaugeVec,
+};
 use std::{
+    collections::{HashMap, HashSet},
     fmt::Debug,
     time::{Duration, SystemTime},
 };
 use tokio::sync::broadcast;
-use tracing::{error, trace};
+use tracing::{error, info, trace, warn};
 
 lazy_static! {
     static ref LATITUDE: GaugeVec = register_gauge_vec!(
@@ -32,8 +49,99 @@ lazy_static! {
         &["device"]
     )
     .unwrap();
+    static ref HEADING: GaugeVec = register_gauge_vec!(
+        "where_am_i_heading_degrees",
+        "True heading of a device's antenna baseline",
+        &["device"]
+    )
+    .unwrap();
+    static ref PITCH: GaugeVec = register_gauge_vec!(
+        "where_am_i_pitch_degrees",
+        "Pitch of a device's antenna baseline",
+        &["device"]
+    )
+    .unwrap();
+    static ref BASELINE_LENGTH: GaugeVec = register_gauge_vec!(
+        "where_am_i_baseline_length_meters",
+        "Length of a device's antenna baseline",
+        &["device"]
+    )
+    .unwrap();
+    static ref RATE_OF_TURN: GaugeVec = register_gauge_vec!(
+        "where_am_i_rate_of_turn_degrees_per_minute",
+        "Rate of turn of a device",
+        &["device"]
+    )
+    .unwrap();
+    static ref RTCM_MESSAGES: IntCounterVec = register_int_counter_vec!(
+        "where_am_i_rtcm_messages_count",
+        "Count of RTCM 3 messages read from a device",
+        &["device", "message_type", "station_id"]
+    )
+    .unwrap();
+    static ref ROLLOVER_CORRECTIONS: IntCounterVec = register_int_counter_vec!(
+        "where_am_i_week_rollover_corrections_count",
+        "Count of dates from a device corrected for GPS week number rollover",
+        &["device"]
+    )
+    .unwrap();
+    static ref DATE_TRUSTED: IntGaugeVec = register_int_gauge_vec!(
+        "where_am_i_date_trusted",
+        "Whether the date from a device is trusted enough to send to NTP",
+        &["device"]
+    )
+    .unwrap();
+    static ref LEAP_SECONDS: IntGaugeVec = register_int_gauge_vec!(
+        "where_am_i_leap_seconds",
+        "Difference between GPS time and UTC reported by a device",
+        &["device"]
+    )
+    .unwrap();
+    static ref LEAP_INDICATOR: IntGaugeVec = register_int_gauge_vec!(
+        "where_am_i_leap_indicator",
+        "NTP leap indicator of a device, 1 when a leap second will be inserted, 2 when deleted",
+        &["device"]
+    )
+    .unwrap();
+    static ref NAVIGATION_MODE: IntGaugeVec = register_int_gauge_vec!(
+        "where_am_i_navigation_mode",
+        "Navigation mode of each system of a device from GSA, 1 no fix, 2 2D fix, 3 3D fix",
+        &["device", "system"]
+    )
+    .unwrap();
+    static ref TIME_HELD: IntCounterVec = register_int_counter_vec!(
+        "where_am_i_time_held_count",
+        "Count of times from a device held back from NTP by the reason they aren't trustworthy",
+        &["device", "reason"]
+    )
+    .unwrap();
+    static ref SATELLITE_FAULTS: IntCounterVec = register_int_counter_vec!(
+        "where_am_i_satellite_faults_count",
+        "Count of satellites a device's fault detection reported as most likely failed",
+        &["device", "system", "satellite"]
+    )
+    .unwrap();
+    static ref RANGE_RESIDUALS: HistogramVec = register_histogram_vec!(
+        "where_am_i_range_residuals_meters",
+        "Range residuals of satellites used in a device's navigation solution",
+        &["device", "system"],
+        vec![
+            -50.0, -20.0, -10.0, -5.0, -2.0, -1.0, -0.5, 0.0, 0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 50.0
+        ]
+    )
+    .unwrap();
 }
 
+const KNOTS_TO_METERS_PER_SECOND: f64 = 0.514444;
+const KILOMETERS_PER_HOUR_TO_METERS_PER_SECOND: f64 = 1.0 / 3.6;
+
+// Timing sentences are consistent when their offsets from the system clock are this close in
+// seconds
+const TIME_CONSISTENCY: f64 = 0.5;
+
+// gpsd's estimate of the time error of NMEA sentences in seconds
+const NMEA_TIME_ERROR: f64 = 0.005;
+
 #[derive(Debug, Default)]
 pub struct GPSData {
     pub(crate) naive_date: Option<NaiveDate>,
@@ -49,12 +157,107 @@ pub struct GPSData {
     pub glonass_navigation_mode: Option<NavigationMode>,
     pub galileo_navigation_mode: Option<NavigationMode>,
     pub beiduo_navigation_mode: Option<NavigationMode>,
-    mode: Option<u32>,
+    pub(crate) mode: Option<u32>,
+    /// Systems expected to report a navigation mode, from the configuration
+    systems: Option<HashSet<System>>,
+    /// Systems that reported a navigation mode in the latest epoch with a GSA
+    learned_systems: HashSet<System>,
+    /// gpsd mode reported by each system this epoch, None for a combined GSA
+    navigation_modes: HashMap<Option<System>, u32>,
 
     pub quality: Option<Quality>,
+
+    pub hdop: Option<f32>,
+    pub pdop: Option<f32>,
+    pub tdop: Option<f32>,
+    pub vdop: Option<f32>,
+
+    /// True heading of the antenna baseline in degrees
+    pub heading: Option<f32>,
+    /// Pitch of the antenna baseline in degrees
+    pub pitch: Option<f32>,
+    /// Length of the antenna baseline in meters
+    pub baseline_length: Option<f32>,
+    /// Degrees per minute, negative when turning to port
+    pub rate_of_turn: Option<f32>,
+
+    assembler: Assembler,
+    /// The latest complete GSV group for each talker and signal
+    pub satellite_views: HashMap<(Talker, Option<Signal>), SatelliteView>,
+
+    /// Groups sentences into navigation epochs
+    epoch: EpochAssembler,
+    /// The latest complete navigation epoch
+    pub fix: Option<Fix>,
+
+    pub(crate) rollover: Rollover,
+    /// A date was corrected for GPS week number rollover
+    rollover_corrected: bool,
+    /// The PUBX,04 GPS week disagrees with the date
+    week_mismatch: bool,
+    /// The date is not trusted and time is not sent to NTP
+    untrusted_date: bool,
+
+    pub leap_seconds: LeapSeconds,
+
+    /// Gates that hold back time from NTP
+    pub(crate) time_quality: TimeQualityConfig,
+    /// The sentence whose time is sent to NTP
+    pub(crate) timing_sentence: TimingSentence,
+    /// Satellites used in the fix from GGA or GNS
+    satellites_used: Option<u32>,
+    /// Whether the latest RMC, GGA, or GNS reported a valid fix
+    status_valid: Option<bool>,
+    /// Whether the latest PUBX,04 reported the firmware default leap seconds
+    leap_second_default: Option<bool>,
+    /// Offset of the latest timing sentence from the system clock in seconds
+    time_offset: Option<f64>,
+    /// Consecutive timing sentences with the same offset from the system clock
+    consistent_sentences: u32,
+    /// Why time is held back from NTP
+    time_held: Option<&'static str>,
 }
 
 impl GPSData {
+    pub fn new(config: &GpsConfig) -> Self {
+        let list =
+            config
+                .leap_seconds_file
+                .as_ref()
+                .and_then(|path| match LeapSecondsList::load(path) {
+                    Ok(list) => Some(list),
+                    Err(e) => {
+                        error!(
+                            "{}: unable to load leap seconds from {}: {}",
+                            config.name, path, e
+                        );
+                        None
+                    }
+                });
+
+        if let Some(list) = &list {
+            if list.is_expired(Utc::now().naive_utc()) {
+                warn!(
+                    "{}: leap seconds list {} has expired, update it",
+                    config.name,
+                    config.leap_seconds_file.as_ref().unwrap()
+                );
+            }
+        }
+
+        GPSData {
+            rollover: config.rollover_epoch().into(),
+            systems: config
+                .systems
+                .as_ref()
+                .map(|systems| systems.iter().cloned().collect()),
+            leap_seconds: LeapSeconds::new(list),
+            time_quality: config.time_quality(),
+            timing_sentence: config.timing_sentence(),
+            ..Default::default()
+        }
+    }
+
     pub fn read_nmea(
         &mut self,
         nmea: NMEA,
@@ -63,19 +266,37 @@ impl GPSData {
         ntp_tx: &TSSender,
     ) {
         match nmea {
-            NMEA::InvalidChecksum(cm) => error!(
-                "checksum match, given {}, calculated {} on {}",
-                cm.given, cm.calculated, cm.message
-            ),
             NMEA::ParseError(e) => error!("parse error: {}", e),
-            NMEA::ParseFailure(f) => error!("parse failure: {}", f),
             NMEA::Unsupported(n) => error!("unsupported: {}", n),
+            NMEA::AIS(nd) => self.ais(nd, name, gpsd_tx),
+            NMEA::GBS(nd) => self.gbs(nd, name, gpsd_tx),
             NMEA::GGA(nd) => self.gga(nd, name, gpsd_tx, ntp_tx),
+            NMEA::GNS(nd) => self.gns(nd, name, gpsd_tx, ntp_tx),
             NMEA::GSA(nd) => self.gsa(nd, name, gpsd_tx, ntp_tx),
+            NMEA::GRS(nd) => self.grs(nd, name, gpsd_tx),
+            NMEA::GST(nd) => self.gst(nd, name, gpsd_tx),
+            NMEA::GSV(nd) => self.gsv(nd, name, gpsd_tx),
+            NMEA::HDT(nd) => self.hdt(nd, name, gpsd_tx),
+            NMEA::PGRM(GarminData::Fix(fix)) => self.garmin_fix(fix, name),
+            NMEA::PUBX(UBXData::Position(p)) => self.pubx_position(p, name, gpsd_tx),
+            NMEA::PUBX(UBXData::Satellites(s)) => self.pubx_satellites(s, name, gpsd_tx),
+            NMEA::PUBX(UBXData::Time(t)) => self.pubx_time(t, name, gpsd_tx, ntp_tx),
+            NMEA::Quectel(QuectelData::PVT(pvt)) => self.quectel_pvt(pvt, name),
             NMEA::RMC(nd) => self.rmc(nd, name, gpsd_tx, ntp_tx),
+            NMEA::ROT(nd) => self.rot(nd, name, gpsd_tx),
+            NMEA::RTCM(frame) => self.rtcm(&frame, name),
+            NMEA::THS(nd) => self.ths(nd, name, gpsd_tx),
+            NMEA::TXT(nd) => self.txt(nd, name),
+            NMEA::UBX(UBXMessage::NavRelPosNED(rp)) => self.relposned(rp, name, gpsd_tx),
+            NMEA::UBX(UBXMessage::NavTimeLS(ls)) => self.nav_timels(ls, name),
+            NMEA::VTG(nd) => self.vtg(nd, name, gpsd_tx),
             NMEA::ZDA(nd) => self.zda(nd, name, gpsd_tx, ntp_tx),
             _ => (),
         }
+
+        if let Some(fix) = self.epoch.end() {
+            self.complete_epoch(fix, name, gpsd_tx);
+        }
     }
 
     pub(crate) fn update_lat_lon(&mut self, new_lat_lon: Option<LatLon>, name: &str) {
@@ -99,14 +320,45 @@ impl GPSData {
         }
     }
 
+    pub(crate) fn update_heading(&mut self, new_heading: Option<f32>, name: &str) {
+        self.heading = new_heading;
+
+        if let Some(heading) = self.heading {
+            HEADING.with_label_values(&[name]).set(heading.into());
+        }
+    }
+
+    pub(crate) fn update_pitch(&mut self, new_pitch: Option<f32>, name: &str) {
+        self.pitch = new_pitch;
+
+        if let Some(pitch) = self.pitch {
+            PITCH.with_label_values(&[name]).set(pitch.into());
+        }
+    }
+
+    pub(crate) fn update_baseline_length(&mut self, new_length: Option<f32>, name: &str) {
+        self.baseline_length = new_length;
+
+        if let Some(length) = self.baseline_length {
+            BASELINE_LENGTH
+                .with_label_values(&[name])
+                .set(length.into());
+        }
+    }
+
     pub(crate) fn update_time(&mut self, new_time: NaiveTime) {
         if let Some(mut date) = self.naive_date {
             if let Some(time) = self.naive_time {
-                if new_time < time {
+                // only a wrap from late in the day to early in the next day is midnight, an
+                // earlier time may be a sentence arriving out of order
+                if time - new_time > chrono::Duration::hours(12) {
                     date = date.succ();
                 }
             }
 
+            self.naive_date = Some(date);
+            self.naive_time = Some(new_time);
+
             let time = NaiveDateTime::new(date, new_time);
 
             let utc_time = DateTime::from_utc(time, Utc);
@@ -123,63 +375,797 @@ impl GPSData {
         }
     }
 
+    // Updates the date and time from a sentence with a date, correcting GPS week number rollover
+    pub(crate) fn update_date(
+        &mut self,
+        date: NaiveDate,
+        time: NaiveTime,
+        name: &str,
+    ) -> DateTime<Utc> {
+        let corrected = self.rollover.correct(date);
+
+        if corrected != date {
+            ROLLOVER_CORRECTIONS.with_label_values(&[name]).inc();
+
+            if !self.rollover_corrected {
+                warn!(
+                    "{}: correcting date {} to {} for GPS week number rollover",
+                    name, date, corrected
+                );
+            }
+        }
+
+        self.rollover_corrected = corrected != date;
+        self.naive_date = Some(corrected);
+        self.naive_time = Some(time);
+
+        let reference = DateTime::from_utc(NaiveDateTime::new(corrected, time), Utc);
+
+        self.time = Some(reference);
+        self.year = reference.year();
+
+        reference
+    }
+
+    // The NTP leap indicator at now
+    pub(crate) fn leap_indicator(&self, name: &str, now: NaiveDateTime) -> LeapIndicator {
+        let indicator = self.leap_seconds.indicator(now);
+
+        LEAP_INDICATOR
+            .with_label_values(&[name])
+            .set(indicator.shm_leap().into());
+
+        indicator
+    }
+
+    fn update_leap_offset(&mut self, offset: i32, name: &str) {
+        if self.leap_seconds.offset != Some(offset) {
+            info!("{}: GPS time is {} seconds ahead of UTC", name, offset);
+        }
+
+        self.leap_seconds.offset = Some(offset);
+
+        LEAP_SECONDS.with_label_values(&[name]).set(offset.into());
+    }
+
+    // Checks the date can be trusted to send time to NTP, alerting when trust changes
+    pub(crate) fn date_trusted(&mut self, name: &str) -> bool {
+        let plausible = match self.time {
+            Some(time) => self.rollover.is_plausible(time.naive_utc().date()),
+            None => false,
+        };
+
+        let trusted = plausible && !self.week_mismatch;
+
+        DATE_TRUSTED.with_label_values(&[name]).set(trusted.into());
+
+        match (self.untrusted_date, trusted) {
+            (false, false) => error!(
+                "{}: date {:?} can't be trusted (rollover pivot {}, GPS week mismatch {}), not sending time to NTP",
+                name,
+                self.time.map(|t| t.naive_utc().date()),
+                self.rollover.pivot(),
+                self.week_mismatch
+            ),
+            (true, true) => info!("{}: date is trusted again, sending time to NTP", name),
+            _ => (),
+        }
+
+        self.untrusted_date = !trusted;
+
+        trusted
+    }
+
+    // Checks the time passes the time quality gates to send to NTP, counting why time is held back
+    // and alerting when that changes
+    pub(crate) fn time_trusted(
+        &mut self,
+        reference: DateTime<Utc>,
+        received: Duration,
+        name: &str,
+    ) -> bool {
+        let consistent = self.time_consistent(reference, received);
+        let date_trusted = self.date_trusted(name);
+        let quality = &self.time_quality;
+
+        let held = if !date_trusted {
+            Some("date")
+        } else if self.fix_mode() < quality.min_fix_mode() {
+            Some("fix_mode")
+        } else if self.satellites_used() < quality.min_satellites() {
+            Some("satellites")
+        } else if quality.valid_status() && self.status_valid == Some(false) {
+            Some("status")
+        } else if quality.leap_seconds() && self.leap_second_default == Some(true) {
+            Some("leap_seconds")
+        } else if !consistent {
+            Some("inconsistent")
+        } else {
+            None
+        };
+
+        if let Some(reason) = held {
+            TIME_HELD.with_label_values(&[name, reason]).inc();
+        }
+
+        // date_trusted alerts for the date
+        match (self.time_held, held) {
+            (previous, Some(reason)) if previous != held && reason != "date" => warn!(
+                "{}: time isn't trustworthy ({}), not sending time to NTP",
+                name, reason
+            ),
+            (Some(previous), None) if previous != "date" => {
+                info!("{}: time is trustworthy again, sending time to NTP", name)
+            }
+            _ => (),
+        }
+
+        self.time_held = held;
+
+        held.is_none()
+    }
+
+    // Counts consecutive timing sentences whose offset from the system clock agrees with the
+    // previous one
+    fn time_consistent(&mut self, reference: DateTime<Utc>, received: Duration) -> bool {
+        let offset = reference.timestamp() as f64
+            + f64::from(reference.timestamp_subsec_nanos()) / 1e9
+            - received.as_secs_f64();
+
+        self.consistent_sentences = match self.time_offset {
+            Some(previous) if (offset - previous).abs() < TIME_CONSISTENCY => {
+                self.consistent_sentences + 1
+            }
+            _ => 1,
+        };
+
+        self.time_offset = Some(offset);
+
+        self.consistent_sentences >= self.time_quality.consistent_sentences()
+    }
+
+    // The gpsd mode of the current epoch, or the latest mode known
+    fn fix_mode(&self) -> u32 {
+        match self.epoch.fix.tpv.mode {
+            0 => self
+                .mode
+                .or_else(|| self.fix.as_ref().map(|fix| fix.tpv.mode))
+                .unwrap_or(0),
+            mode => mode,
+        }
+    }
+
+    // Satellites used in the fix from GGA or GNS, or from GSA of the latest epoch
+    fn satellites_used(&self) -> u32 {
+        match (self.satellites_used, &self.fix) {
+            (Some(used), _) => used,
+            (None, Some(fix)) => fix.used_satellites.len() as u32,
+            (None, None) => 0,
+        }
+    }
+
+    // Adds a sentence to the current epoch, completing the previous epoch when the time of fix
+    // changes
+    pub(crate) fn epoch(
+        &mut self,
+        sentence: &'static str,
+        time: Option<NaiveTime>,
+        name: &str,
+        gpsd_tx: &broadcast::Sender<Response>,
+    ) {
+        if let Some(fix) = self.epoch.add(sentence, time) {
+            self.complete_epoch(fix, name, gpsd_tx);
+        }
+    }
+
+    /// Completes the current epoch after the device was quiet
+    pub fn epoch_timeout(&mut self, name: &str, gpsd_tx: &broadcast::Sender<Response>) {
+        if let Some(fix) = self.epoch.timeout() {
+            self.complete_epoch(fix, name, gpsd_tx);
+        }
+    }
+
+    // Fills in the fields of a complete epoch that depend on other epochs and reports it
+    fn complete_epoch(&mut self, mut fix: Fix, name: &str, gpsd_tx: &broadcast::Sender<Response>) {
+        // the systems that reported this epoch are the systems the receiver tracks
+        if !self.navigation_modes.is_empty() {
+            self.update_mode();
+
+            self.learned_systems = self.navigation_modes.keys().flatten().cloned().collect();
+            self.navigation_modes.clear();
+        }
+
+        fix.date_time = match (self.time, fix.time) {
+            (Some(date), Some(time)) => Some(DateTime::from_utc(
+                NaiveDateTime::new(date.naive_utc().date(), time),
+                Utc,
+            )),
+            _ => None,
+        };
+
+        let tpv = &mut fix.tpv;
+
+        tpv.device = name.to_string();
+
+        if tpv.mode == 0 {
+            tpv.mode = self.mode.unwrap_or(0);
+        }
+
+        tpv.time = fix.date_time.map(format_time);
+        tpv.leapseconds = fix
+            .date_time
+            .and_then(|t| self.leap_seconds.gps_utc_offset(t.naive_utc()));
+
+        if tpv.time.is_some() {
+            tpv.ept = Some(NMEA_TIME_ERROR);
+        }
+
+        if let (Some(speed), Some(track)) = (tpv.speed, tpv.track) {
+            let track = track.to_radians();
+
+            tpv.vel_n = Some(speed * track.cos());
+            tpv.vel_e = Some(speed * track.sin());
+        }
+
+        tpv.vel_d = tpv.climb.map(|climb| -climb);
+
+        if fix.time.is_some() {
+            report_tpv(&fix, gpsd_tx);
+        }
+
+        self.report_sky(&fix, name, gpsd_tx);
+
+        self.fix = Some(fix);
+    }
+
+    // Reports the satellites seen in GSV and used in GSA sentences of an epoch if any arrived
+    pub(crate) fn report_sky(&self, fix: &Fix, name: &str, tx: &broadcast::Sender<Response>) {
+        if !fix.sky_changed {
+            return;
+        }
+
+        let used = &fix.used_satellites;
+
+        let mut satellites: Vec<SkySatellite> = self
+            .satellite_views
+            .values()
+            .flat_map(|view| {
+                let system = view.system.clone().or_else(|| view.talker.system());
+
+                view.satellites.iter().map(move |s| {
+                    let mut satellite = SkySatellite::new(system.as_ref(), s.id);
+
+                    satellite.az = s.azimuth.map(|a| a.into());
+                    satellite.el = s.elevation.map(|e| e.into());
+                    satellite.ss = s.cno.map(|c| c.into());
+                    satellite.used =
+                        used.contains(&(satellite_system(system.as_ref(), s.id), s.id));
+
+                    satellite
+                })
+            })
+            .collect();
+
+        // a satellite is reported once for each signal
+        satellites.sort_by_key(|s| s.prn);
+        satellites.dedup_by_key(|s| s.prn);
+
+        let mut sky = Sky::new(name, satellites);
+
+        sky.time = fix.tpv.time.clone();
+        sky.hdop = fix.hdop.map(|d| d.into());
+        sky.pdop = fix.pdop.map(|d| d.into());
+        sky.tdop = fix.tdop.map(|d| d.into());
+        sky.vdop = fix.vdop.map(|d| d.into());
+
+        if tx.send(Response::Sky(Box::new(sky))).is_ok() {}
+    }
+
+    // The time of fix of the current epoch on the date of the latest fix
+    fn epoch_time(&self) -> Option<String> {
+        let date = self.time?.naive_utc().date();
+        let time = NaiveDateTime::new(date, self.epoch.fix.time?);
+
+        Some(format_time(DateTime::from_utc(time, Utc)))
+    }
+
+    fn set_tpv_position(&mut self, lat_lon: &Option<LatLon>, alt: Option<f32>, sep: Option<f32>) {
+        let tpv = &mut self.epoch.fix.tpv;
+
+        if let Some(lat_lon) = lat_lon {
+            tpv.lat = Some(lat_lon.latitude.into());
+            tpv.lon = Some(lat_lon.longitude.into());
+        }
+
+        tpv.alt_msl = alt.map(|alt| alt.into());
+        tpv.alt = tpv.alt_msl;
+        tpv.geoid_sep = sep.map(|sep| sep.into());
+
+        if let (Some(alt), Some(sep)) = (tpv.alt_msl, tpv.geoid_sep) {
+            tpv.alt_hae = Some(alt + sep);
+        }
+    }
+
+    pub(crate) fn rtcm(&mut self, frame: &RTCMFrame, name: &str) {
+        let station_id = match frame.station_id {
+            Some(id) => id.to_string(),
+            None => "".to_string(),
+        };
+
+        RTCM_MESSAGES
+            .with_label_values(&[name, &frame.message_type.to_string(), &station_id])
+            .inc();
+    }
+
+    // reports complete AIS messages
+    pub(crate) fn ais(
+        &mut self,
+        fragment: AISFragment,
+        name: &str,
+        gpsd_tx: &broadcast::Sender<Response>,
+    ) {
+        if let Some(message) = self.assembler.ais(fragment, name) {
+            let ais = Response::Ais(Box::new(Ais::new(name, &message)));
+
+            if gpsd_tx.send(ais).is_ok() {}
+        }
+    }
+
     // updates lat_lon and time
     pub(crate) fn gga(
         &mut self,
         gga: GGAData,
         name: &str,
-        _gpsd_tx: &broadcast::Sender<Response>,
-        _ntp_tx: &TSSender,
+        gpsd_tx: &broadcast::Sender<Response>,
+        ntp_tx: &TSSender,
     ) {
+        self.epoch("GGA", Some(gga.time), name, gpsd_tx);
+
+        if gga.quality == Quality::NoFix {
+            self.epoch.fix.tpv.mode = 1;
+        }
+
+        self.epoch.fix.tpv.status = Some(quality_status(&gga.quality));
+        self.set_tpv_position(&gga.lat_lon, gga.alt, gga.sep);
+        self.epoch.fix.tpv.dgps_age = gga.diff_age.map(|age| age.into());
+        self.epoch.fix.tpv.dgps_sta = gga.diff_station;
+
+        self.status_valid = Some(gga.quality != Quality::NoFix);
+        self.satellites_used = Some(gga.num_satellites);
         self.quality = Some(gga.quality);
+        self.hdop = gga.hdop;
         self.update_lat_lon(gga.lat_lon, name);
         self.update_altitude_msl(gga.alt, name);
 
         self.update_time(gga.time);
+
+        self.report_time(
+            TimingSentence::GGA,
+            gga.time,
+            gga.received,
+            name,
+            gpsd_tx,
+            ntp_tx,
+        );
+    }
+
+    // updates lat_lon and altitude
+    pub(crate) fn gns(
+        &mut self,
+        gns: GNSData,
+        name: &str,
+        gpsd_tx: &broadcast::Sender<Response>,
+        ntp_tx: &TSSender,
+    ) {
+        self.epoch("GNS", Some(gns.time), name, gpsd_tx);
+
+        let position_modes = [
+            &gns.gps_position_mode,
+            &gns.glonass_position_mode,
+            &gns.galileo_position_mode,
+            &gns.beiduo_position_mode,
+        ];
+
+        // the fix comes from the first system with one
+        match position_modes
+            .iter()
+            .find(|mode| ***mode != PositionMode::NoFix)
+        {
+            Some(mode) => self.epoch.fix.tpv.status = Some(position_mode_status(mode)),
+            None => self.epoch.fix.tpv.mode = 1,
+        }
+
+        self.status_valid = Some(self.epoch.fix.tpv.mode != 1);
+        self.satellites_used = Some(gns.num_satellites);
+
+        self.set_tpv_position(&gns.lat_lon, Some(gns.alt), Some(gns.sep));
+        self.epoch.fix.tpv.dgps_age = gns.diff_age.map(|age| age.into());
+        self.epoch.fix.tpv.dgps_sta = gns.diff_station;
+
+        self.update_lat_lon(gns.lat_lon, name);
+        self.update_altitude_msl(Some(gns.alt), name);
+
+        self.update_time(gns.time);
+
+        self.report_time(
+            TimingSentence::GNS,
+            gns.time,
+            gns.received,
+            name,
+            gpsd_tx,
+            ntp_tx,
+        );
+    }
+
+    // records error estimates for the epoch
+    pub(crate) fn gst(&mut self, gst: GSTData, name: &str, gpsd_tx: &broadcast::Sender<Response>) {
+        self.epoch("GST", Some(gst.time), name, gpsd_tx);
+
+        self.epoch.fix.tpv.epx = gst.std_lon.map(|e| e.into());
+        self.epoch.fix.tpv.epy = gst.std_lat.map(|e| e.into());
+        self.epoch.fix.tpv.epv = gst.std_alt.map(|e| e.into());
+
+        if let (Some(epx), Some(epy)) = (self.epoch.fix.tpv.epx, self.epoch.fix.tpv.epy) {
+            self.epoch.fix.tpv.eph = Some(epx.hypot(epy));
+        }
+
+        let gst = Response::Gst(Gst {
+            device: name.to_string(),
+            time: self.epoch_time(),
+            rms: gst.range_rms.map(|e| e.into()),
+            major: gst.std_major.map(|e| e.into()),
+            minor: gst.std_minor.map(|e| e.into()),
+            orient: gst.orientation.map(|o| o.into()),
+            lat: gst.std_lat.map(|e| e.into()),
+            lon: gst.std_lon.map(|e| e.into()),
+            alt: gst.std_alt.map(|e| e.into()),
+        });
+
+        if gpsd_tx.send(gst).is_ok() {}
+    }
+
+    // records error estimates for the epoch and reports the satellite fault detection found
+    pub(crate) fn gbs(&mut self, gbs: GBSData, name: &str, gpsd_tx: &broadcast::Sender<Response>) {
+        self.epoch("GBS", Some(gbs.time), name, gpsd_tx);
+
+        // GST error estimates are preferred
+        if self.epoch.fix.tpv.epx.is_none() && self.epoch.fix.tpv.epy.is_none() {
+            self.epoch.fix.tpv.epx = Some(gbs.err_lon.into());
+            self.epoch.fix.tpv.epy = Some(gbs.err_lat.into());
+            self.epoch.fix.tpv.epv = Some(gbs.err_alt.into());
+            self.epoch.fix.tpv.eph = Some(f64::from(gbs.err_lon).hypot(gbs.err_lat.into()));
+        }
+
+        let svid = match gbs.svid {
+            Some(svid) => svid,
+            None => return,
+        };
+
+        let system = gbs.system.clone().or_else(|| gbs.talker.system());
+        let system = match satellite_system(system.as_ref(), svid) {
+            Some(system) => system.id().to_string(),
+            None => "".to_string(),
+        };
+
+        warn!(
+            "{}: satellite {} of system {} failed fault detection (probability {:?}, bias {:?} m, standard deviation {:?} m)",
+            name, svid, system, gbs.prob, gbs.bias, gbs.stddev
+        );
+
+        SATELLITE_FAULTS
+            .with_label_values(&[name, &system, &svid.to_string()])
+            .inc();
+    }
+
+    // records range residuals
+    pub(crate) fn grs(&mut self, grs: GRSData, name: &str, gpsd_tx: &broadcast::Sender<Response>) {
+        self.epoch("GRS", Some(grs.time), name, gpsd_tx);
+
+        let system = grs.system.id().to_string();
+        let histogram = RANGE_RESIDUALS.with_label_values(&[name, &system]);
+
+        for residual in grs.residuals.iter().flatten() {
+            histogram.observe((*residual).into());
+        }
+    }
+
+    // records course and speed for the epoch
+    pub(crate) fn vtg(&mut self, vtg: VTGData, name: &str, gpsd_tx: &broadcast::Sender<Response>) {
+        self.epoch("VTG", None, name, gpsd_tx);
+
+        self.epoch.fix.tpv.track = vtg.course_over_ground_true.map(|c| c.into());
+        self.epoch.fix.tpv.magtrack = vtg.course_over_ground_magnetic.map(|c| c.into());
+        self.epoch.fix.tpv.speed =
+            Some(f64::from(vtg.speed_over_ground_km) * KILOMETERS_PER_HOUR_TO_METERS_PER_SECOND);
     }
 
     // updates navigation modes
     pub(crate) fn gsa(
         &mut self,
         gsa: GSAData,
-        _name: &str,
-        _gpsd_tx: &broadcast::Sender<Response>,
+        name: &str,
+        gpsd_tx: &broadcast::Sender<Response>,
         _ntp_tx: &TSSender,
     ) {
-        match gsa.system {
+        self.epoch("GSA", None, name, gpsd_tx);
+
+        let system = gsa.system.clone().or_else(|| gsa.talker.system());
+        let fix = &mut self.epoch.fix;
+
+        for id in gsa.satellite_ids.iter().flatten() {
+            let key = (satellite_system(system.as_ref(), *id), *id);
+
+            fix.used_satellites.insert(key);
+        }
+
+        fix.hdop = gsa.hdop;
+        fix.pdop = gsa.pdop;
+        fix.vdop = gsa.vdop;
+        fix.sky_changed = true;
+
+        self.hdop = gsa.hdop;
+        self.pdop = gsa.pdop;
+        self.vdop = gsa.vdop;
+
+        let system = gsa_system(&gsa);
+        let mode = gpsd_mode(&gsa.navigation_mode);
+
+        let label = match &system {
+            Some(system) => system.id().to_string(),
+            None => "".to_string(),
+        };
+
+        NAVIGATION_MODE
+            .with_label_values(&[name, &label])
+            .set(mode.into());
+
+        match system {
             Some(System::BeiDuo) => self.beiduo_navigation_mode = Some(gsa.navigation_mode),
             Some(System::GLONASS) => self.glonass_navigation_mode = Some(gsa.navigation_mode),
             Some(System::GPS) => self.gps_navigation_mode = Some(gsa.navigation_mode),
             Some(System::Galileo) => self.galileo_navigation_mode = Some(gsa.navigation_mode),
-            _ => return,
+            _ => (),
+        }
+
+        self.navigation_modes.insert(system, mode);
+
+        // a combined GSA covers every system
+        let complete = self.navigation_modes.contains_key(&None) || {
+            let expected = self.systems.as_ref().unwrap_or(&self.learned_systems);
+
+            !expected.is_empty()
+                && expected
+                    .iter()
+                    .all(|s| self.navigation_modes.contains_key(&Some(s.clone())))
+        };
+
+        if complete {
+            self.update_mode();
+        }
+    }
+
+    // Updates the fix mode from the best navigation mode of the systems that reported one this
+    // epoch
+    fn update_mode(&mut self) {
+        if let Some(mode) = self.navigation_modes.values().max() {
+            self.mode = Some(*mode);
+        }
+    }
+
+    // updates satellite views
+    pub(crate) fn gsv(&mut self, gsv: GSVData, name: &str, gpsd_tx: &broadcast::Sender<Response>) {
+        // a GSV group is one sentence of the epoch
+        if let Some(view) = self.assembler.gsv(gsv, name) {
+            self.epoch("GSV", None, name, gpsd_tx);
+
+            let key = (view.talker.clone(), view.signal.clone());
+
+            self.satellite_views.insert(key, view);
+            self.epoch.fix.sky_changed = true;
         }
+    }
 
-        let mut modes = Vec::with_capacity(4);
+    // logs complete text messages
+    pub(crate) fn txt(&mut self, txt: TXTData, name: &str) {
+        let message = match self.assembler.txt(txt, name) {
+            Some(m) => m,
+            None => return,
+        };
 
-        if let Some(beiduo) = &self.beiduo_navigation_mode {
-            modes.push(beiduo);
+        match message.msg_type {
+            MessageType::Error => error!("{}: {}", name, message.text),
+            MessageType::Warning => warn!("{}: {}", name, message.text),
+            _ => info!("{}: {}", name, message.text),
         }
+    }
 
-        if let Some(glonass) = &self.glonass_navigation_mode {
-            modes.push(glonass);
+    // updates lat_lon
+    pub(crate) fn garmin_fix(&mut self, fix: GarminFix, name: &str) {
+        if fix.navigation_mode() == NavigationMode::FixNone {
+            return;
         }
 
-        if let Some(gps) = &self.gps_navigation_mode {
-            modes.push(gps);
+        self.update_lat_lon(fix.lat_lon, name);
+    }
+
+    // updates heading
+    pub(crate) fn hdt(&mut self, hdt: HDTData, name: &str, gpsd_tx: &broadcast::Sender<Response>) {
+        self.update_heading(hdt.heading, name);
+
+        self.report_att(name, gpsd_tx);
+    }
+
+    // updates lat_lon and altitude
+    pub(crate) fn quectel_pvt(&mut self, pvt: QuectelPVT, name: &str) {
+        if pvt.navigation_mode() == NavigationMode::FixNone {
+            return;
+        }
+
+        let lat_lon = match (pvt.latitude, pvt.longitude) {
+            (Some(latitude), Some(longitude)) => Some(LatLon {
+                latitude: latitude as f32,
+                longitude: longitude as f32,
+            }),
+            _ => None,
+        };
+
+        self.update_lat_lon(lat_lon, name);
+        self.update_altitude_msl(pvt.altitude, name);
+    }
+
+    // updates heading, pitch, and baseline length
+    pub(crate) fn relposned(
+        &mut self,
+        relposned: UBXNavRelPosNED,
+        name: &str,
+        gpsd_tx: &broadcast::Sender<Response>,
+    ) {
+        if !relposned.relative_position_valid {
+            return;
+        }
+
+        self.update_baseline_length(Some(relposned.length as f32), name);
+
+        // the heading is only valid for a moving base rover
+        if relposned.heading_valid {
+            self.update_heading(Some(relposned.heading as f32), name);
+            self.update_pitch(Some(relposned.pitch() as f32), name);
+        } else {
+            self.update_heading(None, name);
+            self.update_pitch(None, name);
         }
 
-        if let Some(galileo) = &self.galileo_navigation_mode {
-            modes.push(galileo);
+        self.report_att(name, gpsd_tx);
+    }
+
+    // updates rate of turn
+    pub(crate) fn rot(&mut self, rot: ROTData, name: &str, gpsd_tx: &broadcast::Sender<Response>) {
+        self.rate_of_turn = match rot.status {
+            Status::Valid => rot.rate_of_turn,
+            Status::Invalid => None,
+        };
+
+        if let Some(rate_of_turn) = self.rate_of_turn {
+            RATE_OF_TURN
+                .with_label_values(&[name])
+                .set(rate_of_turn.into());
         }
 
-        if modes.len() == 4 {
-            self.mode = Some(modes.iter().map(|m| gpsd_mode(m)).fold(0, u32::max));
+        self.report_att(name, gpsd_tx);
+    }
+
+    // updates heading
+    pub(crate) fn ths(&mut self, ths: THSData, name: &str, gpsd_tx: &broadcast::Sender<Response>) {
+        let heading = match ths.mode {
+            HeadingMode::Invalid => None,
+            _ => ths.heading,
+        };
+
+        self.update_heading(heading, name);
+
+        self.report_att(name, gpsd_tx);
+    }
 
-            self.beiduo_navigation_mode = None;
-            self.galileo_navigation_mode = None;
-            self.glonass_navigation_mode = None;
-            self.gps_navigation_mode = None;
+    fn report_att(&self, name: &str, tx: &broadcast::Sender<Response>) {
+        if self.heading.is_none() && self.rate_of_turn.is_none() {
+            return;
         }
+
+        let att = Response::Att(Att {
+            device: name.to_string(),
+            time: self
+                .time
+                .map(|t| t.format("%Y-%m-%dT%H:%M:%SZ").to_string()),
+            heading: self.heading,
+            pitch: self.pitch,
+            rot: self.rate_of_turn,
+            baseline_length: self.baseline_length,
+        });
+
+        if tx.send(att).is_ok() {}
+    }
+
+    // updates lat_lon and altitude
+    pub(crate) fn pubx_position(
+        &mut self,
+        position: UBXPosition,
+        name: &str,
+        gpsd_tx: &broadcast::Sender<Response>,
+    ) {
+        self.epoch("PUBX00", Some(position.time), name, gpsd_tx);
+
+        let (mode, status) = match position.nav_status {
+            UBXNavigationStatus::NoFix => (1, 0),
+            UBXNavigationStatus::DeadRecokning => (2, 5),
+            UBXNavigationStatus::Standalone2D => (2, 1),
+            UBXNavigationStatus::Standalone3D => (3, 1),
+            UBXNavigationStatus::Differential2D => (2, 2),
+            UBXNavigationStatus::Differential3D => (3, 2),
+            UBXNavigationStatus::Combined => (3, 6),
+            UBXNavigationStatus::TimeOnly => (1, 7),
+            UBXNavigationStatus::Unknown(_) => (0, 0),
+        };
+
+        self.epoch.fix.tpv.mode = mode;
+        self.epoch.fix.tpv.status = Some(status);
+
+        if let Some(lat_lon) = &position.lat_lon {
+            self.epoch.fix.tpv.lat = Some(lat_lon.latitude.into());
+            self.epoch.fix.tpv.lon = Some(lat_lon.longitude.into());
+        }
+
+        // PUBX,00 altitude is above the ellipsoid
+        self.epoch.fix.tpv.alt_hae = Some(position.alt_ref.into());
+        self.epoch.fix.tpv.eph = Some(position.horizontal_accuracy.into());
+        self.epoch.fix.tpv.epv = Some(position.vertical_accuracy.into());
+        self.epoch.fix.tpv.speed =
+            Some(f64::from(position.speed_over_ground) * KILOMETERS_PER_HOUR_TO_METERS_PER_SECOND);
+        self.epoch.fix.tpv.track = Some(position.course_over_ground.into());
+        self.epoch.fix.tpv.climb = Some(-f64::from(position.vertical_velocity));
+        self.epoch.fix.tpv.dgps_age = position.diff_age.map(|age| age.into());
+
+        self.epoch.fix.hdop = Some(position.hdop);
+        self.epoch.fix.tdop = Some(position.tdop);
+        self.epoch.fix.vdop = Some(position.vdop);
+
+        self.hdop = Some(position.hdop);
+        self.tdop = Some(position.tdop);
+        self.vdop = Some(position.vdop);
+
+        self.update_lat_lon(position.lat_lon, name);
+    }
+
+    // reports the satellite status
+    pub(crate) fn pubx_satellites(
+        &mut self,
+        satellites: UBXSatellites,
+        name: &str,
+        gpsd_tx: &broadcast::Sender<Response>,
+    ) {
+        let satellites = satellites
+            .satellites
+            .iter()
+            .map(|s| {
+                let mut satellite = SkySatellite::new(None, s.id);
+
+                satellite.az = s.azimuth.map(|a| a.into());
+                satellite.el = s.elevation.map(|e| e.into());
+                satellite.ss = Some(s.cno.into());
+                satellite.used = s.status == UBXSatelliteStatus::Used;
+
+                satellite
+            })
+            .collect();
+
+        let mut sky = Sky::new(name, satellites);
+
+        sky.time = self.epoch_time();
+        sky.hdop = self.hdop.map(|d| d.into());
+        sky.pdop = self.pdop.map(|d| d.into());
+        sky.tdop = self.tdop.map(|d| d.into());
+        sky.vdop = self.vdop.map(|d| d.into());
+
+        if gpsd_tx.send(Response::Sky(Box::new(sky))).is_ok() {}
     }
 
     // updates lat_lon and time
@@ -187,16 +1173,128 @@ impl GPSData {
         &mut self,
         rmc: RMCData,
         name: &str,
-        _gpsd_tx: &broadcast::Sender<Response>,
-        _ntp_tx: &TSSender,
+        gpsd_tx: &broadcast::Sender<Response>,
+        ntp_tx: &TSSender,
     ) {
+        self.epoch("RMC", Some(rmc.time), name, gpsd_tx);
+
+        if rmc.position_mode == PositionMode::NoFix {
+            self.epoch.fix.tpv.mode = 1;
+        } else {
+            self.epoch.fix.tpv.status = Some(position_mode_status(&rmc.position_mode));
+        }
+
+        if let Some(lat_lon) = &rmc.lat_lon {
+            self.epoch.fix.tpv.lat = Some(lat_lon.latitude.into());
+            self.epoch.fix.tpv.lon = Some(lat_lon.longitude.into());
+        }
+
+        self.epoch.fix.tpv.speed = Some(f64::from(rmc.speed) * KNOTS_TO_METERS_PER_SECOND);
+        self.epoch.fix.tpv.track = rmc.course_over_ground.map(|c| c.into());
+        self.epoch.fix.tpv.magvar = match (rmc.magnetic_variation, rmc.magnetic_variation_east_west)
+        {
+            (Some(variation), Some(EastWest::West)) => Some(-f64::from(variation)),
+            (Some(variation), _) => Some(variation.into()),
+            (None, _) => None,
+        };
+
+        self.status_valid = Some(rmc.status == Status::Valid);
+
         self.update_lat_lon(rmc.lat_lon, name);
 
-        let reference = NaiveDateTime::new(rmc.date, rmc.time);
-        let reference = DateTime::from_utc(reference, Utc);
+        self.update_date(rmc.date, rmc.time, name);
 
-        self.time = Some(reference);
-        self.year = reference.year();
+        self.report_time(
+            TimingSentence::RMC,
+            rmc.time,
+            rmc.received,
+            name,
+            gpsd_tx,
+            ntp_tx,
+        );
+    }
+
+    // cross-checks the GPS week against the date
+    pub(crate) fn pubx_time(
+        &mut self,
+        time: UBXTime,
+        name: &str,
+        gpsd_tx: &broadcast::Sender<Response>,
+        ntp_tx: &TSSender,
+    ) {
+        // the firmware default may be out of date
+        self.leap_second_default = Some(time.leap_second_default);
+
+        if !time.leap_second_default {
+            self.update_leap_offset(time.leap_seconds as i32, name);
+        }
+
+        // the week is in GPS time which is ahead of the UTC date by the leap seconds
+        let leap_seconds = self.leap_seconds.offset.unwrap_or(time.leap_seconds as i32);
+        let utc = NaiveDateTime::new(self.rollover.correct(time.date), time.time);
+        let date = (utc + chrono::Duration::seconds(leap_seconds.into())).date();
+
+        // the week may be truncated to 10 bits, so only compare within a rollover period
+        let week = gps_week(date);
+        let mismatch = week % ROLLOVER_WEEKS != i64::from(time.week) % ROLLOVER_WEEKS;
+
+        if mismatch && !self.week_mismatch {
+            warn!(
+                "{}: GPS week {} disagrees with date {} (GPS week {})",
+                name, time.week, date, week
+            );
+        }
+
+        self.week_mismatch = mismatch;
+
+        // PUBX,04 dates the time it reports
+        if self.timing_sentence == TimingSentence::PUBX04 {
+            self.update_date(time.date, time.time, name);
+        }
+
+        self.report_time(
+            TimingSentence::PUBX04,
+            time.time,
+            time.received,
+            name,
+            gpsd_tx,
+            ntp_tx,
+        );
+    }
+
+    // updates the leap second offset and the announced leap second
+    pub(crate) fn nav_timels(&mut self, timels: UBXNavTimeLS, name: &str) {
+        // the firmware default may be out of date
+        if timels.valid_current && timels.current_source != 0 {
+            self.update_leap_offset(timels.current.into(), name);
+        }
+
+        if !timels.valid_time_to_event {
+            return;
+        }
+
+        let now = match self.time {
+            Some(t) => t.naive_utc(),
+            None => Utc::now().naive_utc(),
+        };
+
+        let announced = match timels.change {
+            0 => None,
+            change => Some(LeapSecond {
+                at: now + chrono::Duration::seconds(timels.time_to_event.into()),
+                change: change.into(),
+            }),
+        };
+
+        if announced.is_some() && self.leap_seconds.announced.is_none() {
+            info!(
+                "{}: leap second change of {} announced in {} seconds",
+                name, timels.change, timels.time_to_event
+            );
+        }
+
+        self.leap_seconds.announced = announced;
+        self.leap_seconds.receiver_announces = true;
     }
 
     // updates time and date
@@ -227,24 +1325,133 @@ impl GPSData {
             None => return,
         };
 
-        let received = match zda.received {
+        self.epoch("ZDA", Some(time), name, gpsd_tx);
+
+        let date = match NaiveDate::from_ymd_opt(year, month, day) {
+            Some(d) => d,
+            None => {
+                error!("{}: invalid date {}-{}-{}", name, year, month, day);
+                return;
+            }
+        };
+
+        self.update_date(date, time, name);
+
+        self.report_time(
+            TimingSentence::ZDA,
+            time,
+            zda.received,
+            name,
+            gpsd_tx,
+            ntp_tx,
+        );
+    }
+
+    // Sends the time of a timing sentence to NTP and gpsd when it is the device's timing sentence
+    // and the time is trustworthy
+    fn report_time(
+        &mut self,
+        sentence: TimingSentence,
+        time: NaiveTime,
+        received: Option<Duration>,
+        name: &str,
+        gpsd_tx: &broadcast::Sender<Response>,
+        ntp_tx: &TSSender,
+    ) {
+        let timing = match self.timing_sentence {
+            // PUBX,04 isn't part of an epoch
+            TimingSentence::First => {
+                sentence != TimingSentence::PUBX04
+                    && !std::mem::replace(&mut self.epoch.fix.timed, true)
+            }
+            timing => timing == sentence,
+        };
+
+        if !timing {
+            return;
+        }
+
+        let received = match received {
             Some(d) => d,
             None => timestamp(),
         };
 
-        let date = NaiveDate::from_ymd(year, month, day);
-        let reference = NaiveDateTime::new(date, time);
-        let reference = DateTime::from_utc(reference, Utc);
+        // time-only sentences use the date of the latest RMC or ZDA, or the system clock
+        let reference = match self.naive_date {
+            Some(date) => DateTime::from_utc(NaiveDateTime::new(date, time), Utc),
+            None => {
+                let date = infer_date(time, Utc::now().naive_utc());
 
-        self.time = Some(reference);
-        self.year = reference.year();
+                self.update_date(date, time, name)
+            }
+        };
+
+        if !self.time_trusted(reference, received, name) {
+            return;
+        }
 
         report_toff(reference, received, name, gpsd_tx);
-        report_tpv(reference, self.mode, name, gpsd_tx);
         report_ntp(reference, received, ntp_tx);
     }
 }
 
+// The date that puts a time of day closest to now
+pub(crate) fn infer_date(time: NaiveTime, now: NaiveDateTime) -> NaiveDate {
+    let today = now.date();
+
+    [today.pred(), today, today.succ()]
+        .into_iter()
+        .min_by_key(|date| {
+            (NaiveDateTime::new(*date, time) - now)
+                .num_milliseconds()
+                .abs()
+        })
+        .unwrap_or(today)
+}
+
+fn position_mode_status(position_mode: &PositionMode) -> u32 {
+    match position_mode {
+        PositionMode::NoFix => 0,
+        PositionMode::AutonomousGNSSFix => 1,
+        PositionMode::DifferentialGNSSFix => 2,
+        PositionMode::RTKFixed => 3,
+        PositionMode::RTKFloat => 4,
+        PositionMode::EstimatedDeadReckoningFix => 5,
+    }
+}
+
+fn quality_status(quality: &Quality) -> u32 {
+    match quality {
+        Quality::NoFix => 0,
+        Quality::AutonomousGNSSFix | Quality::Fix2D | Quality::Fix3D => 1,
+        Quality::DifferentialGNSSFix => 2,
+        Quality::RTKFixed => 3,
+        Quality::RTKFloat => 4,
+        Quality::EstimatedDeadReckoningFix => 5,
+    }
+}
+
+// The system of a GSA from the system ID, the talker, or the satellites used, None for a combined
+// GSA covering every system
+fn gsa_system(gsa: &GSAData) -> Option<System> {
+    if let Some(system) = gsa.system.clone().or_else(|| gsa.talker.system()) {
+        return Some(system);
+    }
+
+    let mut systems = gsa
+        .satellite_ids
+        .iter()
+        .flatten()
+        .map(|id| satellite_system(None, *id));
+
+    let first = systems.next()??;
+
+    match systems.all(|system| system.as_ref() == Some(&first)) {
+        true => Some(first),
+        false => None,
+    }
+}
+
 fn gpsd_mode(navigation_mode: &NavigationMode) -> u32 {
     match navigation_mode {
         NavigationMode::FixNone => 1,
@@ -253,6 +1460,12 @@ fn gpsd_mode(navigation_mode: &NavigationMode) -> u32 {
     }
 }
 
+fn report_tpv(fix: &Fix, tx: &broadcast::Sender<Response>) {
+    let tpv = Response::Tpv(Box::new(fix.tpv.clone()));
+
+    if tx.send(tpv).is_ok() {}
+}
+
 fn report_ntp(reference: DateTime<Utc>, received: Duration, tx: &TSSender) {
     let ts = Timestamp {
         received_sec: received.as_secs(),
@@ -281,22 +1494,8 @@ fn report_toff(
     if tx.send(toff).is_ok() {}
 }
 
-fn report_tpv(
-    reference: DateTime<Utc>,
-    mode: Option<u32>,
-    name: &str,
-    tx: &broadcast::Sender<Response>,
-) {
-    let time = reference.format("%Y-%m-%dT%H:%M:%SZ").to_string();
-    let mode = mode.unwrap_or(0);
-
-    let tpv = Response::Tpv(Tpv {
-        device: name.to_string(),
-        time,
-        mode,
-    });
-
-    if tx.send(tpv).is_ok() {}
+fn format_time(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()
 }
 
 fn timestamp() -> Duration {
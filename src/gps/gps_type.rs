@@ -3,13 +3,34 @@ use serde::Deserialize;
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[serde(rename_all = "lowercase")]
 pub enum GpsType {
+    /// Detect the receiver type from its output
+    Auto,
+    Garmin,
     Generic,
     #[serde(rename = "mkt")]
     MKT,
+    Quectel,
+    #[serde(rename = "sirf")]
+    SiRF,
     #[serde(rename = "ublox_nmea")]
     UBloxNMEA,
 }
 
+impl GpsType {
+    /// Name of the type in the configuration file
+    pub fn name(&self) -> &'static str {
+        match self {
+            GpsType::Auto => "auto",
+            GpsType::Garmin => "garmin",
+            GpsType::Generic => "generic",
+            GpsType::MKT => "mkt",
+            GpsType::Quectel => "quectel",
+            GpsType::SiRF => "sirf",
+            GpsType::UBloxNMEA => "ublox_nmea",
+        }
+    }
+}
+
 impl Default for GpsType {
     fn default() -> Self {
         GpsType::Generic
@@ -0,0 +1,192 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use std::{fs, io, path::Path};
+
+/// Leap seconds are announced to NTP up to this long before they occur
+pub const LEAP_WARNING: i64 = 28;
+
+/// GPS time was 19 seconds behind TAI at the GPS epoch and does not have leap seconds
+pub const TAI_GPS_OFFSET: i32 = 19;
+
+/// The NTP leap indicator for a pending leap second
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LeapIndicator {
+    NoWarning,
+    /// The last minute of the day has 61 seconds
+    Insert,
+    /// The last minute of the day has 59 seconds
+    Delete,
+}
+
+impl LeapIndicator {
+    /// The value of the NTP SHM `leap` field
+    pub fn shm_leap(&self) -> i32 {
+        match self {
+            LeapIndicator::NoWarning => 0,
+            LeapIndicator::Insert => 1,
+            LeapIndicator::Delete => 2,
+        }
+    }
+}
+
+/// A leap second event
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeapSecond {
+    /// The UTC time the event ends, midnight after the inserted or deleted second
+    pub at: NaiveDateTime,
+    /// 1 for an inserted second, -1 for a deleted second
+    pub change: i32,
+}
+
+/// Leap second state of a device from receiver data and an IERS leap second list
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LeapSeconds {
+    /// The difference between GPS time and UTC in seconds reported by the receiver
+    pub offset: Option<i32>,
+    /// The next leap second announced by the receiver
+    pub announced: Option<LeapSecond>,
+    /// The receiver reports announced leap seconds, so the list is not consulted for them
+    pub receiver_announces: bool,
+    pub list: Option<LeapSecondsList>,
+}
+
+impl LeapSeconds {
+    pub fn new(list: Option<LeapSecondsList>) -> Self {
+        LeapSeconds {
+            list,
+            ..Default::default()
+        }
+    }
+
+    /// The difference between GPS time and UTC at `now` from the receiver or the list
+    pub fn gps_utc_offset(&self, now: NaiveDateTime) -> Option<i32> {
+        match self.offset {
+            Some(offset) => Some(offset),
+            None => self.list.as_ref()?.gps_utc_offset(now),
+        }
+    }
+
+    /// The next leap second after `now` from the receiver or the list
+    pub fn next(&self, now: NaiveDateTime) -> Option<LeapSecond> {
+        let next = match self.receiver_announces {
+            true => self.announced.clone(),
+            false => self.list.as_ref()?.next(now),
+        };
+
+        next.filter(|leap| leap.at > now)
+    }
+
+    /// The NTP leap indicator at `now`
+    pub fn indicator(&self, now: NaiveDateTime) -> LeapIndicator {
+        match self.next(now) {
+            Some(leap) if leap.at - now > Duration::days(LEAP_WARNING) => LeapIndicator::NoWarning,
+            Some(leap) if leap.change > 0 => LeapIndicator::Insert,
+            Some(leap) if leap.change < 0 => LeapIndicator::Delete,
+            _ => LeapIndicator::NoWarning,
+        }
+    }
+}
+
+/// The IERS `leap-seconds.list` file
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeapSecondsList {
+    /// The UTC time each TAI - UTC offset starts, in order
+    pub offsets: Vec<(NaiveDateTime, i32)>,
+    /// The list is not valid after this time
+    pub expires: Option<NaiveDateTime>,
+}
+
+impl LeapSecondsList {
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let source = fs::read_to_string(path)?;
+
+        LeapSecondsList::parse(&source)
+    }
+
+    /// Parses the list.  Lines hold NTP seconds and the TAI - UTC offset from that time, `#@`
+    /// lines hold the expiration in NTP seconds, and other `#` lines are comments.
+    pub fn parse(source: &str) -> io::Result<Self> {
+        let mut offsets = Vec::new();
+        let mut expires = None;
+
+        for line in source.lines() {
+            if let Some(expiration) = line.strip_prefix("#@") {
+                expires = Some(ntp_time(expiration.trim())?);
+
+                continue;
+            }
+
+            let line = match line.find('#') {
+                Some(comment) => &line[..comment],
+                None => line,
+            };
+
+            let mut fields = line.split_whitespace();
+
+            let (time, offset) = match (fields.next(), fields.next()) {
+                (Some(time), Some(offset)) => (time, offset),
+                (None, _) => continue,
+                (Some(_), None) => return Err(invalid(line)),
+            };
+
+            let offset = offset.parse().map_err(|_| invalid(line))?;
+
+            offsets.push((ntp_time(time)?, offset));
+        }
+
+        if offsets.is_empty() {
+            return Err(invalid("no leap seconds"));
+        }
+
+        Ok(LeapSecondsList { offsets, expires })
+    }
+
+    pub fn is_expired(&self, now: NaiveDateTime) -> bool {
+        match self.expires {
+            Some(expires) => now >= expires,
+            None => false,
+        }
+    }
+
+    /// The difference between GPS time and UTC at `now`
+    pub fn gps_utc_offset(&self, now: NaiveDateTime) -> Option<i32> {
+        let (_, tai_utc) = self.offsets.iter().rev().find(|(start, _)| *start <= now)?;
+
+        Some(tai_utc - TAI_GPS_OFFSET)
+    }
+
+    /// The next leap second after `now`, None if there is none or the list has expired
+    pub fn next(&self, now: NaiveDateTime) -> Option<LeapSecond> {
+        if self.is_expired(now) {
+            return None;
+        }
+
+        let next = self.offsets.iter().position(|(start, _)| *start > now)?;
+
+        // the first entry starts the list, it isn't a leap second
+        if next == 0 {
+            return None;
+        }
+
+        let (at, offset) = self.offsets[next];
+        let (_, previous) = self.offsets[next - 1];
+
+        Some(LeapSecond {
+            at,
+            change: offset - previous,
+        })
+    }
+}
+
+// NTP seconds count from 1900-01-01
+fn ntp_time(seconds: &str) -> io::Result<NaiveDateTime> {
+    let seconds: i64 = seconds.parse().map_err(|_| invalid(seconds))?;
+
+    Ok(NaiveDate::from_ymd(1900, 1, 1).and_hms(0, 0, 0) + Duration::seconds(seconds))
+}
+
+fn invalid(line: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid leap seconds list entry: {:?}", line),
+    )
+}
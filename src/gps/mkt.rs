@@ -1,23 +1,50 @@
 // For GlobalTop MKT devices
 
 use crate::{
+    configuration::MKTConfig,
     gps::add_message,
     nmea::{device::SerialCodec, parser::Result, parser_util::*, MessageSetting, NMEA},
 };
-use futures_util::sink::SinkExt;
+use futures_util::{sink::SinkExt, stream::StreamExt};
 use nom::{branch::*, bytes::complete::*, combinator::*, error::*, sequence::*};
-use serde::Serialize;
-use tracing::{debug, error, info};
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Deserialize, Serialize,
+};
+use std::{fmt::Debug, time::Duration};
+use tokio::time::{timeout_at, Instant};
+use tokio_serial::SerialPort;
+use tracing::{debug, error, info, trace};
 
 pub const OUTPUT_MESSAGES: [&str; 7] = ["GGA", "GLL", "GSA", "GSV", "MCHN", "RMC", "VTG"];
 
+pub const SET_FIX_INTERVAL: u32 = 220;
+pub const SET_PPS_SYNC: u32 = 255;
+pub const SET_PPS: u32 = 285;
+pub const SET_NMEA_OUTPUT: u32 = 314;
+
+/// How long to wait for the receiver to respond to a command
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(1);
+
 #[derive(Clone, Default, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct MKT {}
+pub struct MKT {
+    config: MKTConfig,
+}
 
 impl MKT {
+    pub fn new(config: MKTConfig) -> Self {
+        MKT { config }
+    }
+
     pub async fn configure(&self, serial: &mut SerialCodec, messages: &Vec<MessageSetting>) {
         debug!("configuring MKT with sentences {:?}", messages);
 
+        if let Some(restart) = &self.config.restart {
+            self.restart(serial, restart).await;
+        }
+
+        self.release(serial).await;
+
         let mut set = MKTSetNMEAOutput::default();
 
         for message in messages {
@@ -64,9 +91,50 @@ impl MKT {
             .collect::<Vec<String>>()
             .join(", ");
 
-        match serial.send(set).await {
-            Ok(_) => info!("enabling messages {}", summary),
-            Err(e) => error!("unable to enable messages {}, {:?}", summary, e),
+        command(
+            serial,
+            set,
+            SET_NMEA_OUTPUT,
+            &format!("enabled messages to {}", summary),
+        )
+        .await;
+
+        if let Some(interval) = self.config.fix_interval {
+            let set = MKTSetFixInterval { interval };
+
+            command(
+                serial,
+                set,
+                SET_FIX_INTERVAL,
+                &format!("fix interval to {} ms", interval),
+            )
+            .await;
+        }
+
+        if let Some(enabled) = self.config.pps_sync {
+            let set = MKTSetPPSSync { enabled };
+
+            command(
+                serial,
+                set,
+                SET_PPS_SYNC,
+                &format!("PPS sync to {}", enabled),
+            )
+            .await;
+        }
+
+        if let Some(mode) = &self.config.pps {
+            let set = MKTSetPPS {
+                mode: mode.value(),
+                pulse_width: self.config.pps_pulse_width(),
+            };
+
+            command(serial, set, SET_PPS, &format!("PPS to {:?}", mode)).await;
+        }
+
+        // last, the receiver stops listening at the old baud rate
+        if let Some(baud_rate) = self.config.baud_rate {
+            self.baud_rate(serial, baud_rate).await;
         }
     }
 
@@ -96,22 +164,185 @@ impl MKT {
                     map(mkt_001, MKTData::Acknowledge),
                     map(mkt_010, MKTData::SystemMessage),
                     map(mkt_011, MKTData::TextMessage),
+                    map(mkt_705, MKTData::Release),
                 )),
                 NMEA::PMKT,
             ),
         )(input)
     }
+
+    // Switches the receiver and the serial port to baud_rate
+    async fn baud_rate(&self, serial: &mut SerialCodec, baud_rate: u32) {
+        // PMTK251 is not acknowledged, the receiver switches immediately
+        if let Err(e) = serial.send(MKTSetBaudRate { baud_rate }).await {
+            error!("unable to set baud rate to {}: {:?}", baud_rate, e);
+            return;
+        }
+
+        match serial.get_mut().set_baud_rate(baud_rate) {
+            Ok(_) => info!("set baud rate to {}", baud_rate),
+            Err(e) => error!("unable to switch serial port to {}: {:?}", baud_rate, e),
+        }
+    }
+
+    // Logs the firmware release of the receiver
+    async fn release(&self, serial: &mut SerialCodec) {
+        if let Err(e) = serial.send(MKTReleasePoll {}).await {
+            error!("unable to request firmware release: {:?}", e);
+            return;
+        }
+
+        let response = response(serial, |data| matches!(data, MKTData::Release(_))).await;
+
+        match response {
+            Some(MKTData::Release(r)) => {
+                info!("firmware release {} build {}", r.release, r.build_id)
+            }
+            _ => error!("firmware release not reported"),
+        }
+    }
+
+    // Restarts the receiver and waits for it to start up
+    async fn restart(&self, serial: &mut SerialCodec, restart: &MKTRestart) {
+        if let Err(e) = serial.send(restart.clone()).await {
+            error!("unable to send {:?} restart: {:?}", restart, e);
+            return;
+        }
+
+        let response = response(serial, |data| {
+            matches!(data, MKTData::SystemMessage(MKTSystemMessage::Startup))
+        })
+        .await;
+
+        match response {
+            Some(_) => info!("{:?} restart complete", restart),
+            None => error!("no startup message after {:?} restart", restart),
+        }
+    }
+}
+
+// Sends a $PMTK command and waits for its acknowledgement, returns true when it succeeded
+async fn command<T>(serial: &mut SerialCodec, command: T, id: u32, description: &str) -> bool
+where
+    T: Serialize + Debug,
+{
+    trace!("sending {:?}", command);
+
+    if let Err(e) = serial.send(command).await {
+        error!("unable to set {}: {:?}", description, e);
+        return false;
+    }
+
+    let response = response(serial, |data| match data {
+        MKTData::Acknowledge(a) => a.command == id,
+        _ => false,
+    })
+    .await;
+
+    match response {
+        Some(MKTData::Acknowledge(a)) if a.result == MKTResult::Succeeded => {
+            info!("set {}", description);
+            true
+        }
+        Some(MKTData::Acknowledge(a)) => {
+            error!("unable to set {}: {:?}", description, a.result);
+            false
+        }
+        _ => {
+            error!("setting {} not acknowledged", description);
+            false
+        }
+    }
+}
+
+// Waits for the first MKT sentence from the receiver that `matches`
+async fn response<F>(serial: &mut SerialCodec, matches: F) -> Option<MKTData>
+where
+    F: Fn(&MKTData) -> bool,
+{
+    let deadline = Instant::now() + RESPONSE_TIMEOUT;
+
+    loop {
+        match timeout_at(deadline, serial.next()).await {
+            Ok(Some(Ok(NMEA::PMKT(data)))) if matches(&data) => return Some(data),
+            Ok(Some(Ok(_))) => continue,
+            Ok(Some(Err(e))) => {
+                error!("error waiting for command response: {:?}", e);
+                return None;
+            }
+            Ok(None) | Err(_) => return None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum MKTData {
     Acknowledge(MKTAcknowledge),
+    Release(MKTRelease),
     SystemMessage(MKTSystemMessage),
     TextMessage(MKTTextMessage),
 }
 
+impl Serialize for MKTData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            MKTData::Acknowledge(a) => {
+                let flag = match a.result {
+                    MKTResult::Invalid => 0,
+                    MKTResult::Unsupported => 1,
+                    MKTResult::Failed => 2,
+                    MKTResult::Succeeded => 3,
+                    MKTResult::Unhandled(u) => u,
+                };
+
+                let mut state = serializer.serialize_struct("MKTAcknowledge", 2)?;
+                state.serialize_field("command", &a.command)?;
+                state.serialize_field("flag", &flag)?;
+                state.end()
+            }
+            MKTData::Release(r) => {
+                let mut state = serializer.serialize_struct("MKTRelease", 4)?;
+                state.serialize_field("release", &r.release)?;
+                state.serialize_field("build_id", &r.build_id)?;
+
+                // older firmware omits the trailing fields
+                if let Some(model) = &r.model {
+                    state.serialize_field("model", model)?;
+                }
+
+                if let Some(sdk_version) = &r.sdk_version {
+                    state.serialize_field("sdk_version", sdk_version)?;
+                }
+
+                state.end()
+            }
+            MKTData::SystemMessage(m) => {
+                let message = match m {
+                    MKTSystemMessage::Unknown => 0,
+                    MKTSystemMessage::Startup => 1,
+                    MKTSystemMessage::ExtendedPredictionOrbit => 2,
+                    MKTSystemMessage::Normal => 3,
+                    MKTSystemMessage::Unhandled(u) => *u,
+                };
+
+                let mut state = serializer.serialize_struct("MKTSystemMessage", 1)?;
+                state.serialize_field("message", &message)?;
+                state.end()
+            }
+            MKTData::TextMessage(t) => {
+                let mut state = serializer.serialize_struct("MKTTextMessage", 1)?;
+                state.serialize_field("message", &t.message)?;
+                state.end()
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
-pub enum MKTAcknowledge {
+pub enum MKTResult {
     Invalid,
     Unsupported,
     Failed,
@@ -119,35 +350,46 @@ pub enum MKTAcknowledge {
     Unhandled(u32),
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct MKTAcknowledge {
+    pub command: u32,
+    pub result: MKTResult,
+}
+
 pub(crate) fn mkt_001<'a>(input: &'a str) -> Result<&'a str, MKTAcknowledge> {
     let result = parse_message(
         "MKT 001",
-        preceded(preceded(tag("PMTK001"), comma), uint32),
-        |m| match m {
-            0 => MKTAcknowledge::Invalid,
-            1 => MKTAcknowledge::Unsupported,
-            2 => MKTAcknowledge::Failed,
-            3 => MKTAcknowledge::Succeeded,
-            u => MKTAcknowledge::Unhandled(u),
+        tuple((preceded(tag("PMTK001,"), terminated(uint32, comma)), uint32)),
+        |(command, flag)| MKTAcknowledge {
+            command,
+            result: match flag {
+                0 => MKTResult::Invalid,
+                1 => MKTResult::Unsupported,
+                2 => MKTResult::Failed,
+                3 => MKTResult::Succeeded,
+                u => MKTResult::Unhandled(u),
+            },
         },
     )(input);
 
     if let Ok((_, ref acknowledgement)) = result {
-        match acknowledgement {
-            MKTAcknowledge::Invalid => {
-                error!("Invalid PMKT command");
+        let command = acknowledgement.command;
+
+        match acknowledgement.result {
+            MKTResult::Invalid => {
+                error!("Invalid PMTK{:03} command", command);
             }
-            MKTAcknowledge::Unsupported => {
-                error!("Unsupported PMKT command");
+            MKTResult::Unsupported => {
+                error!("Unsupported PMTK{:03} command", command);
             }
-            MKTAcknowledge::Failed => {
-                error!("Failed (but valid) PMKT command");
+            MKTResult::Failed => {
+                error!("Failed (but valid) PMTK{:03} command", command);
             }
-            MKTAcknowledge::Succeeded => {
-                info!("Successful PMKT command");
+            MKTResult::Succeeded => {
+                info!("Successful PMTK{:03} command", command);
             }
-            MKTAcknowledge::Unhandled(u) => {
-                error!("Unhandled PMKT acknowledgement {}", u);
+            MKTResult::Unhandled(u) => {
+                error!("Unhandled PMTK{:03} acknowledgement {}", command, u);
             }
         };
     }
@@ -215,3 +457,115 @@ pub struct MKTSetNMEAOutput {
     _17: u32,
     pub mchn: u32,
 }
+
+/// Firmware release information, the response to PMTK605
+#[derive(Clone, Debug, PartialEq)]
+pub struct MKTRelease {
+    pub release: String,
+    pub build_id: String,
+    pub model: Option<String>,
+    pub sdk_version: Option<String>,
+}
+
+pub(crate) fn mkt_705(input: &str) -> Result<&str, MKTRelease> {
+    parse_message(
+        "MKT 705",
+        tuple((
+            preceded(tag("PMTK705,"), terminated(any, comma)),
+            any,
+            opt(preceded(comma, any)),
+            opt(preceded(comma, any)),
+        )),
+        |(release, build_id, model, sdk_version)| MKTRelease {
+            release,
+            build_id,
+            model,
+            sdk_version,
+        },
+    )(input)
+}
+
+/// Sets the time between fixes in milliseconds
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct MKTSetFixInterval {
+    pub interval: u32,
+}
+
+/// Sets the baud rate of the receiver's serial port
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct MKTSetBaudRate {
+    pub baud_rate: u32,
+}
+
+/// Only output fix sentences after the PPS pulse
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct MKTSetPPSSync {
+    pub enabled: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum MKTPPSMode {
+    #[serde(rename = "disabled")]
+    Disabled,
+    #[serde(rename = "first_fix")]
+    FirstFix,
+    #[serde(rename = "fix_3d")]
+    Fix3D,
+    #[serde(rename = "fix_2d_3d")]
+    Fix2D3D,
+    #[serde(rename = "always")]
+    Always,
+}
+
+impl MKTPPSMode {
+    /// PMTK285 PPS type
+    pub fn value(&self) -> u32 {
+        match self {
+            MKTPPSMode::Disabled => 0,
+            MKTPPSMode::FirstFix => 1,
+            MKTPPSMode::Fix3D => 2,
+            MKTPPSMode::Fix2D3D => 3,
+            MKTPPSMode::Always => 4,
+        }
+    }
+}
+
+/// Sets when the PPS pulse is output and its width in milliseconds
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct MKTSetPPS {
+    pub mode: u32,
+    pub pulse_width: u32,
+}
+
+/// Requests the firmware release, answered by PMTK705
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct MKTReleasePoll {}
+
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[serde(rename_all = "snake_case")]
+pub enum MKTRestart {
+    /// Restart using all available data
+    Hot,
+    /// Restart without ephemeris
+    Warm,
+    /// Restart without time, position, almanac, or ephemeris
+    Cold,
+    /// Cold restart that also resets the configuration to the factory defaults
+    FullCold,
+}
+
+impl Serialize for MKTRestart {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let name = match self {
+            MKTRestart::Hot => "MKTHotRestart",
+            MKTRestart::Warm => "MKTWarmRestart",
+            MKTRestart::Cold => "MKTColdRestart",
+            MKTRestart::FullCold => "MKTFullColdRestart",
+        };
+
+        serializer.serialize_struct(name, 0)?.end()
+    }
+}
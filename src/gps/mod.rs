@@ -1,17 +1,71 @@
+mod detect;
 mod driver;
+mod epoch;
+mod garmin;
 mod generic;
 mod gps_data;
 mod gps_type;
+mod leap_seconds;
 mod mkt;
+mod quectel;
+mod rollover;
+mod sirf;
 mod ublox_nmea;
 
+pub use detect::detect;
+pub use detect::identify;
+pub use detect::Detection;
+pub use detect::DETECT_TIMEOUT;
+pub use detect::GPS_TYPES;
+pub use detect::QUECTEL_TIMEOUT;
 pub use driver::add_message;
 pub use driver::Driver;
+pub use epoch::EpochAssembler;
+pub use epoch::Fix;
+pub use epoch::EPOCH_TIMEOUT;
+pub use garmin::Garmin;
+pub use garmin::GarminData;
+pub use garmin::GarminErrorEstimate;
+pub use garmin::GarminFix;
+pub use garmin::GarminRetention;
+pub use garmin::GarminSensorStatus;
+pub use garmin::GarminSetOutput;
+pub use garmin::GarminTest;
 pub use generic::Generic;
 pub use gps_data::GPSData;
 pub use gps_type::GpsType;
+pub use leap_seconds::LeapIndicator;
+pub use leap_seconds::LeapSecond;
+pub use leap_seconds::LeapSeconds;
+pub use leap_seconds::LeapSecondsList;
+pub use leap_seconds::LEAP_WARNING;
+pub use leap_seconds::TAI_GPS_OFFSET;
+pub use mkt::MKTAcknowledge;
 pub use mkt::MKTData;
+pub use mkt::MKTPPSMode;
+pub use mkt::MKTRelease;
+pub use mkt::MKTRestart;
+pub use mkt::MKTResult;
 pub use mkt::MKT;
+pub use quectel::Quectel;
+pub use quectel::QuectelAcknowledge;
+pub use quectel::QuectelData;
+pub use quectel::QuectelPPSMode;
+pub use quectel::QuectelPVT;
+pub use quectel::QuectelResponse;
+pub use quectel::QuectelResult;
+pub use quectel::QuectelVersion;
+pub use rollover::build_date;
+pub use rollover::full_year;
+pub use rollover::gps_week;
+pub use rollover::Rollover;
+pub use rollover::ROLLOVER_WEEKS;
+pub use sirf::SiRF;
+pub use sirf::SiRFData;
+pub use sirf::SiRFEphemerisRequest;
+pub use sirf::SiRFExtendedEphemeris;
+pub use sirf::SiRFOkToSend;
+pub use sirf::SiRFSetRate;
 pub use ublox_nmea::UBXConfig;
 pub use ublox_nmea::UBXData;
 pub use ublox_nmea::UBXNavigationStatus;
@@ -30,15 +84,21 @@ pub use ublox_nmea::UBloxNMEA;
 
 use crate::configuration::GpsConfig;
 use crate::gpsd::Response;
+use crate::gpsd::Sky;
+use crate::gpsd::Tpv;
 use crate::nmea::Device;
 use crate::nmea::*;
 use crate::TSSender;
 use anyhow::Result;
+use chrono::Utc;
 use std::fmt::Debug;
 use std::sync::Arc;
 use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::broadcast::Receiver;
+use tokio::sync::watch;
 use tokio::sync::Mutex;
+use tokio::time::timeout;
 
 type Locked = Arc<Mutex<GPSData>>;
 
@@ -47,6 +107,13 @@ pub struct GPS {
     pub name: String,
     pub gpsd_tx: broadcast::Sender<Response>,
     pub ntp_tx: TSSender,
+    /// The latest TPV report for `?POLL`
+    pub tpv: watch::Receiver<Option<Tpv>>,
+    /// The latest SKY report for `?POLL`
+    pub sky: watch::Receiver<Option<Sky>>,
+    /// The NTP leap indicator for the SHM `leap` field
+    pub leap: watch::Receiver<i32>,
+    leap_tx: Arc<watch::Sender<i32>>,
     device: Device,
     data: Locked,
 }
@@ -56,14 +123,29 @@ impl GPS {
         let device = Device::new(&config).await?;
 
         let name = config.name.clone();
-        let (gpsd_tx, _) = broadcast::channel(5);
+        // Each epoch may send TPV, SKY, GST, TOFF, ATT, and AIS, hold several epochs for slow clients
+        let (gpsd_tx, _) = broadcast::channel(64);
         let (ntp_tx, _) = broadcast::channel(5);
-        let data = Arc::new(Mutex::new(GPSData::default()));
+        let data = Arc::new(Mutex::new(GPSData::new(config)));
+        let (tpv_tx, tpv) = watch::channel(None);
+        let (sky_tx, sky) = watch::channel(None);
+        let (leap_tx, leap) = watch::channel(0);
+        let leap_tx = Arc::new(leap_tx);
+
+        let rx = gpsd_tx.subscribe();
+
+        tokio::spawn(async move {
+            record_latest(rx, tpv_tx, sky_tx).await;
+        });
 
         Ok(GPS {
             name,
             gpsd_tx,
             ntp_tx,
+            tpv,
+            sky,
+            leap,
+            leap_tx,
             device,
             data,
         })
@@ -79,9 +161,10 @@ impl GPS {
         let rx = self.device.subscribe();
         let gpsd_tx = self.gpsd_tx.clone();
         let ntp_tx = self.ntp_tx.clone();
+        let leap_tx = Arc::clone(&self.leap_tx);
 
         tokio::spawn(async move {
-            read_device(rx, data, name, gpsd_tx, ntp_tx).await;
+            read_device(rx, data, name, gpsd_tx, ntp_tx, leap_tx).await;
         });
     }
 }
@@ -92,19 +175,67 @@ async fn read_device(
     name: String,
     gpsd_tx: broadcast::Sender<Response>,
     ntp_tx: TSSender,
+    leap_tx: Arc<watch::Sender<i32>>,
 ) {
     let mut data = data.lock().await;
 
-    while let Ok(nmea) = rx.recv().await {
-        data.read_nmea(nmea, &name, &gpsd_tx, &ntp_tx);
+    loop {
+        match timeout(EPOCH_TIMEOUT, rx.recv()).await {
+            Ok(Ok(nmea)) => data.read_nmea(nmea, &name, &gpsd_tx, &ntp_tx),
+            Ok(Err(_)) => break,
+            Err(_) => data.epoch_timeout(&name, &gpsd_tx),
+        }
+
+        let leap = data
+            .leap_indicator(&name, Utc::now().naive_utc())
+            .shm_leap();
+
+        if *leap_tx.borrow() != leap && leap_tx.send(leap).is_ok() {}
+    }
+}
+
+async fn record_latest(
+    mut rx: Receiver<Response>,
+    tpv_tx: watch::Sender<Option<Tpv>>,
+    sky_tx: watch::Sender<Option<Sky>>,
+) {
+    loop {
+        match rx.recv().await {
+            Ok(Response::Tpv(tpv)) => if tpv_tx.send(Some(*tpv)).is_ok() {},
+            Ok(Response::Sky(sky)) => if sky_tx.send(Some(*sky)).is_ok() {},
+            Ok(_) => (),
+            Err(RecvError::Lagged(_)) => (),
+            Err(RecvError::Closed) => break,
+        }
     }
 }
 
 #[cfg(test)]
 mod test;
 
+#[cfg(test)]
+mod test_detect;
+
+#[cfg(test)]
+mod test_epoch;
+
+#[cfg(test)]
+mod test_garmin;
+
+#[cfg(test)]
+mod test_leap_seconds;
+
 #[cfg(test)]
 mod test_mkt;
 
+#[cfg(test)]
+mod test_quectel;
+
+#[cfg(test)]
+mod test_rollover;
+
+#[cfg(test)]
+mod test_sirf;
+
 #[cfg(test)]
 mod test_ublox_nmea;
@@ -0,0 +1,604 @@
+// For Quectel LC29H and L76 devices using $PAIR and $PQTM sentences
+
+use crate::{
+    configuration::QuectelConfig,
+    gps::add_message,
+    nmea::{
+        device::SerialCodec,
+        parser::{NavigationMode, Result},
+        parser_util::*,
+        ser_util, MessageSetting, NMEA,
+    },
+};
+use chrono::naive::{NaiveDate, NaiveTime};
+use futures_util::{sink::SinkExt, stream::StreamExt};
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1, take_while_m_n},
+    combinator::{map, map_opt, map_res, opt, value},
+    error::context,
+    sequence::{preceded, terminated, tuple},
+};
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Deserialize, Serialize,
+};
+use std::{fmt::Debug, time::Duration};
+use tokio::time::{timeout_at, Instant};
+use tracing::{debug, error, info, trace};
+
+pub const OUTPUT_MESSAGES: [&str; 9] = [
+    "GGA", "GLL", "GRS", "GSA", "GST", "GSV", "RMC", "VTG", "ZDA",
+];
+
+pub const SET_FIX_INTERVAL: u32 = 50;
+pub const SET_NMEA_OUTPUT_RATE: u32 = 62;
+pub const SET_PPS: u32 = 752;
+
+/// How long to wait for the receiver to respond to a command
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Default, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Quectel {
+    config: QuectelConfig,
+}
+
+impl Quectel {
+    pub fn new(config: QuectelConfig) -> Self {
+        Quectel { config }
+    }
+
+    pub async fn configure(&self, serial: &mut SerialCodec, messages: &[MessageSetting]) {
+        debug!("configuring Quectel with sentences {:?}", messages);
+
+        self.version(serial).await;
+
+        for message in messages {
+            let message_type = match nmea_type(&message.id) {
+                Some(t) => t,
+                None => {
+                    error!(
+                        "Quectel receivers don't support enabling {} sentences, ignored",
+                        message.id
+                    );
+                    continue;
+                }
+            };
+
+            let rate = match message.enabled {
+                true => self.config.rate(&message.id),
+                false => 0,
+            };
+
+            let set = QuectelSetNMEAOutputRate { message_type, rate };
+
+            command(
+                serial,
+                set,
+                SET_NMEA_OUTPUT_RATE,
+                &format!("{} output rate to {}", message.id, rate),
+            )
+            .await;
+        }
+
+        if let Some(interval) = self.config.fix_interval {
+            let set = QuectelSetFixInterval { interval };
+
+            command(
+                serial,
+                set,
+                SET_FIX_INTERVAL,
+                &format!("fix interval to {} ms", interval),
+            )
+            .await;
+        }
+
+        if let Some(mode) = &self.config.pps {
+            let set = QuectelSetPPS {
+                mode: mode.value(),
+                pulse_width: self.config.pps_pulse_width(),
+            };
+
+            command(serial, set, SET_PPS, &format!("PPS to {:?}", mode)).await;
+        }
+    }
+
+    pub fn message_settings(&self, messages: &[String]) -> Vec<MessageSetting> {
+        let mut message_settings: Vec<MessageSetting> = vec![];
+
+        if messages.is_empty() {
+            for message in &OUTPUT_MESSAGES {
+                add_message(&mut message_settings, message, true);
+            }
+        } else {
+            for default in &OUTPUT_MESSAGES {
+                let enabled = messages.contains(&default.to_string());
+
+                add_message(&mut message_settings, default, enabled);
+            }
+        }
+
+        message_settings
+    }
+
+    pub fn parse_private<'a>(&self, input: &'a str) -> Result<&'a str, NMEA> {
+        context(
+            "Quectel",
+            map(
+                alt((
+                    map(pair_001, QuectelData::Acknowledge),
+                    map(pqtm_verno, QuectelData::Version),
+                    map(pqtm_pvt, QuectelData::PVT),
+                    map(pqtm_response, QuectelData::Response),
+                )),
+                NMEA::Quectel,
+            ),
+        )(input)
+    }
+
+    // Logs the firmware version of the receiver
+    async fn version(&self, serial: &mut SerialCodec) {
+        if let Err(e) = serial.send(QuectelVersionPoll {}).await {
+            error!("unable to request firmware version: {:?}", e);
+            return;
+        }
+
+        let response = response(serial, |data| matches!(data, QuectelData::Version(_))).await;
+
+        match response {
+            Some(QuectelData::Version(v)) => info!(
+                "firmware version {} built {} {}",
+                v.version, v.build_date, v.build_time
+            ),
+            _ => error!("firmware version not reported"),
+        }
+    }
+}
+
+// Sends a $PAIR command and waits for its acknowledgement, returns true when it succeeded
+async fn command<T>(serial: &mut SerialCodec, command: T, id: u32, description: &str) -> bool
+where
+    T: Serialize + Debug,
+{
+    trace!("sending {:?}", command);
+
+    if let Err(e) = serial.send(command).await {
+        error!("unable to set {}: {:?}", description, e);
+        return false;
+    }
+
+    // PAIR001 reports Processing before the final result for slow commands
+    let response = response(serial, |data| match data {
+        QuectelData::Acknowledge(a) => a.command == id && a.result != QuectelResult::Processing,
+        _ => false,
+    })
+    .await;
+
+    match response {
+        Some(QuectelData::Acknowledge(a)) if a.result == QuectelResult::Succeeded => {
+            info!("set {}", description);
+            true
+        }
+        Some(QuectelData::Acknowledge(a)) => {
+            error!("unable to set {}: {:?}", description, a.result);
+            false
+        }
+        _ => {
+            error!("setting {} not acknowledged", description);
+            false
+        }
+    }
+}
+
+// Waits for the first Quectel sentence from the receiver that `matches`
+async fn response<F>(serial: &mut SerialCodec, matches: F) -> Option<QuectelData>
+where
+    F: Fn(&QuectelData) -> bool,
+{
+    let deadline = Instant::now() + RESPONSE_TIMEOUT;
+
+    loop {
+        match timeout_at(deadline, serial.next()).await {
+            Ok(Some(Ok(NMEA::Quectel(data)))) if matches(&data) => return Some(data),
+            Ok(Some(Ok(_))) => continue,
+            Ok(Some(Err(e))) => {
+                error!("error waiting for command response: {:?}", e);
+                return None;
+            }
+            Ok(None) | Err(_) => return None,
+        }
+    }
+}
+
+/// The PAIR062 message type for an NMEA sentence
+pub fn nmea_type(message: &str) -> Option<u32> {
+    match message {
+        "GGA" => Some(0),
+        "GLL" => Some(1),
+        "GSA" => Some(2),
+        "GSV" => Some(3),
+        "RMC" => Some(4),
+        "VTG" => Some(5),
+        "ZDA" => Some(6),
+        "GRS" => Some(7),
+        "GST" => Some(8),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum QuectelPPSMode {
+    #[serde(rename = "disabled")]
+    Disabled,
+    #[serde(rename = "first_fix")]
+    FirstFix,
+    #[serde(rename = "fix_3d")]
+    Fix3D,
+    #[serde(rename = "fix_2d_3d")]
+    Fix2D3D,
+    #[serde(rename = "always")]
+    Always,
+}
+
+impl QuectelPPSMode {
+    /// The PAIR752 PPS type
+    pub fn value(&self) -> u32 {
+        match self {
+            QuectelPPSMode::Disabled => 0,
+            QuectelPPSMode::FirstFix => 1,
+            QuectelPPSMode::Fix3D => 2,
+            QuectelPPSMode::Fix2D3D => 3,
+            QuectelPPSMode::Always => 4,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum QuectelData {
+    Acknowledge(QuectelAcknowledge),
+    PVT(QuectelPVT),
+    Response(QuectelResponse),
+    Version(QuectelVersion),
+}
+
+impl Serialize for QuectelData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            QuectelData::Acknowledge(a) => a.serialize(serializer),
+            QuectelData::PVT(p) => p.serialize(serializer),
+            QuectelData::Response(r) => r.serialize(serializer),
+            QuectelData::Version(v) => v.serialize(serializer),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum QuectelResult {
+    Succeeded,
+    Processing,
+    Failed,
+    Unsupported,
+    ParameterError,
+    Busy,
+    Unhandled(u32),
+}
+
+impl QuectelResult {
+    pub fn code(&self) -> u32 {
+        match self {
+            QuectelResult::Succeeded => 0,
+            QuectelResult::Processing => 1,
+            QuectelResult::Failed => 2,
+            QuectelResult::Unsupported => 3,
+            QuectelResult::ParameterError => 4,
+            QuectelResult::Busy => 5,
+            QuectelResult::Unhandled(u) => *u,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuectelAcknowledge {
+    pub command: u32,
+    pub result: QuectelResult,
+}
+
+impl Serialize for QuectelAcknowledge {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("QuectelAcknowledge", 2)?;
+        state.serialize_field("command", &ser_util::padded(Some(self.command), 3))?;
+        state.serialize_field("result", &self.result.code())?;
+        state.end()
+    }
+}
+
+pub(crate) fn pair_001(input: &str) -> Result<&str, QuectelAcknowledge> {
+    parse_message(
+        "PAIR 001",
+        tuple((preceded(tag("PAIR001,"), terminated(uint32, comma)), uint32)),
+        |(command, result)| QuectelAcknowledge {
+            command,
+            result: match result {
+                0 => QuectelResult::Succeeded,
+                1 => QuectelResult::Processing,
+                2 => QuectelResult::Failed,
+                3 => QuectelResult::Unsupported,
+                4 => QuectelResult::ParameterError,
+                5 => QuectelResult::Busy,
+                u => QuectelResult::Unhandled(u),
+            },
+        },
+    )(input)
+}
+
+/// Response to a $PQTM command, `error` is None when the command succeeded
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuectelResponse {
+    pub command: String,
+    pub error: Option<u32>,
+}
+
+impl Serialize for QuectelResponse {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("QuectelResponse", 3)?;
+        state.serialize_field("no comma", "PQTM")?;
+        state.serialize_field("no comma", &self.command)?;
+
+        match self.error {
+            None => state.serialize_field("result", "OK")?,
+            Some(code) => {
+                state.serialize_field("result", "ERROR")?;
+                state.serialize_field("error", &code)?;
+            }
+        }
+
+        state.end()
+    }
+}
+
+pub(crate) fn pqtm_response(input: &str) -> Result<&str, QuectelResponse> {
+    parse_message(
+        "PQTM response",
+        tuple((
+            preceded(
+                tag("PQTM"),
+                terminated(take_while1(is_upper_alphanum), comma),
+            ),
+            alt((
+                value(None, tag("OK")),
+                map(preceded(tag("ERROR,"), uint32), Some),
+            )),
+        )),
+        |(command, error): (&str, Option<u32>)| QuectelResponse {
+            command: command.to_string(),
+            error,
+        },
+    )(input)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuectelVersion {
+    pub version: String,
+    pub build_date: String,
+    pub build_time: String,
+}
+
+impl Serialize for QuectelVersion {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("QuectelVersion", 3)?;
+        state.serialize_field("version", &self.version)?;
+        state.serialize_field("build_date", &self.build_date)?;
+        state.serialize_field("build_time", &self.build_time)?;
+        state.end()
+    }
+}
+
+pub(crate) fn pqtm_verno(input: &str) -> Result<&str, QuectelVersion> {
+    parse_message(
+        "PQTM VERNO",
+        tuple((
+            preceded(tag("PQTMVERNO,"), terminated(any, comma)),
+            terminated(any, comma),
+            any,
+        )),
+        |(version, build_date, build_time)| QuectelVersion {
+            version,
+            build_date,
+            build_time,
+        },
+    )(input)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuectelPVT {
+    pub message_version: u32,
+    /// GPS time of week in milliseconds
+    pub time_of_week: u32,
+    pub date: Option<NaiveDate>,
+    pub time: Option<NaiveTime>,
+    /// 0 for no fix, 2 for a 2D fix, 3 for a 3D fix
+    pub fix_mode: u32,
+    pub satellites_used: u32,
+    pub leap_seconds: i32,
+    /// Degrees
+    pub latitude: Option<f64>,
+    /// Degrees
+    pub longitude: Option<f64>,
+    /// Height above mean sea level in meters
+    pub altitude: Option<f32>,
+    /// Geoid separation in meters
+    pub separation: Option<f32>,
+    /// North velocity in m/s
+    pub velocity_north: Option<f32>,
+    /// East velocity in m/s
+    pub velocity_east: Option<f32>,
+    /// Down velocity in m/s
+    pub velocity_down: Option<f32>,
+    /// Ground speed in m/s
+    pub speed: Option<f32>,
+    /// Heading of motion in degrees
+    pub heading: Option<f32>,
+    pub hdop: Option<f32>,
+    pub pdop: Option<f32>,
+}
+
+impl QuectelPVT {
+    pub fn navigation_mode(&self) -> NavigationMode {
+        match self.fix_mode {
+            2 => NavigationMode::Fix2D,
+            3 => NavigationMode::Fix3D,
+            _ => NavigationMode::FixNone,
+        }
+    }
+}
+
+impl Serialize for QuectelPVT {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("QuectelPVT", 19)?;
+        state.serialize_field("message_version", &self.message_version)?;
+        state.serialize_field("time_of_week", &self.time_of_week)?;
+        state.serialize_field("date", &self.date.map(|d| d.format("%Y%m%d").to_string()))?;
+        state.serialize_field("time", &self.time.as_ref().map(ser_util::time))?;
+        state.serialize_field("reserved", "")?;
+        state.serialize_field("fix_mode", &self.fix_mode)?;
+        state.serialize_field("satellites_used", &self.satellites_used)?;
+        state.serialize_field("leap_seconds", &self.leap_seconds)?;
+        state.serialize_field("latitude", &self.latitude)?;
+        state.serialize_field("longitude", &self.longitude)?;
+        state.serialize_field("altitude", &self.altitude)?;
+        state.serialize_field("separation", &self.separation)?;
+        state.serialize_field("velocity_north", &self.velocity_north)?;
+        state.serialize_field("velocity_east", &self.velocity_east)?;
+        state.serialize_field("velocity_down", &self.velocity_down)?;
+        state.serialize_field("speed", &self.speed)?;
+        state.serialize_field("heading", &self.heading)?;
+        state.serialize_field("hdop", &self.hdop)?;
+        state.serialize_field("pdop", &self.pdop)?;
+        state.end()
+    }
+}
+
+// Parses a date in yyyymmdd format
+fn yyyymmdd(input: &str) -> Result<&str, NaiveDate> {
+    map_opt(
+        tuple((
+            map_res(take_while_m_n(4, 4, is_digit), |y: &str| y.parse()),
+            two_digit,
+            two_digit,
+        )),
+        |(year, month, day)| NaiveDate::from_ymd_opt(year, month, day),
+    )(input)
+}
+
+pub(crate) fn pqtm_pvt(input: &str) -> Result<&str, QuectelPVT> {
+    parse_message(
+        "PQTM PVT",
+        tuple((
+            tuple((
+                preceded(tag("PQTMPVT,"), terminated(uint32, comma)),
+                terminated(uint32, comma),
+                terminated(opt(yyyymmdd), comma),
+                terminated(opt(time), comma),
+                terminated(any, comma),
+                terminated(uint32, comma),
+                terminated(uint32, comma),
+                terminated(int32, comma),
+            )),
+            tuple((
+                terminated(opt(flt64), comma),
+                terminated(opt(flt64), comma),
+                terminated(opt(flt32), comma),
+                terminated(opt(flt32), comma),
+                terminated(opt(flt32), comma),
+                terminated(opt(flt32), comma),
+                terminated(opt(flt32), comma),
+                terminated(opt(flt32), comma),
+                terminated(opt(flt32), comma),
+                terminated(opt(flt32), comma),
+                opt(flt32),
+            )),
+        )),
+        |(
+            (
+                message_version,
+                time_of_week,
+                date,
+                time,
+                _reserved,
+                fix_mode,
+                satellites_used,
+                leap_seconds,
+            ),
+            (
+                latitude,
+                longitude,
+                altitude,
+                separation,
+                velocity_north,
+                velocity_east,
+                velocity_down,
+                speed,
+                heading,
+                hdop,
+                pdop,
+            ),
+        )| QuectelPVT {
+            message_version,
+            time_of_week,
+            date,
+            time,
+            fix_mode,
+            satellites_used,
+            leap_seconds,
+            latitude,
+            longitude,
+            altitude,
+            separation,
+            velocity_north,
+            velocity_east,
+            velocity_down,
+            speed,
+            heading,
+            hdop,
+            pdop,
+        },
+    )(input)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct QuectelSetFixInterval {
+    /// Milliseconds between fixes
+    pub interval: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct QuectelSetNMEAOutputRate {
+    pub message_type: u32,
+    /// Fixes between each output of the message, 0 disables it
+    pub rate: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct QuectelSetPPS {
+    pub mode: u32,
+    /// Milliseconds
+    pub pulse_width: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct QuectelVersionPoll {}
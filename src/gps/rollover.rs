@@ -0,0 +1,79 @@
+use crate::configuration::RolloverEpoch;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+
+/// GPS broadcasts a 10-bit week number which rolls over every 1024 weeks
+pub const ROLLOVER_WEEKS: i64 = 1024;
+
+/// Corrects dates from receivers that have missed a GPS week number rollover
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rollover {
+    pivot: NaiveDate,
+}
+
+impl Rollover {
+    /// Dates before `pivot` are moved forward by whole rollover periods
+    pub fn new(pivot: NaiveDate) -> Self {
+        Rollover { pivot }
+    }
+
+    pub fn pivot(&self) -> NaiveDate {
+        self.pivot
+    }
+
+    /// Moves `date` forward by whole rollover periods until it is on or after the pivot
+    pub fn correct(&self, date: NaiveDate) -> NaiveDate {
+        let mut date = date;
+
+        while date < self.pivot {
+            date += Duration::weeks(ROLLOVER_WEEKS);
+        }
+
+        date
+    }
+
+    /// A date is plausible within one rollover period after the pivot
+    pub fn is_plausible(&self, date: NaiveDate) -> bool {
+        date >= self.pivot && date < self.pivot + Duration::weeks(ROLLOVER_WEEKS)
+    }
+}
+
+impl Default for Rollover {
+    fn default() -> Self {
+        Rollover::new(build_date())
+    }
+}
+
+impl From<RolloverEpoch> for Rollover {
+    fn from(epoch: RolloverEpoch) -> Self {
+        match epoch {
+            RolloverEpoch::Build => Rollover::default(),
+            RolloverEpoch::Date(date) => Rollover::new(date),
+        }
+    }
+}
+
+/// The date where_am_i was built
+pub fn build_date() -> NaiveDate {
+    let build_time = env!("WHERE_AM_I_BUILD_TIME").parse().unwrap_or(0);
+
+    NaiveDateTime::from_timestamp(build_time, 0).date()
+}
+
+/// Expands the two-digit `year` of NMEA dates to the year with those digits nearest `pivot`
+pub fn full_year(year: i32, pivot: NaiveDate) -> i32 {
+    let pivot = pivot.year();
+    let full = pivot - pivot.rem_euclid(100) + year;
+
+    if full - pivot > 50 {
+        full - 100
+    } else if pivot - full >= 50 {
+        full + 100
+    } else {
+        full
+    }
+}
+
+/// The full GPS week number of `date`
+pub fn gps_week(date: NaiveDate) -> i64 {
+    (date - NaiveDate::from_ymd(1980, 1, 6)).num_weeks()
+}
@@ -0,0 +1,244 @@
+// For SiRFstar devices
+
+use crate::{
+    gps::add_message,
+    nmea::{device::SerialCodec, parser::Result, parser_util::*, ser_util, MessageSetting, NMEA},
+};
+use futures_util::sink::SinkExt;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    character::complete::one_of,
+    combinator::{map, map_res},
+    error::context,
+    multi::many0,
+    sequence::{preceded, terminated, tuple},
+};
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Serialize,
+};
+use tracing::{debug, error, info};
+
+pub const OUTPUT_MESSAGES: [&str; 7] = ["GGA", "GLL", "GSA", "GSV", "RMC", "VTG", "ZDA"];
+
+#[derive(Clone, Default, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct SiRF {}
+
+impl SiRF {
+    pub async fn configure(&self, serial: &mut SerialCodec, messages: &[MessageSetting]) {
+        debug!("configuring SiRF with sentences {:?}", messages);
+
+        for message in messages {
+            let message_type = match nmea_type(&message.id) {
+                Some(t) => t,
+                None => {
+                    error!(
+                        "SiRF receivers don't support enabling {} sentences, ignored",
+                        message.id
+                    );
+                    continue;
+                }
+            };
+
+            let rate = match message.enabled {
+                true => 1,
+                false => 0,
+            };
+
+            let set = SiRFSetRate { message_type, rate };
+
+            match serial.send(set).await {
+                Ok(_) => info!("setting {} to {}", message.id, message.enabled),
+                Err(e) => error!(
+                    "unable to set {} to {}: {:?}",
+                    message.id, message.enabled, e
+                ),
+            }
+        }
+    }
+
+    pub fn message_settings(&self, messages: &[String]) -> Vec<MessageSetting> {
+        let mut message_settings: Vec<MessageSetting> = vec![];
+
+        if messages.is_empty() {
+            for message in &OUTPUT_MESSAGES {
+                add_message(&mut message_settings, message, true);
+            }
+        } else {
+            for default in &OUTPUT_MESSAGES {
+                let enabled = messages.contains(&default.to_string());
+
+                add_message(&mut message_settings, default, enabled);
+            }
+        }
+
+        message_settings
+    }
+
+    pub fn parse_private<'a>(&self, input: &'a str) -> Result<&'a str, NMEA> {
+        context(
+            "PSRF",
+            map(
+                alt((
+                    map(srf_150, SiRFData::OkToSend),
+                    map(srf_151, SiRFData::EphemerisRequest),
+                    map(srf_156, SiRFData::ExtendedEphemeris),
+                )),
+                NMEA::PSRF,
+            ),
+        )(input)
+    }
+}
+
+/// The PSRF103 message type for an NMEA sentence
+pub fn nmea_type(message: &str) -> Option<u32> {
+    match message {
+        "GGA" => Some(0),
+        "GLL" => Some(1),
+        "GSA" => Some(2),
+        "GSV" => Some(3),
+        "RMC" => Some(4),
+        "VTG" => Some(5),
+        "ZDA" => Some(8),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SiRFData {
+    OkToSend(SiRFOkToSend),
+    EphemerisRequest(SiRFEphemerisRequest),
+    ExtendedEphemeris(SiRFExtendedEphemeris),
+}
+
+impl Serialize for SiRFData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            SiRFData::OkToSend(o) => {
+                let mut state = serializer.serialize_struct("SiRFOkToSend", 1)?;
+                state.serialize_field("ok_to_send", &o.ok_to_send)?;
+                state.end()
+            }
+            SiRFData::EphemerisRequest(r) => {
+                let mut state = serializer.serialize_struct("SiRFEphemerisRequest", 4)?;
+                state.serialize_field("time_valid", &r.time_valid)?;
+                state.serialize_field("week", &r.week)?;
+                state.serialize_field("time_of_week", &r.time_of_week)?;
+                state.serialize_field("satellites", &format!("0x{:08X}", r.satellites))?;
+                state.end()
+            }
+            SiRFData::ExtendedEphemeris(e) => {
+                let mut state = serializer.serialize_struct("SiRFExtendedEphemeris", 2)?;
+                state.serialize_field("message_type", &e.message_type)?;
+
+                for field in &e.fields {
+                    state.serialize_field("field", field)?;
+                }
+
+                state.end()
+            }
+        }
+    }
+}
+
+/// Whether the receiver is ready to accept commands, sent when entering and leaving power saving
+#[derive(Clone, Debug, PartialEq)]
+pub struct SiRFOkToSend {
+    pub ok_to_send: bool,
+}
+
+pub(crate) fn srf_150(input: &str) -> Result<&str, SiRFOkToSend> {
+    parse_message("SRF 150", preceded(tag("PSRF150,"), one_of("01")), |c| {
+        SiRFOkToSend {
+            ok_to_send: c == '1',
+        }
+    })(input)
+}
+
+/// Request for extended ephemeris for the satellites in `satellites`
+#[derive(Clone, Debug, PartialEq)]
+pub struct SiRFEphemerisRequest {
+    /// Bit 0 is set when the week is valid, bit 1 when the time of week is valid
+    pub time_valid: u32,
+    pub week: u32,
+    /// Seconds
+    pub time_of_week: f64,
+    /// Bit N - 1 is set for satellite PRN N
+    pub satellites: u32,
+}
+
+fn hex_mask(input: &str) -> Result<&str, u32> {
+    map_res(
+        preceded(
+            alt((tag("0x"), tag("0X"))),
+            take_while1(|c: char| c.is_ascii_hexdigit()),
+        ),
+        |h| u32::from_str_radix(h, 16),
+    )(input)
+}
+
+pub(crate) fn srf_151(input: &str) -> Result<&str, SiRFEphemerisRequest> {
+    parse_message(
+        "SRF 151",
+        tuple((
+            preceded(tag("PSRF151,"), terminated(uint32, comma)),
+            terminated(uint32, comma),
+            terminated(flt64, comma),
+            hex_mask,
+        )),
+        |(time_valid, week, time_of_week, satellites)| SiRFEphemerisRequest {
+            time_valid,
+            week,
+            time_of_week,
+            satellites,
+        },
+    )(input)
+}
+
+/// Extended ephemeris status, the fields depend on the message type
+#[derive(Clone, Debug, PartialEq)]
+pub struct SiRFExtendedEphemeris {
+    pub message_type: String,
+    pub fields: Vec<String>,
+}
+
+pub(crate) fn srf_156(input: &str) -> Result<&str, SiRFExtendedEphemeris> {
+    parse_message(
+        "SRF 156",
+        tuple((preceded(tag("PSRF156,"), any), many0(preceded(comma, any)))),
+        |(message_type, fields)| SiRFExtendedEphemeris {
+            message_type,
+            fields,
+        },
+    )(input)
+}
+
+/// Sets the output rate of an NMEA message, in seconds between messages
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SiRFSetRate {
+    pub message_type: u32,
+    pub rate: u32,
+}
+
+impl Serialize for SiRFSetRate {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("SiRFSetRate", 4)?;
+        state.serialize_field(
+            "message_type",
+            &ser_util::padded(Some(self.message_type), 2),
+        )?;
+        // set the rate instead of querying it
+        state.serialize_field("mode", "00")?;
+        state.serialize_field("rate", &ser_util::padded(Some(self.rate), 2))?;
+        // always send checksums
+        state.serialize_field("checksum", "01")?;
+        state.end()
+    }
+}
@@ -1,7 +1,17 @@
 use chrono::prelude::*;
 
-use crate::gps::GPSData;
+use crate::ais::AISFragment;
+use crate::configuration::{GpsConfig, TimeQualityConfig, TimingSentence};
+use crate::gps::gps_data::infer_date;
+use crate::gps::{
+    Driver, GPSData, GpsType, LeapIndicator, Rollover, UBXNavigationStatus, UBXPosition,
+    UBXSatellite, UBXSatelliteStatus, UBXSatellites, UBXTime, UBloxNMEA,
+};
+use crate::gpsd::{satellite_system, Response, SkySatellite};
 use crate::nmea::*;
+use crate::ubx::UBXNavTimeLS;
+
+use std::time::Duration;
 
 use tokio::sync::broadcast;
 
@@ -21,6 +31,15 @@ fn build_time(
     DateTime::from_utc(time, Utc)
 }
 
+// Parses a sentence as a u-blox receiver sends it
+fn ublox_sentence(sentence: &str, received: Duration) -> NMEA {
+    let parser = parser::Parser::new(Driver::UBloxNMEA(UBloxNMEA::default()));
+    let checksum = sentence.bytes().fold(0, |checksum, b| checksum ^ b);
+    let input = format!("${}*{:02X}\r\n", sentence, checksum);
+
+    parser.parse(input.as_bytes(), received).unwrap().1
+}
+
 #[test]
 fn test_update_time() {
     let mut gps = GPSData::default();
@@ -36,6 +55,17 @@ fn test_update_time() {
     assert_eq!(expected, gps.time);
 }
 
+#[test]
+fn test_update_time_out_of_order() {
+    let mut gps = GPSData::default();
+    gps.naive_date = Some(NaiveDate::from_ymd(2020, 5, 26));
+    gps.naive_time = Some(NaiveTime::from_hms_milli(1, 8, 1, 0));
+
+    gps.update_time(NaiveTime::from_hms_milli(1, 8, 0, 0));
+
+    assert_eq!(Some(build_time(2020, 5, 26, 1, 8, 0, 0)), gps.time);
+}
+
 #[test]
 fn test_update_time_day_boundary() {
     let mut gps = GPSData::default();
@@ -52,6 +82,38 @@ fn test_update_time_day_boundary() {
     assert_eq!(expected, gps.time);
 }
 
+#[test]
+fn test_ais() {
+    let (gpsd_tx, mut gpsd_rx) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+
+    let fragment = AISFragment {
+        received: None,
+        talker: Talker::AIS,
+        own_vessel: false,
+        fragment_count: 1,
+        fragment: 1,
+        message_id: None,
+        channel: Some('B'),
+        payload: "15M67FC000G?ufbE`FepT@3n00Sa".to_string(),
+        fill_bits: 0,
+    };
+
+    gps.ais(fragment, "name", &gpsd_tx);
+
+    match gpsd_rx.try_recv().unwrap() {
+        Response::Ais(ais) => {
+            assert_eq!("name", ais.device);
+            assert_eq!(1, ais.message_type);
+            assert_eq!(366053209, ais.mmsi);
+            assert!(ais.scaled);
+            assert_eq!(Some(3), ais.status);
+            assert_approx_eq!(219.3, ais.course.unwrap());
+        }
+        r => panic!("expected AIS, got {:?}", r),
+    }
+}
+
 #[test]
 fn test_gga() {
     let (gpsd_tx, _) = broadcast::channel(1);
@@ -87,6 +149,56 @@ fn test_gga() {
     assert_approx_eq!(264.0, gps.altitude_msl.unwrap());
 }
 
+#[test]
+fn test_gbs() {
+    let (gpsd_tx, mut gpsd_rx) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+
+    let gbs = |second, svid| GBSData {
+        received: None,
+        talker: Talker::GPS,
+        time: NaiveTime::from_hms_milli(1, 8, second, 0),
+        err_lat: 3.0,
+        err_lon: 4.0,
+        err_alt: 5.0,
+        svid,
+        prob: Some(0.01),
+        bias: Some(40.0),
+        stddev: Some(2.5),
+        system: None,
+        signal: None,
+    };
+
+    gps.gbs(gbs(0, Some(14)), "gbs", &gpsd_tx);
+    gps.gbs(gbs(1, None), "gbs", &gpsd_tx);
+
+    let tpv = match gpsd_rx.try_recv().unwrap() {
+        Response::Tpv(tpv) => tpv,
+        r => panic!("expected TPV, got {:?}", r),
+    };
+
+    assert_approx_eq!(4.0, tpv.epx.unwrap());
+    assert_approx_eq!(3.0, tpv.epy.unwrap());
+    assert_approx_eq!(5.0, tpv.eph.unwrap());
+    assert_approx_eq!(5.0, tpv.epv.unwrap());
+
+    let faults = prometheus::gather()
+        .into_iter()
+        .find(|family| family.get_name() == "where_am_i_satellite_faults_count")
+        .unwrap();
+
+    let metric = faults
+        .get_metric()
+        .iter()
+        .find(|m| m.get_label().iter().any(|l| l.get_value() == "gbs"))
+        .unwrap();
+
+    let labels: Vec<&str> = metric.get_label().iter().map(|l| l.get_value()).collect();
+
+    assert_eq!(vec!["gbs", "14", "1"], labels);
+    assert_approx_eq!(1.0, metric.get_counter().get_value());
+}
+
 #[test]
 fn test_gsa() {
     let (gpsd_tx, _) = broadcast::channel(1);
@@ -144,12 +256,202 @@ fn test_gsa() {
     gps.gsa(gagsa, "name", &gpsd_tx, &ntp_tx);
     gps.gsa(gbgsa, "name", &gpsd_tx, &ntp_tx);
     gps.gsa(glgsa, "name", &gpsd_tx, &ntp_tx);
+    gps.gsa(gpgsa.clone(), "name", &gpsd_tx, &ntp_tx);
+
+    assert_eq!(None, gps.mode);
+
+    gps.epoch_timeout("name", &gpsd_tx);
+
+    assert_eq!(Some(3), gps.mode);
+    assert_eq!(Some(NavigationMode::Fix3D), gps.beiduo_navigation_mode);
+    assert_eq!(Some(NavigationMode::Fix3D), gps.galileo_navigation_mode);
+    assert_eq!(Some(NavigationMode::Fix3D), gps.glonass_navigation_mode);
+    assert_eq!(Some(NavigationMode::Fix3D), gps.gps_navigation_mode);
+
+    // GPS alone doesn't report a mode while four systems are learned
+    gps.gsa(gpgsa.clone(), "name", &gpsd_tx, &ntp_tx);
+    gps.gsa(gpgsa.clone(), "name", &gpsd_tx, &ntp_tx);
+
+    let mut gpgsa = gpgsa;
+    gpgsa.navigation_mode = NavigationMode::Fix2D;
+
     gps.gsa(gpgsa, "name", &gpsd_tx, &ntp_tx);
 
-    assert_eq!(None, gps.beiduo_navigation_mode);
-    assert_eq!(None, gps.galileo_navigation_mode);
-    assert_eq!(None, gps.glonass_navigation_mode);
-    assert_eq!(None, gps.gps_navigation_mode);
+    assert_eq!(Some(3), gps.mode);
+}
+
+#[test]
+fn test_gsa_learned_systems() {
+    let (gpsd_tx, _) = broadcast::channel(1);
+    let (ntp_tx, _) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+
+    let gsa = |talker, navigation_mode| GSAData {
+        received: None,
+        talker,
+        operation_mode: OperationMode::Automatic,
+        navigation_mode,
+        satellite_ids: vec![Some(1), Some(2), Some(3)],
+        pdop: Some(1.0),
+        hdop: Some(1.0),
+        vdop: Some(1.0),
+        system: None,
+    };
+
+    gps.gsa(
+        gsa(Talker::GPS, NavigationMode::Fix2D),
+        "learned",
+        &gpsd_tx,
+        &ntp_tx,
+    );
+    gps.epoch_timeout("learned", &gpsd_tx);
+
+    assert_eq!(Some(2), gps.mode);
+
+    // a GPS-only receiver reports its mode with each GSA
+    gps.gsa(
+        gsa(Talker::GPS, NavigationMode::Fix3D),
+        "learned",
+        &gpsd_tx,
+        &ntp_tx,
+    );
+
+    assert_eq!(Some(3), gps.mode);
+
+    let metric = prometheus::gather()
+        .into_iter()
+        .find(|m| m.get_name() == "where_am_i_navigation_mode")
+        .unwrap();
+
+    let gauge = metric
+        .get_metric()
+        .iter()
+        .find(|m| {
+            m.get_label()
+                .iter()
+                .any(|l| l.get_name() == "device" && l.get_value() == "learned")
+                && m.get_label()
+                    .iter()
+                    .any(|l| l.get_name() == "system" && l.get_value() == "1")
+        })
+        .unwrap();
+
+    assert_approx_eq!(3.0, gauge.get_gauge().get_value());
+}
+
+#[test]
+fn test_gsa_combined() {
+    let (gpsd_tx, _) = broadcast::channel(1);
+    let (ntp_tx, _) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+
+    let gsa = |satellite_ids| GSAData {
+        received: None,
+        talker: Talker::Combination,
+        operation_mode: OperationMode::Automatic,
+        navigation_mode: NavigationMode::Fix3D,
+        satellite_ids,
+        pdop: Some(1.0),
+        hdop: Some(1.0),
+        vdop: Some(1.0),
+        system: None,
+    };
+
+    // a GN GSA for the GLONASS satellites of the solution
+    gps.gsa(gsa(vec![Some(65), Some(70)]), "name", &gpsd_tx, &ntp_tx);
+
+    assert_eq!(None, gps.mode);
+    assert_eq!(Some(NavigationMode::Fix3D), gps.glonass_navigation_mode);
+
+    // a GN GSA for the whole solution
+    gps.gsa(gsa(vec![Some(23), Some(65)]), "name", &gpsd_tx, &ntp_tx);
+
+    assert_eq!(Some(3), gps.mode);
+}
+
+#[test]
+fn test_gsa_configured_systems() {
+    let (gpsd_tx, _) = broadcast::channel(1);
+    let (ntp_tx, _) = broadcast::channel(1);
+
+    let config = GpsConfig {
+        name: "name".to_string(),
+        device: "/dev/gps0".to_string(),
+        gps_type: GpsType::Generic,
+        pps: None,
+        baud_rate: None,
+        parse_profile: None,
+        framing: None,
+        flow_control: None,
+        timeout: None,
+        messages: None,
+        ntp_unit: None,
+        quarantine: None,
+        rollover_epoch: None,
+        leap_seconds_file: None,
+        systems: Some(vec![System::GPS, System::GLONASS]),
+        timing_sentence: None,
+        time_quality: None,
+        ublox: None,
+        quectel: None,
+        mkt: None,
+    };
+
+    let mut gps = GPSData::new(&config);
+
+    let gsa = |system| GSAData {
+        received: None,
+        talker: Talker::Combination,
+        operation_mode: OperationMode::Automatic,
+        navigation_mode: NavigationMode::Fix3D,
+        satellite_ids: vec![Some(1), Some(2), Some(3)],
+        pdop: Some(1.0),
+        hdop: Some(1.0),
+        vdop: Some(1.0),
+        system: Some(system),
+    };
+
+    gps.gsa(gsa(System::GPS), "name", &gpsd_tx, &ntp_tx);
+
+    assert_eq!(None, gps.mode);
+
+    gps.gsa(gsa(System::GLONASS), "name", &gpsd_tx, &ntp_tx);
+
+    assert_eq!(Some(3), gps.mode);
+}
+
+#[test]
+fn test_gsv() {
+    let (gpsd_tx, _) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+
+    let gsv = |msg, satellites| GSVData {
+        received: None,
+        talker: Talker::GPS,
+        num_msgs: 2,
+        msg,
+        num_satellites: 2,
+        satellites,
+        system: Some(System::GPS),
+        signal: None,
+    };
+
+    let sat = |id| parser::GSVsatellite {
+        id,
+        elevation: Some(38),
+        azimuth: Some(230),
+        cno: Some(44),
+    };
+
+    gps.gsv(gsv(1, vec![sat(23)]), "name", &gpsd_tx);
+
+    assert!(gps.satellite_views.is_empty());
+
+    gps.gsv(gsv(2, vec![sat(29)]), "name", &gpsd_tx);
+
+    let view = &gps.satellite_views[&(Talker::GPS, None)];
+
+    assert_eq!(vec![sat(23), sat(29)], view.satellites);
 }
 
 #[test]
@@ -240,11 +542,36 @@ fn test_gsa_gps() {
     assert_eq!(NavigationMode::Fix3D, gps.gps_navigation_mode.unwrap());
 }
 
+#[test]
+fn test_hdt() {
+    let (gpsd_tx, mut gpsd_rx) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+
+    let hdt = HDTData {
+        received: None,
+        talker: Talker::Combination,
+        heading: Some(274.07),
+    };
+
+    gps.hdt(hdt, "name", &gpsd_tx);
+
+    assert_approx_eq!(274.07, gps.heading.unwrap());
+
+    match gpsd_rx.try_recv().unwrap() {
+        Response::Att(att) => {
+            assert_eq!("name", att.device);
+            assert_approx_eq!(274.07, att.heading.unwrap());
+        }
+        r => panic!("expected ATT, got {:?}", r),
+    }
+}
+
 #[test]
 fn test_rmc() {
     let (gpsd_tx, _) = broadcast::channel(1);
     let (ntp_tx, _) = broadcast::channel(1);
     let mut gps = GPSData::default();
+    gps.rollover = Rollover::new(NaiveDate::from_ymd(2020, 1, 1));
 
     let rmc = RMCData {
         received: None,
@@ -278,26 +605,976 @@ fn test_rmc() {
 }
 
 #[test]
-fn test_zda() {
+fn test_pubx_position() {
+    let (gpsd_tx, mut gpsd_rx) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+
+    let position = |second| UBXPosition {
+        time: NaiveTime::from_hms_milli(1, 8, second, 0),
+        lat_lon: Some(LatLon {
+            latitude: 44.9343,
+            longitude: -93.2624,
+        }),
+        alt_ref: 237.0,
+        nav_status: UBXNavigationStatus::Differential3D,
+        horizontal_accuracy: 2.5,
+        vertical_accuracy: 3.5,
+        speed_over_ground: 36.0,
+        course_over_ground: 90.0,
+        vertical_velocity: 0.5,
+        diff_age: Some(2),
+        hdop: 1.0,
+        vdop: 1.5,
+        tdop: 1.2,
+        num_satellites: 10,
+        reserved: 0,
+        dead_reckoning: false,
+    };
+
+    gps.pubx_position(position(0), "name", &gpsd_tx);
+
+    assert!(gpsd_rx.try_recv().is_err());
+
+    gps.pubx_position(position(1), "name", &gpsd_tx);
+
+    match gpsd_rx.try_recv().unwrap() {
+        Response::Tpv(tpv) => {
+            assert_eq!(3, tpv.mode);
+            assert_eq!(Some(2), tpv.status);
+            assert_approx_eq!(237.0, tpv.alt_hae.unwrap());
+            assert_approx_eq!(2.5, tpv.eph.unwrap());
+            assert_approx_eq!(3.5, tpv.epv.unwrap());
+            assert_approx_eq!(10.0, tpv.speed.unwrap());
+            assert_approx_eq!(-0.5, tpv.climb.unwrap());
+            assert_approx_eq!(0.0, tpv.vel_n.unwrap());
+            assert_approx_eq!(10.0, tpv.vel_e.unwrap());
+            assert_approx_eq!(0.5, tpv.vel_d.unwrap());
+            assert_eq!(Some(2.0), tpv.dgps_age);
+        }
+        r => panic!("expected TPV, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_pubx_satellites() {
+    let (gpsd_tx, mut gpsd_rx) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+
+    let satellites = UBXSatellites {
+        satellites: vec![
+            UBXSatellite {
+                id: 23,
+                status: UBXSatelliteStatus::Used,
+                azimuth: Some(230),
+                elevation: Some(38),
+                cno: 44,
+                lock_time: 64,
+            },
+            UBXSatellite {
+                id: 120,
+                status: UBXSatelliteStatus::NotUsed,
+                azimuth: None,
+                elevation: None,
+                cno: 30,
+                lock_time: 0,
+            },
+        ],
+    };
+
+    gps.pubx_satellites(satellites, "name", &gpsd_tx);
+
+    let sky = match gpsd_rx.try_recv().unwrap() {
+        Response::Sky(sky) => sky,
+        r => panic!("expected SKY, got {:?}", r),
+    };
+
+    assert_eq!(2, sky.n_sat);
+    assert_eq!(1, sky.u_sat);
+
+    let sbas = &sky.satellites[1];
+
+    assert_eq!(120, sbas.prn);
+    assert_eq!(Some(1), sbas.gnssid);
+    assert_eq!(None, sbas.az);
+    assert_eq!(Some(30.0), sbas.ss);
+    assert!(!sbas.used);
+}
+
+#[test]
+fn test_sky() {
+    let (gpsd_tx, mut gpsd_rx) = broadcast::channel(2);
+    let (ntp_tx, _) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+
+    let gga = |second| GGAData {
+        received: None,
+        talker: Talker::GPS,
+        time: NaiveTime::from_hms_milli(1, 8, second, 0),
+        lat_lon: None,
+        quality: Quality::AutonomousGNSSFix,
+        num_satellites: 1,
+        hdop: Some(1.2),
+        alt: None,
+        alt_unit: "M".to_string(),
+        sep: None,
+        sep_unit: "M".to_string(),
+        diff_age: None,
+        diff_station: None,
+    };
+
+    let gsv = |talker, satellites: Vec<u32>| GSVData {
+        received: None,
+        talker,
+        num_msgs: 1,
+        msg: 1,
+        num_satellites: satellites.len() as u32,
+        satellites: satellites
+            .into_iter()
+            .map(|id| parser::GSVsatellite {
+                id,
+                elevation: Some(38),
+                azimuth: Some(230),
+                cno: Some(44),
+            })
+            .collect(),
+        system: None,
+        signal: None,
+    };
+
+    let gsa = GSAData {
+        received: None,
+        talker: Talker::Combination,
+        operation_mode: OperationMode::Automatic,
+        navigation_mode: NavigationMode::Fix3D,
+        satellite_ids: vec![Some(23), Some(65), None],
+        pdop: Some(2.0),
+        hdop: Some(1.2),
+        vdop: Some(1.6),
+        system: None,
+    };
+
+    gps.gga(gga(0), "name", &gpsd_tx, &ntp_tx);
+    gps.gsv(gsv(Talker::GPS, vec![23, 29]), "name", &gpsd_tx);
+    gps.gsv(gsv(Talker::GLONASS, vec![65]), "name", &gpsd_tx);
+    gps.gsa(gsa, "name", &gpsd_tx, &ntp_tx);
+    gps.gga(gga(1), "name", &gpsd_tx, &ntp_tx);
+
+    assert!(matches!(gpsd_rx.try_recv().unwrap(), Response::Tpv(_)));
+
+    let sky = match gpsd_rx.try_recv().unwrap() {
+        Response::Sky(sky) => sky,
+        r => panic!("expected SKY, got {:?}", r),
+    };
+
+    assert_eq!(3, sky.n_sat);
+    assert_eq!(2, sky.u_sat);
+    assert_eq!(Some(2.0), sky.pdop);
+    assert_approx_eq!(1.6, sky.vdop.unwrap(), 1e-6);
+
+    let prns: Vec<u32> = sky.satellites.iter().map(|s| s.prn).collect();
+
+    assert_eq!(vec![23, 29, 65], prns);
+
+    let glonass = &sky.satellites[2];
+
+    assert_eq!(Some(6), glonass.gnssid);
+    assert_eq!(Some(1), glonass.svid);
+    assert!(glonass.used);
+    assert!(!sky.satellites[1].used);
+
+    let json = serde_json::to_value(&sky).unwrap();
+
+    assert_eq!("SKY", json["class"]);
+    assert_eq!(3, json["nSat"]);
+    assert_eq!(23, json["satellites"][0]["PRN"]);
+
+    gps.gga(gga(2), "name", &gpsd_tx, &ntp_tx);
+
+    assert!(matches!(gpsd_rx.try_recv().unwrap(), Response::Tpv(_)));
+    assert!(gpsd_rx.try_recv().is_err());
+}
+
+#[test]
+fn test_satellite_system() {
+    assert_eq!(Some(System::QZSS), satellite_system(None, 193));
+    assert_eq!(Some(System::QZSS), satellite_system(None, 200));
+    assert_eq!(Some(System::BeiDuo), satellite_system(None, 201));
+    assert_eq!(Some(System::BeiDuo), satellite_system(None, 202));
+
+    let beidou = SkySatellite::new(None, 202);
+
+    assert_eq!(402, beidou.prn);
+    assert_eq!(Some(3), beidou.gnssid);
+    assert_eq!(Some(2), beidou.svid);
+
+    let qzss = SkySatellite::new(None, 200);
+
+    assert_eq!(200, qzss.prn);
+    assert_eq!(Some(5), qzss.gnssid);
+    assert_eq!(Some(8), qzss.svid);
+}
+
+#[test]
+fn test_rmc_rollover() {
     let (gpsd_tx, _) = broadcast::channel(1);
     let (ntp_tx, _) = broadcast::channel(1);
     let mut gps = GPSData::default();
+    gps.rollover = Rollover::new(NaiveDate::from_ymd(2020, 1, 1));
 
-    let zda = ZDAData {
+    let rmc = RMCData {
         received: None,
         talker: Talker::GPS,
-        time: Some(NaiveTime::from_hms_milli(1, 8, 0, 0)),
-        day: Some(26),
-        month: Some(5),
-        year: Some(2020),
-        local_tz_hour: 0,
-        local_tz_minute: 0,
+        time: NaiveTime::from_hms_milli(1, 8, 0, 0),
+        status: parser::Status::Valid,
+        lat_lon: None,
+        speed: 0.0,
+        course_over_ground: None,
+        date: NaiveDate::from_ymd(2000, 10, 10),
+        magnetic_variation: None,
+        magnetic_variation_east_west: None,
+        position_mode: parser::PositionMode::AutonomousGNSSFix,
+        nav_status: None,
     };
 
-    let expected_time = build_time(2020, 5, 26, 1, 8, 0, 0);
+    gps.rmc(rmc, "name", &gpsd_tx, &ntp_tx);
 
-    gps.zda(zda, "name", &gpsd_tx, &ntp_tx);
+    assert_eq!(build_time(2020, 5, 26, 1, 8, 0, 0), gps.time.unwrap());
+    assert_eq!(Some(NaiveDate::from_ymd(2020, 5, 26)), gps.naive_date);
+}
 
-    assert_eq!(2020, gps.year);
-    assert_eq!(expected_time, gps.time.unwrap());
+#[test]
+fn test_nav_timels() {
+    let mut gps = GPSData::default();
+    gps.time = Some(build_time(2016, 12, 30, 0, 0, 0, 0));
+
+    let timels = UBXNavTimeLS {
+        received: None,
+        time_of_week: 432000000,
+        version: 0,
+        current_source: 2,
+        current: 17,
+        change_source: 2,
+        change: 1,
+        time_to_event: 2 * 86400,
+        event_week: 1930,
+        event_day: 7,
+        valid_current: true,
+        valid_time_to_event: true,
+    };
+
+    gps.nav_timels(timels, "name");
+
+    assert_eq!(Some(17), gps.leap_seconds.offset);
+
+    let now = build_time(2016, 12, 31, 0, 0, 0, 0).naive_utc();
+
+    assert_eq!(LeapIndicator::Insert, gps.leap_indicator("name", now));
+
+    let now = build_time(2017, 1, 1, 0, 0, 0, 0).naive_utc();
+
+    assert_eq!(LeapIndicator::NoWarning, gps.leap_indicator("name", now));
+}
+
+#[test]
+fn test_ths_invalid() {
+    let (gpsd_tx, _) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+    gps.heading = Some(12.0);
+
+    let ths = THSData {
+        received: None,
+        talker: Talker::Combination,
+        heading: Some(77.52),
+        mode: HeadingMode::Invalid,
+    };
+
+    gps.ths(ths, "name", &gpsd_tx);
+
+    assert_eq!(None, gps.heading);
+}
+
+#[test]
+fn test_epoch_end() {
+    let (gpsd_tx, mut gpsd_rx) = broadcast::channel(3);
+    let (ntp_tx, _) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+    gps.rollover = Rollover::new(NaiveDate::from_ymd(2020, 1, 1));
+
+    let rmc = |second| RMCData {
+        received: None,
+        talker: Talker::GPS,
+        time: NaiveTime::from_hms_milli(1, 8, second, 0),
+        status: parser::Status::Valid,
+        lat_lon: None,
+        speed: 0.0,
+        course_over_ground: None,
+        date: NaiveDate::from_ymd(2020, 5, 26),
+        magnetic_variation: None,
+        magnetic_variation_east_west: None,
+        position_mode: parser::PositionMode::AutonomousGNSSFix,
+        nav_status: None,
+    };
+
+    gps.read_nmea(NMEA::RMC(rmc(0)), "name", &gpsd_tx, &ntp_tx);
+    gps.read_nmea(NMEA::RMC(rmc(1)), "name", &gpsd_tx, &ntp_tx);
+
+    assert!(matches!(gpsd_rx.try_recv().unwrap(), Response::Tpv(_)));
+    assert!(gpsd_rx.try_recv().is_err());
+
+    // two epochs ended with RMC, so the third is complete when its RMC arrives
+    gps.read_nmea(NMEA::RMC(rmc(2)), "name", &gpsd_tx, &ntp_tx);
+
+    assert!(matches!(gpsd_rx.try_recv().unwrap(), Response::Tpv(_)));
+
+    let tpv = match gpsd_rx.try_recv().unwrap() {
+        Response::Tpv(tpv) => tpv,
+        r => panic!("expected TPV, got {:?}", r),
+    };
+
+    assert_eq!(Some("2020-05-26T01:08:02.000Z".to_string()), tpv.time);
+
+    let fix = gps.fix.as_ref().unwrap();
+
+    assert_eq!(Some(NaiveTime::from_hms(1, 8, 2)), fix.time);
+    assert_eq!(Some(build_time(2020, 5, 26, 1, 8, 2, 0)), fix.date_time);
+    assert_eq!(vec!["RMC"], fix.sentences);
+}
+
+#[test]
+fn test_tpv() {
+    let (gpsd_tx, mut gpsd_rx) = broadcast::channel(1);
+    let (ntp_tx, _) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+    gps.rollover = Rollover::new(NaiveDate::from_ymd(2020, 1, 1));
+
+    let rmc = |second| RMCData {
+        received: None,
+        talker: Talker::GPS,
+        time: NaiveTime::from_hms_milli(1, 8, second, 0),
+        status: parser::Status::Valid,
+        lat_lon: Some(LatLon {
+            latitude: 44.9343,
+            longitude: -93.2624,
+        }),
+        speed: 10.0,
+        course_over_ground: Some(180.0),
+        date: NaiveDate::from_ymd(2020, 5, 26),
+        magnetic_variation: Some(1.5),
+        magnetic_variation_east_west: Some(EastWest::West),
+        position_mode: parser::PositionMode::DifferentialGNSSFix,
+        nav_status: None,
+    };
+
+    let gga = GGAData {
+        received: None,
+        talker: Talker::GPS,
+        time: NaiveTime::from_hms_milli(1, 8, 0, 0),
+        lat_lon: Some(LatLon {
+            latitude: 44.9343,
+            longitude: -93.2624,
+        }),
+        quality: Quality::DifferentialGNSSFix,
+        num_satellites: 12,
+        hdop: Some(1.0),
+        alt: Some(264.0),
+        alt_unit: "M".to_string(),
+        sep: Some(-27.0),
+        sep_unit: "M".to_string(),
+        diff_age: Some(3),
+        diff_station: Some(101),
+    };
+
+    let gst = GSTData {
+        received: None,
+        talker: Talker::GPS,
+        time: NaiveTime::from_hms_milli(1, 8, 0, 0),
+        range_rms: Some(1.1),
+        std_major: Some(4.0),
+        std_minor: Some(3.0),
+        orientation: Some(10.0),
+        std_lat: Some(3.0),
+        std_lon: Some(4.0),
+        std_alt: Some(5.0),
+    };
+
+    gps.rmc(rmc(0), "name", &gpsd_tx, &ntp_tx);
+    gps.gga(gga, "name", &gpsd_tx, &ntp_tx);
+    gps.gst(gst, "name", &gpsd_tx);
+
+    match gpsd_rx.try_recv().unwrap() {
+        Response::Gst(gst) => {
+            assert_eq!(Some("2020-05-26T01:08:00.000Z".to_string()), gst.time);
+            assert_approx_eq!(1.1, gst.rms.unwrap(), 1e-6);
+            assert_approx_eq!(10.0, gst.orient.unwrap());
+            assert_approx_eq!(5.0, gst.alt.unwrap());
+        }
+        r => panic!("expected GST, got {:?}", r),
+    }
+
+    assert!(gpsd_rx.try_recv().is_err());
+
+    gps.rmc(rmc(1), "name", &gpsd_tx, &ntp_tx);
+
+    let tpv = match gpsd_rx.try_recv().unwrap() {
+        Response::Tpv(tpv) => tpv,
+        r => panic!("expected TPV, got {:?}", r),
+    };
+
+    assert_eq!("name", tpv.device);
+    assert_eq!(Some("2020-05-26T01:08:00.000Z".to_string()), tpv.time);
+    assert_eq!(Some(2), tpv.status);
+    assert_approx_eq!(44.9343, tpv.lat.unwrap(), 1e-5);
+    assert_approx_eq!(-93.2624, tpv.lon.unwrap(), 1e-5);
+    assert_approx_eq!(264.0, tpv.alt_msl.unwrap());
+    assert_approx_eq!(237.0, tpv.alt_hae.unwrap());
+    assert_approx_eq!(-27.0, tpv.geoid_sep.unwrap());
+    assert_approx_eq!(5.14444, tpv.speed.unwrap());
+    assert_approx_eq!(180.0, tpv.track.unwrap());
+    assert_approx_eq!(-1.5, tpv.magvar.unwrap());
+    assert_approx_eq!(-5.14444, tpv.vel_n.unwrap());
+    assert_approx_eq!(4.0, tpv.epx.unwrap());
+    assert_approx_eq!(3.0, tpv.epy.unwrap());
+    assert_approx_eq!(5.0, tpv.eph.unwrap());
+    assert_approx_eq!(5.0, tpv.epv.unwrap());
+    assert_eq!(Some(0.005), tpv.ept);
+    assert_eq!(Some(101), tpv.dgps_sta);
+
+    let json = serde_json::to_value(&tpv).unwrap();
+
+    assert_eq!("TPV", json["class"]);
+    assert_approx_eq!(237.0, json["altHAE"].as_f64().unwrap());
+    assert_approx_eq!(264.0, json["altMSL"].as_f64().unwrap());
+    assert_approx_eq!(3.0, json["dgpsAge"].as_f64().unwrap());
+    assert!(json.get("climb").is_none());
+}
+
+#[test]
+fn test_zda() {
+    let (gpsd_tx, _) = broadcast::channel(1);
+    let (ntp_tx, _) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+    gps.rollover = Rollover::new(NaiveDate::from_ymd(2020, 1, 1));
+
+    let zda = ZDAData {
+        received: None,
+        talker: Talker::GPS,
+        time: Some(NaiveTime::from_hms_milli(1, 8, 0, 0)),
+        day: Some(26),
+        month: Some(5),
+        year: Some(2020),
+        local_tz_hour: 0,
+        local_tz_minute: 0,
+    };
+
+    let expected_time = build_time(2020, 5, 26, 1, 8, 0, 0);
+
+    gps.zda(zda, "name", &gpsd_tx, &ntp_tx);
+
+    assert_eq!(2020, gps.year);
+    assert_eq!(expected_time, gps.time.unwrap());
+}
+
+#[test]
+fn test_zda_week_mismatch() {
+    let (gpsd_tx, mut gpsd_rx) = broadcast::channel(1);
+    let (ntp_tx, mut ntp_rx) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+    gps.rollover = Rollover::new(NaiveDate::from_ymd(2020, 1, 1));
+
+    let zda = |second| ZDAData {
+        received: None,
+        talker: Talker::GPS,
+        time: Some(NaiveTime::from_hms_milli(1, 8, second, 0)),
+        day: Some(26),
+        month: Some(5),
+        year: Some(2020),
+        local_tz_hour: 0,
+        local_tz_minute: 0,
+    };
+
+    let pubx_time = |week| UBXTime {
+        received: None,
+        time: NaiveTime::from_hms_milli(1, 8, 0, 0),
+        date: NaiveDate::from_ymd(2020, 5, 26),
+        time_of_week: 176880.0,
+        week,
+        leap_seconds: 18,
+        leap_second_default: false,
+        clock_bias: 0,
+        clock_drift: 0.0,
+        time_pulse_granularity: 0,
+    };
+
+    gps.zda(zda(0), "name", &gpsd_tx, &ntp_tx);
+
+    assert!(ntp_rx.try_recv().is_ok());
+    assert!(matches!(gpsd_rx.try_recv().unwrap(), Response::Toff(_)));
+
+    gps.pubx_time(pubx_time(2107), "name", &gpsd_tx, &ntp_tx);
+
+    assert_eq!(Some(18), gps.leap_seconds.offset);
+
+    // truncated to 10 bits
+    gps.pubx_time(pubx_time(2107 - 1024), "name", &gpsd_tx, &ntp_tx);
+    gps.zda(zda(1), "name", &gpsd_tx, &ntp_tx);
+
+    assert!(ntp_rx.try_recv().is_ok());
+
+    gps.pubx_time(pubx_time(2100), "name", &gpsd_tx, &ntp_tx);
+    gps.zda(zda(2), "name", &gpsd_tx, &ntp_tx);
+
+    assert!(ntp_rx.try_recv().is_err());
+
+    gps.pubx_time(pubx_time(2107), "name", &gpsd_tx, &ntp_tx);
+    gps.zda(zda(3), "name", &gpsd_tx, &ntp_tx);
+
+    assert!(ntp_rx.try_recv().is_ok());
+}
+
+#[test]
+fn test_rmc_sentence() {
+    let (gpsd_tx, _) = broadcast::channel(10);
+    let (ntp_tx, _) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+    gps.rollover = Rollover::new(NaiveDate::from_ymd(2020, 1, 1));
+
+    let rmc = ublox_sentence(
+        "GPRMC,083559.00,A,4717.11437,N,00833.91522,E,0.004,77.52,160926,,,A,V",
+        Duration::ZERO,
+    );
+
+    gps.read_nmea(rmc, "name", &gpsd_tx, &ntp_tx);
+
+    assert_eq!(build_time(2026, 9, 16, 8, 35, 59, 0), gps.time.unwrap());
+}
+
+#[test]
+fn test_ublox_sentences_ntp() {
+    let (gpsd_tx, _) = broadcast::channel(100);
+    let (ntp_tx, mut ntp_rx) = broadcast::channel(10);
+    let mut gps = GPSData::default();
+    gps.rollover = Rollover::new(NaiveDate::from_ymd(2020, 1, 1));
+
+    let epoch = build_time(2026, 9, 16, 8, 35, 0, 0).timestamp() as u64;
+
+    for second in 0..3 {
+        let received = Duration::from_secs(epoch + second);
+        let time = format!("0835{:02}.00", second);
+
+        for sentence in [
+            format!(
+                "GPRMC,{},A,4717.11437,N,00833.91522,E,0.004,77.52,160926,,,A,V",
+                time
+            ),
+            format!(
+                "GPGGA,{},4717.11437,N,00833.91522,E,1,08,1.01,499.6,M,48.0,M,,",
+                time
+            ),
+            "GPGSA,A,3,23,29,07,08,09,18,26,28,,,,,1.94,1.18,1.54,1".to_string(),
+            format!(
+                "PUBX,04,{},160926,{}.00,2436,18,1930035,-2660.664,43,",
+                time,
+                290118 + second
+            ),
+            format!("GPZDA,{},16,09,2026,00,00", time),
+        ] {
+            gps.read_nmea(
+                ublox_sentence(&sentence, received),
+                "name",
+                &gpsd_tx,
+                &ntp_tx,
+            );
+        }
+    }
+
+    let mut timestamps = vec![];
+
+    while let Ok(ts) = ntp_rx.try_recv() {
+        timestamps.push(ts.reference_sec);
+    }
+
+    assert_eq!(vec![epoch, epoch + 1, epoch + 2], timestamps);
+}
+
+#[test]
+fn test_pubx_time_week_boundary() {
+    let (gpsd_tx, _) = broadcast::channel(10);
+    let (ntp_tx, mut ntp_rx) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+    gps.rollover = Rollover::new(NaiveDate::from_ymd(2020, 1, 1));
+
+    let zda = ZDAData {
+        received: None,
+        talker: Talker::GPS,
+        time: Some(NaiveTime::from_hms(23, 59, 50)),
+        day: Some(19),
+        month: Some(9),
+        year: Some(2026),
+        local_tz_hour: 0,
+        local_tz_minute: 0,
+    };
+
+    // Saturday in UTC is already the next GPS week
+    let pubx_time = ublox_sentence(
+        "PUBX,04,235950.00,190926,8.00,2437,18,1930035,-2660.664,43,",
+        Duration::ZERO,
+    );
+
+    gps.read_nmea(pubx_time, "name", &gpsd_tx, &ntp_tx);
+    gps.zda(zda, "name", &gpsd_tx, &ntp_tx);
+
+    assert!(ntp_rx.try_recv().is_ok());
+}
+
+#[test]
+fn test_time_quality() {
+    let (gpsd_tx, _) = broadcast::channel(10);
+    let (ntp_tx, mut ntp_rx) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+    gps.rollover = Rollover::new(NaiveDate::from_ymd(2020, 1, 1));
+    gps.time_quality = TimeQualityConfig {
+        min_fix_mode: Some(3),
+        min_satellites: Some(4),
+        valid_status: Some(true),
+        leap_seconds: None,
+        consistent_sentences: Some(2),
+    };
+
+    let zda = |second, received| ZDAData {
+        received: Some(Duration::from_millis(received)),
+        talker: Talker::GPS,
+        time: Some(NaiveTime::from_hms_milli(1, 8, second, 0)),
+        day: Some(26),
+        month: Some(5),
+        year: Some(2020),
+        local_tz_hour: 0,
+        local_tz_minute: 0,
+    };
+
+    let gga = |second, quality| GGAData {
+        received: None,
+        talker: Talker::GPS,
+        time: NaiveTime::from_hms_milli(1, 8, second, 0),
+        lat_lon: None,
+        quality,
+        num_satellites: 8,
+        hdop: None,
+        alt: None,
+        alt_unit: "M".to_string(),
+        sep: None,
+        sep_unit: "M".to_string(),
+        diff_age: None,
+        diff_station: None,
+    };
+
+    let rmc = |second, status| RMCData {
+        received: None,
+        talker: Talker::GPS,
+        time: NaiveTime::from_hms_milli(1, 8, second, 0),
+        status,
+        lat_lon: None,
+        speed: 0.0,
+        course_over_ground: None,
+        date: NaiveDate::from_ymd(2020, 5, 26),
+        magnetic_variation: None,
+        magnetic_variation_east_west: None,
+        position_mode: parser::PositionMode::AutonomousGNSSFix,
+        nav_status: None,
+    };
+
+    let held = |reason| {
+        let metric = prometheus::gather()
+            .into_iter()
+            .find(|m| m.get_name() == "where_am_i_time_held_count")
+            .unwrap();
+
+        metric
+            .get_metric()
+            .iter()
+            .find(|m| {
+                m.get_label()
+                    .iter()
+                    .any(|l| l.get_name() == "device" && l.get_value() == "held")
+                    && m.get_label()
+                        .iter()
+                        .any(|l| l.get_name() == "reason" && l.get_value() == reason)
+            })
+            .map(|m| m.get_counter().get_value())
+            .unwrap_or(0.0)
+    };
+
+    gps.zda(zda(0, 0), "held", &gpsd_tx, &ntp_tx);
+
+    assert!(ntp_rx.try_recv().is_err());
+    assert_approx_eq!(1.0, held("fix_mode"));
+
+    gps.mode = Some(3);
+    gps.zda(zda(1, 1000), "held", &gpsd_tx, &ntp_tx);
+
+    assert!(ntp_rx.try_recv().is_err());
+    assert_approx_eq!(1.0, held("satellites"));
+
+    gps.gga(
+        gga(2, Quality::AutonomousGNSSFix),
+        "held",
+        &gpsd_tx,
+        &ntp_tx,
+    );
+    gps.rmc(rmc(2, parser::Status::Invalid), "held", &gpsd_tx, &ntp_tx);
+    gps.zda(zda(2, 2000), "held", &gpsd_tx, &ntp_tx);
+
+    assert!(ntp_rx.try_recv().is_err());
+    assert_approx_eq!(1.0, held("status"));
+
+    gps.gga(
+        gga(3, Quality::AutonomousGNSSFix),
+        "held",
+        &gpsd_tx,
+        &ntp_tx,
+    );
+    gps.zda(zda(3, 3000), "held", &gpsd_tx, &ntp_tx);
+
+    assert!(ntp_rx.try_recv().is_ok());
+
+    // the receiver jumped from its RTC time
+    gps.gga(
+        gga(4, Quality::AutonomousGNSSFix),
+        "held",
+        &gpsd_tx,
+        &ntp_tx,
+    );
+    gps.zda(zda(4, 9000), "held", &gpsd_tx, &ntp_tx);
+
+    assert!(ntp_rx.try_recv().is_err());
+    assert_approx_eq!(1.0, held("inconsistent"));
+
+    gps.gga(
+        gga(5, Quality::AutonomousGNSSFix),
+        "held",
+        &gpsd_tx,
+        &ntp_tx,
+    );
+    gps.zda(zda(5, 10000), "held", &gpsd_tx, &ntp_tx);
+
+    assert!(ntp_rx.try_recv().is_ok());
+}
+
+#[test]
+fn test_time_quality_leap_seconds() {
+    let (gpsd_tx, _) = broadcast::channel(10);
+    let (ntp_tx, mut ntp_rx) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+    gps.rollover = Rollover::new(NaiveDate::from_ymd(2020, 1, 1));
+
+    let zda = |second| ZDAData {
+        received: None,
+        talker: Talker::GPS,
+        time: Some(NaiveTime::from_hms_milli(1, 8, second, 0)),
+        day: Some(26),
+        month: Some(5),
+        year: Some(2020),
+        local_tz_hour: 0,
+        local_tz_minute: 0,
+    };
+
+    let pubx_time = |leap_second_default| UBXTime {
+        received: None,
+        time: NaiveTime::from_hms_milli(1, 8, 0, 0),
+        date: NaiveDate::from_ymd(2020, 5, 26),
+        time_of_week: 176880.0,
+        week: 2107,
+        leap_seconds: 18,
+        leap_second_default,
+        clock_bias: 0,
+        clock_drift: 0.0,
+        time_pulse_granularity: 0,
+    };
+
+    // the gate is off by default
+    gps.pubx_time(pubx_time(true), "name", &gpsd_tx, &ntp_tx);
+    gps.zda(zda(0), "name", &gpsd_tx, &ntp_tx);
+
+    assert!(ntp_rx.try_recv().is_ok());
+
+    gps.time_quality.leap_seconds = Some(true);
+    gps.pubx_time(pubx_time(true), "name", &gpsd_tx, &ntp_tx);
+    gps.zda(zda(1), "name", &gpsd_tx, &ntp_tx);
+
+    assert!(ntp_rx.try_recv().is_err());
+
+    gps.pubx_time(pubx_time(false), "name", &gpsd_tx, &ntp_tx);
+    gps.zda(zda(2), "name", &gpsd_tx, &ntp_tx);
+
+    assert!(ntp_rx.try_recv().is_ok());
+}
+
+#[test]
+fn test_infer_date() {
+    let now = NaiveDate::from_ymd(2020, 5, 26).and_hms(23, 59, 59);
+
+    assert_eq!(
+        NaiveDate::from_ymd(2020, 5, 26),
+        infer_date(NaiveTime::from_hms(23, 59, 58), now)
+    );
+    assert_eq!(
+        NaiveDate::from_ymd(2020, 5, 27),
+        infer_date(NaiveTime::from_hms(0, 0, 1), now)
+    );
+
+    let now = NaiveDate::from_ymd(2020, 5, 26).and_hms(0, 0, 1);
+
+    assert_eq!(
+        NaiveDate::from_ymd(2020, 5, 25),
+        infer_date(NaiveTime::from_hms(23, 59, 59), now)
+    );
+}
+
+#[test]
+fn test_timing_sentence_gga() {
+    let (gpsd_tx, mut gpsd_rx) = broadcast::channel(10);
+    let (ntp_tx, mut ntp_rx) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+    gps.timing_sentence = TimingSentence::GGA;
+
+    let now = Utc::now().naive_utc();
+    let time = NaiveTime::from_hms(now.hour(), now.minute(), now.second());
+
+    let gga = GGAData {
+        received: Some(Duration::from_secs(1)),
+        talker: Talker::GPS,
+        time,
+        lat_lon: None,
+        quality: Quality::AutonomousGNSSFix,
+        num_satellites: 8,
+        hdop: None,
+        alt: None,
+        alt_unit: "M".to_string(),
+        sep: None,
+        sep_unit: "M".to_string(),
+        diff_age: None,
+        diff_station: None,
+    };
+
+    // dated from the system clock
+    gps.gga(gga, "name", &gpsd_tx, &ntp_tx);
+
+    let expected = DateTime::<Utc>::from_utc(NaiveDateTime::new(now.date(), time), Utc);
+    let ts = ntp_rx.try_recv().unwrap();
+
+    assert_eq!(expected.timestamp() as u64, ts.reference_sec);
+    assert_eq!(1, ts.received_sec);
+    assert!(matches!(gpsd_rx.try_recv().unwrap(), Response::Toff(_)));
+    assert_eq!(Some(expected), gps.time);
+
+    let zda = ZDAData {
+        received: None,
+        talker: Talker::GPS,
+        time: Some(time),
+        day: Some(now.day()),
+        month: Some(now.month()),
+        year: Some(now.year()),
+        local_tz_hour: 0,
+        local_tz_minute: 0,
+    };
+
+    gps.zda(zda, "name", &gpsd_tx, &ntp_tx);
+
+    assert!(ntp_rx.try_recv().is_err());
+}
+
+#[test]
+fn test_timing_sentence_first() {
+    let (gpsd_tx, _) = broadcast::channel(10);
+    let (ntp_tx, mut ntp_rx) = broadcast::channel(10);
+    let mut gps = GPSData::default();
+    gps.rollover = Rollover::new(NaiveDate::from_ymd(2020, 1, 1));
+    gps.timing_sentence = TimingSentence::First;
+
+    let rmc = |second: u32| RMCData {
+        received: Some(Duration::from_secs(second.into())),
+        talker: Talker::GPS,
+        time: NaiveTime::from_hms_milli(1, 8, second, 0),
+        status: parser::Status::Valid,
+        lat_lon: None,
+        speed: 0.0,
+        course_over_ground: None,
+        date: NaiveDate::from_ymd(2020, 5, 26),
+        magnetic_variation: None,
+        magnetic_variation_east_west: None,
+        position_mode: parser::PositionMode::AutonomousGNSSFix,
+        nav_status: None,
+    };
+
+    let zda = |second| ZDAData {
+        received: None,
+        talker: Talker::GPS,
+        time: Some(NaiveTime::from_hms_milli(1, 8, second, 0)),
+        day: Some(26),
+        month: Some(5),
+        year: Some(2020),
+        local_tz_hour: 0,
+        local_tz_minute: 0,
+    };
+
+    gps.rmc(rmc(0), "name", &gpsd_tx, &ntp_tx);
+    gps.zda(zda(0), "name", &gpsd_tx, &ntp_tx);
+    gps.rmc(rmc(1), "name", &gpsd_tx, &ntp_tx);
+    gps.zda(zda(1), "name", &gpsd_tx, &ntp_tx);
+
+    let first = ntp_rx.try_recv().unwrap();
+    let second = ntp_rx.try_recv().unwrap();
+
+    assert_eq!(0, first.received_sec);
+    assert_eq!(1, second.received_sec);
+    assert_eq!(first.reference_sec + 1, second.reference_sec);
+    assert!(ntp_rx.try_recv().is_err());
+}
+
+#[test]
+fn test_timing_sentence_rmc() {
+    let (gpsd_tx, _) = broadcast::channel(10);
+    let (ntp_tx, mut ntp_rx) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+    gps.rollover = Rollover::new(NaiveDate::from_ymd(2020, 1, 1));
+    gps.timing_sentence = TimingSentence::RMC;
+
+    let expected = build_time(2026, 9, 16, 8, 35, 59, 0);
+    let received = Duration::from_secs(expected.timestamp() as u64 + 1);
+
+    let rmc = ublox_sentence(
+        "GPRMC,083559.00,A,4717.11437,N,00833.91522,E,0.004,77.52,160926,,,A,V",
+        received,
+    );
+
+    gps.read_nmea(rmc, "name", &gpsd_tx, &ntp_tx);
+
+    let ts = ntp_rx.try_recv().unwrap();
+
+    assert_eq!(expected.timestamp() as u64, ts.reference_sec);
+    assert_eq!(received.as_secs(), ts.received_sec);
+}
+
+#[test]
+fn test_timing_sentence_pubx04() {
+    let (gpsd_tx, _) = broadcast::channel(10);
+    let (ntp_tx, mut ntp_rx) = broadcast::channel(1);
+    let mut gps = GPSData::default();
+    gps.rollover = Rollover::new(NaiveDate::from_ymd(2020, 1, 1));
+    gps.timing_sentence = TimingSentence::PUBX04;
+
+    let expected = build_time(2020, 5, 26, 1, 8, 0, 0);
+    let received = Duration::from_secs(expected.timestamp() as u64 + 1);
+
+    let time = ublox_sentence(
+        "PUBX,04,010800.00,260520,176898.00,2107,18,1930035,-2660.664,43,",
+        received,
+    );
+
+    gps.read_nmea(time, "name", &gpsd_tx, &ntp_tx);
+
+    let ts = ntp_rx.try_recv().unwrap();
+
+    // the serial receive time, not the time the sentence was processed
+    assert_eq!(expected.timestamp() as u64, ts.reference_sec);
+    assert_eq!(received.as_secs(), ts.received_sec);
 }
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod test {
+    use crate::gps::{identify, Detection, GpsType};
+    use crate::nmea::{parser::MessageType, TXTData, Talker, NMEA};
+
+    fn unsupported(sentence: &str) -> NMEA {
+        NMEA::Unsupported(sentence.to_string())
+    }
+
+    fn txt(text: &str) -> NMEA {
+        NMEA::TXT(TXTData {
+            received: None,
+            talker: Talker::GPS,
+            num_msgs: 1,
+            msg: 1,
+            msg_type: MessageType::Notice,
+            text: text.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_identify_private() {
+        assert_eq!(
+            Some(GpsType::UBloxNMEA),
+            identify(&unsupported("PUBX,00,081350.00,4717.113210,N"))
+        );
+        assert_eq!(
+            Some(GpsType::MKT),
+            identify(&unsupported(
+                "PMTK705,AXN_2.10_3339_2012072601,5223,PA6H,1.0"
+            ))
+        );
+        assert_eq!(
+            Some(GpsType::Quectel),
+            identify(&unsupported(
+                "PQTMVERNO,LC29HDANR11A01S_RSA,2022/07/28,17:42:59"
+            ))
+        );
+        assert_eq!(
+            Some(GpsType::Quectel),
+            identify(&unsupported("PAIR001,062,0"))
+        );
+        assert_eq!(Some(GpsType::SiRF), identify(&unsupported("PSRF150,1")));
+        assert_eq!(
+            Some(GpsType::Garmin),
+            identify(&unsupported("PGRME,15.0,M,45.0,M,25.0,M"))
+        );
+        assert_eq!(None, identify(&unsupported("PXYZ,1")));
+    }
+
+    #[test]
+    fn test_identify_private_quectel_mkt() {
+        assert_eq!(
+            Some(GpsType::Quectel),
+            identify(&unsupported(
+                "PMTK705,AXN_3.8_3333_16070800,0000,Quectel-L76B,1.0"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_detection_mkt() {
+        let mut detection = Detection::default();
+
+        assert_eq!(
+            None,
+            detection.add(&unsupported(
+                "PMTK705,AXN_2.10_3339_2012072601,5223,PA6H,1.0"
+            ))
+        );
+        assert_eq!(None, detection.add(&txt("ANTSTATUS=OK")));
+
+        assert_eq!(Some(GpsType::MKT), detection.finish());
+    }
+
+    #[test]
+    fn test_detection_quectel_answers_mkt() {
+        let mut detection = Detection::default();
+
+        // the MKT probe is answered before the Quectel probe
+        assert_eq!(
+            None,
+            detection.add(&unsupported("PMTK705,AXN_3.8_3333_16070800,0000,L76,1.0"))
+        );
+        assert_eq!(
+            Some(GpsType::Quectel),
+            detection.add(&unsupported("PQTMVERNO,L76NR03A01S,2021/03/09,18:10:26"))
+        );
+    }
+
+    #[test]
+    fn test_detection_nothing() {
+        let mut detection = Detection::default();
+
+        assert_eq!(None, detection.add(&txt("ANTSTATUS=OK")));
+
+        assert_eq!(None, detection.finish());
+    }
+
+    #[test]
+    fn test_identify_txt() {
+        assert_eq!(
+            Some(GpsType::UBloxNMEA),
+            identify(&txt("u-blox ag - www.u-blox.com"))
+        );
+        assert_eq!(
+            Some(GpsType::UBloxNMEA),
+            identify(&txt("HW UBX-M8030 00080000"))
+        );
+        assert_eq!(Some(GpsType::Quectel), identify(&txt("Quectel LC29H")));
+        assert_eq!(Some(GpsType::MKT), identify(&txt("GlobalTop PA6H")));
+        assert_eq!(None, identify(&txt("ANTSTATUS=OK")));
+    }
+
+    #[test]
+    fn test_identify_other() {
+        let zda = crate::nmea::parser::zda("GPZDA,082710.00,16,09,2002,00,00")
+            .unwrap()
+            .1;
+
+        assert_eq!(None, identify(&NMEA::ZDA(zda)));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::gps::EpochAssembler;
+
+use chrono::NaiveTime;
+
+fn time(second: u32) -> Option<NaiveTime> {
+    Some(NaiveTime::from_hms(1, 8, second))
+}
+
+#[test]
+fn test_add() {
+    let mut epochs = EpochAssembler::default();
+
+    assert_eq!(None, epochs.add("GSA", None));
+    assert_eq!(None, epochs.add("GGA", time(0)));
+    assert_eq!(None, epochs.add("RMC", time(0)));
+
+    let fix = epochs.add("GGA", time(1)).unwrap();
+
+    assert_eq!(time(0), fix.time);
+    assert_eq!(vec!["GSA", "GGA", "RMC"], fix.sentences);
+
+    assert_eq!(time(1), epochs.fix.time);
+    assert_eq!(vec!["GGA"], epochs.fix.sentences);
+}
+
+#[test]
+fn test_timeout() {
+    let mut epochs = EpochAssembler::default();
+
+    assert_eq!(None, epochs.timeout());
+
+    epochs.add("GGA", time(0));
+
+    let fix = epochs.timeout().unwrap();
+
+    assert_eq!(time(0), fix.time);
+    assert!(epochs.fix.sentences.is_empty());
+    assert_eq!(None, epochs.timeout());
+}
+
+#[test]
+fn test_end() {
+    let mut epochs = EpochAssembler::default();
+
+    let cycle = |epochs: &mut EpochAssembler, second| {
+        epochs.add("GGA", time(second));
+        epochs.add("GSA", None);
+        epochs.add("GSA", None);
+        epochs.add("RMC", time(second));
+        epochs.add("GSV", None);
+    };
+
+    cycle(&mut epochs, 0);
+    assert_eq!(None, epochs.end());
+    epochs.timeout();
+
+    assert_eq!(None, epochs.learned_end());
+
+    cycle(&mut epochs, 1);
+    assert_eq!(None, epochs.end());
+    epochs.timeout();
+
+    assert_eq!(Some(("GSV", 1)), epochs.learned_end());
+
+    epochs.add("GGA", time(2));
+    epochs.add("GSA", None);
+    assert_eq!(None, epochs.end());
+    epochs.add("GSA", None);
+    epochs.add("RMC", time(2));
+    epochs.add("GSV", None);
+
+    let fix = epochs.end().unwrap();
+
+    assert_eq!(time(2), fix.time);
+    assert_eq!(5, fix.sentences.len());
+    assert_eq!(Some(("GSV", 1)), epochs.learned_end());
+}
+
+#[test]
+fn test_end_count() {
+    let mut epochs = EpochAssembler::default();
+
+    for second in 0..2 {
+        epochs.add("GGA", time(second));
+        epochs.add("GSA", None);
+        epochs.add("GSA", None);
+        epochs.timeout();
+    }
+
+    assert_eq!(Some(("GSA", 2)), epochs.learned_end());
+
+    epochs.add("GGA", time(2));
+    epochs.add("GSA", None);
+
+    assert_eq!(None, epochs.end());
+
+    epochs.add("GSA", None);
+
+    assert!(epochs.end().is_some());
+}
+
+#[test]
+fn test_end_forgotten() {
+    let mut epochs = EpochAssembler::default();
+
+    for second in 0..2 {
+        epochs.add("GGA", time(second));
+        epochs.add("RMC", time(second));
+        epochs.timeout();
+    }
+
+    assert_eq!(Some(("RMC", 1)), epochs.learned_end());
+
+    // VTG was enabled
+    epochs.add("GGA", time(2));
+    epochs.add("VTG", None);
+
+    let fix = epochs.add("GGA", time(3)).unwrap();
+
+    assert_eq!(vec!["GGA", "VTG"], fix.sentences);
+    assert_eq!(None, epochs.learned_end());
+}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod test {
+    use crate::gps::garmin::*;
+    use crate::nmea::{to_sentence, NavigationMode, OperationMode, NMEA};
+    use chrono::naive::{NaiveDate, NaiveTime};
+
+    #[test]
+    fn test_grm_e() {
+        let parsed = grm_e("PGRME,15.0,M,45.0,M,25.0,M").unwrap().1;
+
+        assert_approx_eq!(15.0, parsed.horizontal.unwrap());
+        assert_approx_eq!(45.0, parsed.vertical.unwrap());
+        assert_approx_eq!(25.0, parsed.spherical.unwrap());
+
+        let parsed = grm_e("PGRME,,M,,M,,M").unwrap().1;
+
+        assert_eq!(None, parsed.horizontal);
+        assert_eq!(None, parsed.spherical);
+    }
+
+    #[test]
+    fn test_grm_f() {
+        let parsed =
+            grm_f("PGRMF,290,293895,160305,093802,13,5213.1439,N,02100.6511,E,A,2,0,62,2,1")
+                .unwrap()
+                .1;
+
+        assert_eq!(290, parsed.week);
+        assert_eq!(293895, parsed.seconds);
+        assert_eq!(Some(NaiveDate::from_ymd(2005, 3, 16)), parsed.date);
+        assert_eq!(Some(NaiveTime::from_hms(9, 38, 2)), parsed.time);
+        assert_eq!(13, parsed.leap_seconds);
+        assert_approx_eq!(52.219065, parsed.lat_lon.as_ref().unwrap().latitude);
+        assert_approx_eq!(21.01085, parsed.lat_lon.as_ref().unwrap().longitude);
+        assert_eq!(OperationMode::Automatic, parsed.mode);
+        assert_eq!(NavigationMode::Fix3D, parsed.navigation_mode());
+        assert_approx_eq!(0.0, parsed.speed.unwrap());
+        assert_approx_eq!(62.0, parsed.course.unwrap());
+        assert_eq!(Some(2), parsed.pdop);
+        assert_eq!(Some(1), parsed.tdop);
+    }
+
+    #[test]
+    fn test_grm_f_no_fix() {
+        let parsed = grm_f("PGRMF,290,293895,,,13,,,,,A,0,,,,").unwrap().1;
+
+        assert_eq!(None, parsed.date);
+        assert_eq!(None, parsed.lat_lon);
+        assert_eq!(NavigationMode::FixNone, parsed.navigation_mode());
+        assert_eq!(None, parsed.pdop);
+    }
+
+    #[test]
+    fn test_grm_t() {
+        let parsed = grm_t("PGRMT,GPS 16x-LVS Ver. 3.70,P,P,R,R,P,C,32,R")
+            .unwrap()
+            .1;
+
+        assert_eq!("GPS 16x-LVS Ver. 3.70", parsed.product);
+        assert_eq!(Some(GarminTest::Pass), parsed.rom_checksum);
+        assert_eq!(Some(GarminTest::Pass), parsed.receiver_failure);
+        assert_eq!(Some(GarminRetention::Retained), parsed.stored_data);
+        assert_eq!(Some(GarminRetention::Retained), parsed.real_time_clock);
+        assert_eq!(Some(GarminTest::Pass), parsed.oscillator_drift);
+        assert!(parsed.data_collection);
+        assert_approx_eq!(32.0, parsed.temperature.unwrap());
+        assert_eq!(Some(GarminRetention::Retained), parsed.configuration);
+
+        let parsed = grm_t("PGRMT,GPS 18x,F,,L,,,,,").unwrap().1;
+
+        assert_eq!(Some(GarminTest::Fail), parsed.rom_checksum);
+        assert_eq!(None, parsed.receiver_failure);
+        assert_eq!(Some(GarminRetention::Lost), parsed.stored_data);
+        assert!(!parsed.data_collection);
+        assert_eq!(None, parsed.temperature);
+    }
+
+    #[test]
+    fn test_parse_private() {
+        let driver = Garmin::default();
+
+        match driver
+            .parse_private("PGRME,15.0,M,45.0,M,25.0,M")
+            .unwrap()
+            .1
+        {
+            NMEA::PGRM(GarminData::ErrorEstimate(_)) => (),
+            n => panic!("expected error estimate, got {:?}", n),
+        }
+
+        match driver.parse_private("PGRMT,GPS 18x,F,,L,,,,,").unwrap().1 {
+            NMEA::PGRM(GarminData::SensorStatus(_)) => (),
+            n => panic!("expected sensor status, got {:?}", n),
+        }
+    }
+
+    #[test]
+    fn test_message_settings() {
+        let driver = Garmin::default();
+
+        let settings = driver.message_settings(&["PGRME".to_string()]);
+
+        assert_eq!(OUTPUT_MESSAGES.len(), settings.len());
+        assert!(settings.iter().any(|s| s.id == "PGRME" && s.enabled));
+        assert!(settings.iter().any(|s| s.id == "GGA" && !s.enabled));
+    }
+
+    #[test]
+    fn test_set_output() {
+        let set = GarminSetOutput {
+            sentence: "GPGGA".to_string(),
+            enabled: true,
+        };
+
+        assert_eq!("$PGRMO,GPGGA,1*20\r\n", to_sentence(&set).unwrap());
+
+        let set = GarminSetOutput {
+            sentence: "PGRME".to_string(),
+            enabled: false,
+        };
+
+        assert_eq!("$PGRMO,PGRME,0*3A\r\n", to_sentence(&set).unwrap());
+    }
+}
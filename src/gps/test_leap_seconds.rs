@@ -0,0 +1,115 @@
+use crate::gps::{LeapIndicator, LeapSecond, LeapSeconds, LeapSecondsList};
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+const LIST: &str = "#	Updated through IERS Bulletin C64
+#
+#$	 3676924800
+#@	 3912710400
+#
+#	NTP Time      DTAI    Day Month Year
+#
+2272060800      10      # 1 Jan 1972
+2287785600      11      # 1 Jul 1972
+3550089600      35      # 1 Jul 2012
+3644697600      36      # 1 Jul 2015
+3692217600      37      # 1 Jan 2017
+";
+
+fn date_time(year: i32, month: u32, day: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd(year, month, day).and_hms(0, 0, 0)
+}
+
+#[test]
+fn test_list_parse() {
+    let list = LeapSecondsList::parse(LIST).unwrap();
+
+    assert_eq!(5, list.offsets.len());
+    assert_eq!((date_time(1972, 1, 1), 10), list.offsets[0]);
+    assert_eq!((date_time(2017, 1, 1), 37), list.offsets[4]);
+    assert_eq!(Some(date_time(2023, 12, 28)), list.expires);
+}
+
+#[test]
+fn test_list_parse_invalid() {
+    assert!(LeapSecondsList::parse("# only comments\n").is_err());
+    assert!(LeapSecondsList::parse("2272060800 ten\n").is_err());
+    assert!(LeapSecondsList::parse("2272060800\n").is_err());
+}
+
+#[test]
+fn test_list_gps_utc_offset() {
+    let list = LeapSecondsList::parse(LIST).unwrap();
+
+    assert_eq!(None, list.gps_utc_offset(date_time(1971, 1, 1)));
+    assert_eq!(Some(16), list.gps_utc_offset(date_time(2012, 7, 1)));
+    assert_eq!(Some(18), list.gps_utc_offset(date_time(2020, 5, 26)));
+}
+
+#[test]
+fn test_list_next() {
+    let list = LeapSecondsList::parse(LIST).unwrap();
+
+    let expected = LeapSecond {
+        at: date_time(2017, 1, 1),
+        change: 1,
+    };
+
+    assert_eq!(Some(expected), list.next(date_time(2016, 12, 31)));
+    assert_eq!(None, list.next(date_time(2020, 5, 26)));
+    assert_eq!(None, list.next(date_time(1971, 1, 1)));
+}
+
+#[test]
+fn test_list_expired() {
+    let list = LeapSecondsList::parse(LIST).unwrap();
+
+    assert!(!list.is_expired(date_time(2023, 12, 27)));
+    assert!(list.is_expired(date_time(2023, 12, 28)));
+}
+
+#[test]
+fn test_indicator() {
+    let list = LeapSecondsList::parse(LIST).unwrap();
+    let leap_seconds = LeapSeconds::new(Some(list));
+
+    assert_eq!(
+        LeapIndicator::NoWarning,
+        leap_seconds.indicator(date_time(2016, 12, 1))
+    );
+    assert_eq!(
+        LeapIndicator::Insert,
+        leap_seconds.indicator(date_time(2016, 12, 5))
+    );
+    assert_eq!(
+        LeapIndicator::NoWarning,
+        leap_seconds.indicator(date_time(2017, 1, 1))
+    );
+}
+
+#[test]
+fn test_indicator_receiver() {
+    let list = LeapSecondsList::parse(LIST).unwrap();
+    let mut leap_seconds = LeapSeconds::new(Some(list));
+
+    leap_seconds.receiver_announces = true;
+
+    assert_eq!(
+        LeapIndicator::NoWarning,
+        leap_seconds.indicator(date_time(2016, 12, 31))
+    );
+
+    leap_seconds.announced = Some(LeapSecond {
+        at: date_time(2017, 1, 1),
+        change: -1,
+    });
+
+    assert_eq!(
+        LeapIndicator::Delete,
+        leap_seconds.indicator(date_time(2016, 12, 31))
+    );
+    assert_eq!(
+        2,
+        leap_seconds.indicator(date_time(2016, 12, 31)).shm_leap()
+    );
+}
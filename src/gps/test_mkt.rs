@@ -1,6 +1,25 @@
 #[cfg(test)]
 mod test {
+    use crate::configuration::MKTConfig;
     use crate::gps::mkt::*;
+    use crate::nmea::{to_sentence, NMEA};
+
+    #[test]
+    fn test_mkt_001() {
+        let parsed = mkt_001("PMTK001,314,3").unwrap().1;
+
+        assert_eq!(SET_NMEA_OUTPUT, parsed.command);
+        assert_eq!(MKTResult::Succeeded, parsed.result);
+
+        let parsed = mkt_001("PMTK001,285,1").unwrap().1;
+
+        assert_eq!(SET_PPS, parsed.command);
+        assert_eq!(MKTResult::Unsupported, parsed.result);
+
+        let parsed = mkt_001("PMTK001,220,7").unwrap().1;
+
+        assert_eq!(MKTResult::Unhandled(7), parsed.result);
+    }
 
     #[test]
     fn test_mkt_010() {
@@ -23,4 +42,80 @@ mod test {
 
         assert_eq!("MTKGPS", parsed.message);
     }
+
+    #[test]
+    fn test_mkt_705() {
+        let parsed = mkt_705("PMTK705,AXN_2.10_3339_2012072601,5223,PA6H,1.0")
+            .unwrap()
+            .1;
+
+        assert_eq!("AXN_2.10_3339_2012072601", parsed.release);
+        assert_eq!("5223", parsed.build_id);
+        assert_eq!(Some("PA6H".to_string()), parsed.model);
+        assert_eq!(Some("1.0".to_string()), parsed.sdk_version);
+
+        let parsed = mkt_705("PMTK705,AXN_1.30,0000").unwrap().1;
+
+        assert_eq!("AXN_1.30", parsed.release);
+        assert_eq!(None, parsed.model);
+        assert_eq!(None, parsed.sdk_version);
+    }
+
+    #[test]
+    fn test_parse_private() {
+        let driver = MKT::default();
+
+        match driver.parse_private("PMTK001,314,3").unwrap().1 {
+            NMEA::PMKT(MKTData::Acknowledge(a)) => assert_eq!(SET_NMEA_OUTPUT, a.command),
+            n => panic!("expected acknowledgement, got {:?}", n),
+        }
+
+        match driver.parse_private("PMTK705,AXN_1.30,0000").unwrap().1 {
+            NMEA::PMKT(MKTData::Release(_)) => (),
+            n => panic!("expected release, got {:?}", n),
+        }
+    }
+
+    #[test]
+    fn test_commands() {
+        let mut set = MKTSetNMEAOutput::default();
+        set.gll = 1;
+        set.rmc = 1;
+        set.gga = 1;
+
+        assert_eq!(
+            "$PMTK314,1,1,0,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0*29\r\n",
+            to_sentence(&set).unwrap()
+        );
+
+        let set = MKTSetFixInterval { interval: 200 };
+
+        assert_eq!("$PMTK220,200*2C\r\n", to_sentence(&set).unwrap());
+
+        let set = MKTSetBaudRate { baud_rate: 115200 };
+
+        assert_eq!("$PMTK251,115200*1F\r\n", to_sentence(&set).unwrap());
+
+        let set = MKTSetPPSSync { enabled: true };
+
+        assert_eq!("$PMTK255,1*2D\r\n", to_sentence(&set).unwrap());
+
+        let set = MKTSetPPS {
+            mode: MKTPPSMode::Fix3D.value(),
+            pulse_width: MKTConfig::default().pps_pulse_width(),
+        };
+
+        assert_eq!("$PMTK285,2,100*3E\r\n", to_sentence(&set).unwrap());
+
+        assert_eq!("$PMTK605*31\r\n", to_sentence(&MKTReleasePoll {}).unwrap());
+    }
+
+    #[test]
+    fn test_restart() {
+        assert_eq!("$PMTK101*32\r\n", to_sentence(&MKTRestart::Hot).unwrap());
+        assert_eq!(
+            "$PMTK104*37\r\n",
+            to_sentence(&MKTRestart::FullCold).unwrap()
+        );
+    }
 }
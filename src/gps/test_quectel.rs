@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod test {
+    use crate::configuration::QuectelConfig;
+    use crate::gps::quectel::*;
+    use crate::nmea::{to_sentence, NavigationMode, NMEA};
+    use chrono::naive::{NaiveDate, NaiveTime};
+
+    #[test]
+    fn test_pair_001() {
+        let parsed = pair_001("PAIR001,062,0").unwrap().1;
+
+        assert_eq!(SET_NMEA_OUTPUT_RATE, parsed.command);
+        assert_eq!(QuectelResult::Succeeded, parsed.result);
+
+        let parsed = pair_001("PAIR001,752,3").unwrap().1;
+
+        assert_eq!(SET_PPS, parsed.command);
+        assert_eq!(QuectelResult::Unsupported, parsed.result);
+
+        let parsed = pair_001("PAIR001,050,9").unwrap().1;
+
+        assert_eq!(QuectelResult::Unhandled(9), parsed.result);
+    }
+
+    #[test]
+    fn test_pqtm_response() {
+        let parsed = pqtm_response("PQTMCFGPPS,OK").unwrap().1;
+
+        assert_eq!("CFGPPS", parsed.command);
+        assert_eq!(None, parsed.error);
+
+        let parsed = pqtm_response("PQTMCFGPPS,ERROR,1").unwrap().1;
+
+        assert_eq!("CFGPPS", parsed.command);
+        assert_eq!(Some(1), parsed.error);
+    }
+
+    #[test]
+    fn test_pqtm_verno() {
+        let parsed = pqtm_verno("PQTMVERNO,LC29HDANR11A01S_RSA,2022/07/28,17:42:59")
+            .unwrap()
+            .1;
+
+        assert_eq!("LC29HDANR11A01S_RSA", parsed.version);
+        assert_eq!("2022/07/28", parsed.build_date);
+        assert_eq!("17:42:59", parsed.build_time);
+    }
+
+    #[test]
+    fn test_pqtm_pvt() {
+        let parsed = pqtm_pvt("PQTMPVT,1,31075000,20220124,083737.000,,3,8,18,31.12738291,117.26372910,34.212,5.267,0.016,-0.065,0.098,0.067,0.000,0.93,1.34").unwrap().1;
+
+        assert_eq!(1, parsed.message_version);
+        assert_eq!(31075000, parsed.time_of_week);
+        assert_eq!(Some(NaiveDate::from_ymd(2022, 1, 24)), parsed.date);
+        assert_eq!(Some(NaiveTime::from_hms(8, 37, 37)), parsed.time);
+        assert_eq!(NavigationMode::Fix3D, parsed.navigation_mode());
+        assert_eq!(8, parsed.satellites_used);
+        assert_eq!(18, parsed.leap_seconds);
+        assert_approx_eq!(31.12738291, parsed.latitude.unwrap());
+        assert_approx_eq!(117.2637291, parsed.longitude.unwrap());
+        assert_approx_eq!(34.212, parsed.altitude.unwrap());
+        assert_approx_eq!(5.267, parsed.separation.unwrap());
+        assert_approx_eq!(-0.065, parsed.velocity_east.unwrap());
+        assert_approx_eq!(0.067, parsed.speed.unwrap());
+        assert_approx_eq!(1.34, parsed.pdop.unwrap());
+    }
+
+    #[test]
+    fn test_pqtm_pvt_no_fix() {
+        let parsed = pqtm_pvt("PQTMPVT,1,0,,,,0,0,18,,,,,,,,,,,").unwrap().1;
+
+        assert_eq!(None, parsed.date);
+        assert_eq!(None, parsed.time);
+        assert_eq!(NavigationMode::FixNone, parsed.navigation_mode());
+        assert_eq!(None, parsed.latitude);
+        assert_eq!(None, parsed.pdop);
+    }
+
+    #[test]
+    fn test_parse_private() {
+        let driver = Quectel::default();
+
+        match driver.parse_private("PAIR001,062,0").unwrap().1 {
+            NMEA::Quectel(QuectelData::Acknowledge(_)) => (),
+            n => panic!("expected acknowledgement, got {:?}", n),
+        }
+
+        match driver.parse_private("PQTMVERNO,ERROR,1").unwrap().1 {
+            NMEA::Quectel(QuectelData::Response(r)) => assert_eq!("VERNO", r.command),
+            n => panic!("expected response, got {:?}", n),
+        }
+    }
+
+    #[test]
+    fn test_message_settings() {
+        let driver = Quectel::default();
+
+        let settings = driver.message_settings(&["ZDA".to_string()]);
+
+        assert_eq!(OUTPUT_MESSAGES.len(), settings.len());
+        assert!(settings.iter().any(|s| s.id == "ZDA" && s.enabled));
+        assert!(settings.iter().any(|s| s.id == "GGA" && !s.enabled));
+    }
+
+    #[test]
+    fn test_nmea_type() {
+        assert_eq!(Some(0), nmea_type("GGA"));
+        assert_eq!(Some(8), nmea_type("GST"));
+        assert_eq!(None, nmea_type("GNS"));
+    }
+
+    #[test]
+    fn test_commands() {
+        let set = QuectelSetNMEAOutputRate {
+            message_type: 0,
+            rate: 1,
+        };
+
+        assert_eq!("$PAIR062,0,1*3F\r\n", to_sentence(&set).unwrap());
+
+        let set = QuectelSetFixInterval { interval: 200 };
+
+        assert_eq!("$PAIR050,200*21\r\n", to_sentence(&set).unwrap());
+
+        let set = QuectelSetPPS {
+            mode: QuectelPPSMode::Fix3D.value(),
+            pulse_width: QuectelConfig::default().pps_pulse_width(),
+        };
+
+        assert_eq!("$PAIR752,2,100*39\r\n", to_sentence(&set).unwrap());
+
+        assert_eq!(
+            "$PQTMVERNO*58\r\n",
+            to_sentence(&QuectelVersionPoll {}).unwrap()
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use crate::gps::{build_date, full_year, gps_week, Rollover};
+
+use chrono::{Duration, NaiveDate};
+
+#[test]
+fn test_correct() {
+    let rollover = Rollover::new(NaiveDate::from_ymd(2019, 4, 7));
+
+    // first rollover
+    assert_eq!(
+        NaiveDate::from_ymd(2019, 4, 7),
+        rollover.correct(NaiveDate::from_ymd(1999, 8, 22))
+    );
+    assert_eq!(
+        NaiveDate::from_ymd(2020, 5, 26),
+        rollover.correct(NaiveDate::from_ymd(2000, 10, 10))
+    );
+    // second rollover
+    assert_eq!(
+        NaiveDate::from_ymd(2020, 5, 26),
+        rollover.correct(NaiveDate::from_ymd(1981, 2, 24))
+    );
+    assert_eq!(
+        NaiveDate::from_ymd(2020, 5, 26),
+        rollover.correct(NaiveDate::from_ymd(2020, 5, 26))
+    );
+}
+
+#[test]
+fn test_default() {
+    let rollover = Rollover::default();
+
+    assert_eq!(build_date(), rollover.pivot());
+    assert!(rollover.pivot() > NaiveDate::from_ymd(2022, 1, 1));
+}
+
+#[test]
+fn test_is_plausible() {
+    let pivot = NaiveDate::from_ymd(2019, 4, 7);
+    let rollover = Rollover::new(pivot);
+
+    assert!(!rollover.is_plausible(pivot.pred()));
+    assert!(rollover.is_plausible(pivot));
+    assert!(rollover.is_plausible(pivot + Duration::weeks(1023)));
+    assert!(!rollover.is_plausible(pivot + Duration::weeks(1024)));
+}
+
+#[test]
+fn test_gps_week() {
+    assert_eq!(0, gps_week(NaiveDate::from_ymd(1980, 1, 6)));
+    assert_eq!(1024, gps_week(NaiveDate::from_ymd(1999, 8, 22)));
+    assert_eq!(2048, gps_week(NaiveDate::from_ymd(2019, 4, 7)));
+    assert_eq!(2107, gps_week(NaiveDate::from_ymd(2020, 5, 26)));
+}
+
+#[test]
+fn test_full_year() {
+    let pivot = NaiveDate::from_ymd(2026, 10, 16);
+
+    assert_eq!(2026, full_year(26, pivot));
+    assert_eq!(2000, full_year(0, pivot));
+    assert_eq!(2076, full_year(76, pivot));
+    assert_eq!(1977, full_year(77, pivot));
+    assert_eq!(1999, full_year(99, pivot));
+
+    assert_eq!(2100, full_year(0, NaiveDate::from_ymd(2050, 1, 1)));
+    assert_eq!(2099, full_year(99, NaiveDate::from_ymd(2050, 1, 1)));
+}
@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod test {
+    use crate::gps::sirf::*;
+    use crate::nmea::{to_sentence, NMEA};
+
+    #[test]
+    fn test_srf_150() {
+        let parsed = srf_150("PSRF150,1").unwrap().1;
+
+        assert!(parsed.ok_to_send);
+
+        let parsed = srf_150("PSRF150,0").unwrap().1;
+
+        assert!(!parsed.ok_to_send);
+    }
+
+    #[test]
+    fn test_srf_151() {
+        let parsed = srf_151("PSRF151,3,1485,147873.000,0x00000080").unwrap().1;
+
+        assert_eq!(3, parsed.time_valid);
+        assert_eq!(1485, parsed.week);
+        assert_approx_eq!(147873.0, parsed.time_of_week);
+        assert_eq!(0x80, parsed.satellites);
+    }
+
+    #[test]
+    fn test_srf_156() {
+        let parsed = srf_156("PSRF156,26,4,0,1,2").unwrap().1;
+
+        assert_eq!("26", parsed.message_type);
+        assert_eq!(vec!["4", "0", "1", "2"], parsed.fields);
+    }
+
+    #[test]
+    fn test_parse_private() {
+        let driver = SiRF::default();
+
+        match driver.parse_private("PSRF150,1").unwrap().1 {
+            NMEA::PSRF(SiRFData::OkToSend(_)) => (),
+            n => panic!("expected OK to send, got {:?}", n),
+        }
+
+        match driver.parse_private("PSRF156,26").unwrap().1 {
+            NMEA::PSRF(SiRFData::ExtendedEphemeris(e)) => assert!(e.fields.is_empty()),
+            n => panic!("expected extended ephemeris, got {:?}", n),
+        }
+    }
+
+    #[test]
+    fn test_message_settings() {
+        let driver = SiRF::default();
+
+        let settings = driver.message_settings(&["ZDA".to_string()]);
+
+        assert_eq!(OUTPUT_MESSAGES.len(), settings.len());
+        assert!(settings.iter().any(|s| s.id == "ZDA" && s.enabled));
+        assert!(settings.iter().any(|s| s.id == "GGA" && !s.enabled));
+    }
+
+    #[test]
+    fn test_nmea_type() {
+        assert_eq!(Some(0), nmea_type("GGA"));
+        assert_eq!(Some(8), nmea_type("ZDA"));
+        assert_eq!(None, nmea_type("GNS"));
+    }
+
+    #[test]
+    fn test_set_rate() {
+        let set = SiRFSetRate {
+            message_type: 0,
+            rate: 1,
+        };
+
+        assert_eq!("$PSRF103,00,00,01,01*25\r\n", to_sentence(&set).unwrap());
+
+        let set = SiRFSetRate {
+            message_type: 8,
+            rate: 0,
+        };
+
+        assert_eq!("$PSRF103,08,00,00,01*2C\r\n", to_sentence(&set).unwrap());
+    }
+}
@@ -4,6 +4,14 @@ mod test {
         ublox_nmea::{ubx_00, ubx_03, ubx_04},
         *,
     };
+    use crate::{
+        configuration::UBloxConfig,
+        ubx::cfg::{
+            UBXConfigItem, UBXConstellation, UBXDynamicModel, UBXLayer, CFG_VALGET,
+            INFMSG_NMEA_I2C, INFMSG_TXT, NAVSPG_DYNMODEL, RATE_MEAS, SIGNAL_GLO_ENA,
+            SIGNAL_GPS_ENA,
+        },
+    };
     use chrono::{NaiveDate, NaiveTime};
     use nom::error::*;
 
@@ -155,7 +163,7 @@ mod test {
         let parsed = p::<UBXTime>(input, result);
 
         assert_eq!(NaiveTime::from_hms_milli(7, 37, 31, 0), parsed.time);
-        assert_eq!(NaiveDate::from_ymd(2, 12, 9), parsed.date);
+        assert_eq!(NaiveDate::from_ymd(2002, 12, 9), parsed.date);
         assert_approx_eq!(113851.0, parsed.time_of_week);
         assert_eq!(1196, parsed.week);
         assert_eq!(15, parsed.leap_seconds);
@@ -164,4 +172,82 @@ mod test {
         assert_approx_eq!(-2660.664, parsed.clock_drift);
         assert_eq!(43, parsed.time_pulse_granularity);
     }
+
+    #[test]
+    fn test_config_items() {
+        let config = UBloxConfig {
+            port: Some(UBXPort::USB),
+            dynamic_model: Some(UBXDynamicModel::Stationary),
+            constellations: Some(vec![UBXConstellation::GPS]),
+            ..UBloxConfig::default()
+        };
+
+        let driver = UBloxNMEA::new(config);
+        let messages = driver.message_settings(&vec!["ZDA".to_string()]);
+
+        let items: Vec<UBXConfigItem> = driver
+            .config_items(&messages)
+            .into_iter()
+            .map(|(_, item)| item)
+            .collect();
+
+        assert!(items.contains(&UBXConfigItem::new(0x209100D8 + 3, 1)));
+        assert!(items.contains(&UBXConfigItem::new(0x209100AB + 3, 0)));
+        assert!(items.contains(&UBXConfigItem::new(0x20910006 + 3, 0)));
+        assert!(items.contains(&UBXConfigItem::new(NAVSPG_DYNMODEL, 2)));
+        assert!(items.contains(&UBXConfigItem::new(SIGNAL_GPS_ENA, 1)));
+        assert!(items.contains(&UBXConfigItem::new(SIGNAL_GLO_ENA, 0)));
+        assert!(!items.iter().any(|item| item.key == RATE_MEAS));
+        // TXT is an information message
+        assert!(items.contains(&UBXConfigItem::new(INFMSG_NMEA_I2C + 3, 0)));
+    }
+
+    #[test]
+    fn test_config_items_txt() {
+        let driver = UBloxNMEA::default();
+        let messages = driver.message_settings(&vec!["TXT".to_string()]);
+
+        let items: Vec<UBXConfigItem> = driver
+            .config_items(&messages)
+            .into_iter()
+            .map(|(_, item)| item)
+            .collect();
+
+        assert_eq!(messages.len(), items.len());
+        assert!(items.contains(&UBXConfigItem::new(INFMSG_NMEA_I2C + 1, INFMSG_TXT)));
+    }
+
+    #[test]
+    fn test_valget_polls() {
+        let config = UBloxConfig {
+            layers: Some(vec![UBXLayer::Flash]),
+            ..UBloxConfig::default()
+        };
+
+        let driver = UBloxNMEA::new(config);
+        let items = vec![(
+            "dynamic model".to_string(),
+            UBXConfigItem::new(NAVSPG_DYNMODEL, 2),
+        )];
+
+        let polls = driver.valget_polls(&items);
+
+        assert_eq!(1, polls.len());
+
+        let (layer, frame) = &polls[0];
+
+        assert_eq!(UBXLayer::Flash, *layer);
+        assert_eq!(CFG_VALGET, (frame.class, frame.id));
+        assert_eq!(
+            vec![0x00, 0x02, 0x00, 0x00, 0x21, 0x00, 0x11, 0x20],
+            frame.payload
+        );
+
+        // the default layer is RAM
+        let polls = UBloxNMEA::default().valget_polls(&items);
+
+        assert_eq!(1, polls.len());
+        assert_eq!(UBXLayer::RAM, polls[0].0);
+        assert_eq!(0x00, polls[0].1.payload[1]);
+    }
 }
@@ -1,13 +1,23 @@
 // For UBlox ZED-F9P devices using NMEA
 
 use crate::{
+    configuration::UBloxConfig,
     gps::add_message,
     nmea::{
-        device::SerialCodec, parser::Result as ParseResult, parser_util::*, MessageSetting, NMEA,
+        device::SerialCodec, parser::Result as ParseResult, parser_util::*, ser_util,
+        MessageSetting, NMEA,
+    },
+    ubx::{
+        cfg::{
+            msgout_key, valdel, valget, valset, UBXConfigItem, UBXLayer, CFG_VALDEL, CFG_VALGET,
+            CFG_VALSET, CONSTELLATIONS, INFMSG_NMEA_I2C, INFMSG_TXT, MAX_ITEMS, NAVSPG_DYNMODEL,
+            RATE_MEAS, RATE_NAV,
+        },
+        UBXFrame, UBXMessage,
     },
 };
 use chrono::naive::{NaiveDate, NaiveTime};
-use futures_util::sink::SinkExt;
+use futures_util::{sink::SinkExt, StreamExt};
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_while_m_n},
@@ -19,31 +29,223 @@ use nom::{
 };
 use serde::{
     ser::{SerializeStruct, Serializer},
-    Serialize,
+    Deserialize, Serialize,
 };
-use tracing::{error, info, trace};
+use std::{collections::HashMap, time::Duration};
+use tokio::time::{timeout_at, Instant};
+use tracing::{debug, error, info, trace, warn};
 
 pub const OUTPUT_MESSAGES: [&str; 15] = [
     "DTM", "GBS", "GGA", "GLL", "GNS", "GRS", "GSA", "GST", "GSV", "RLM", "RMC", "TXT", "VLW",
     "VTG", "ZDA",
 ];
 
+/// THS is only output by dual-antenna receivers like the ZED-F9P moving base rover or the ZED-F9H,
+/// so it is only enabled when listed
+pub const HEADING_OUTPUT_MESSAGES: [&str; 1] = ["THS"];
+
+pub const UBX_OUTPUT_MESSAGES: [&str; 7] = [
+    "UBX-NAV-PVT",
+    "UBX-NAV-RELPOSNED",
+    "UBX-NAV-SAT",
+    "UBX-NAV-STATUS",
+    "UBX-NAV-TIMELS",
+    "UBX-NAV-TIMEUTC",
+    "UBX-TIM-TP",
+];
+
+/// How long to wait for the receiver to respond to a configuration message
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(1);
+
 #[derive(Clone, Default, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct UBloxNMEA {}
+pub struct UBloxNMEA {
+    config: UBloxConfig,
+}
 
 impl UBloxNMEA {
+    pub fn new(config: UBloxConfig) -> Self {
+        UBloxNMEA { config }
+    }
+
     pub async fn configure(&self, serial: &mut SerialCodec, messages: &Vec<MessageSetting>) {
         trace!("configuring u-blox NMEA {:?}", serial);
 
+        let items = self.config_items(messages);
+
+        if !self.config.clear_layers().is_empty() {
+            for chunk in items.chunks(MAX_ITEMS) {
+                let keys: Vec<u32> = chunk.iter().map(|(_, item)| item.key).collect();
+
+                self.valdel(serial, &keys).await;
+            }
+        }
+
+        let mut set = vec![];
+
+        for chunk in items.chunks(MAX_ITEMS) {
+            let chunk: Vec<UBXConfigItem> = chunk.iter().map(|(_, item)| item.clone()).collect();
+
+            if !self.valset(serial, &chunk).await {
+                break;
+            }
+
+            set.extend(chunk);
+        }
+
+        if set.is_empty() && !items.is_empty() {
+            info!("CFG-VALSET not accepted, setting message rates with PUBX,40");
+
+            configure_pubx(serial, messages).await;
+
+            return;
+        }
+
+        for (name, item) in &items {
+            if set.contains(item) {
+                info!("set {} to {}", name, item.value);
+            }
+        }
+
+        self.verify(serial, &items).await;
+    }
+
+    /// Configuration items for `messages` and the `[gps.ublox]` settings, labeled for logging
+    pub fn config_items(&self, messages: &Vec<MessageSetting>) -> Vec<(String, UBXConfigItem)> {
+        let mut items = vec![];
+        let port = self.config.port() as u32;
+
         for message in messages {
-            let rate = rate_for(message.id.clone(), message.enabled);
-
-            match serial.send(rate).await {
-                Ok(_) => info!("setting {} to {}", message.id, message.enabled),
-                Err(e) => error!(
-                    "unable to set {} to {}: {:?}",
-                    message.id, message.enabled, e
-                ),
+            // TXT output is set by information message type, not rate
+            if message.id == "TXT" {
+                let enabled = if message.enabled { INFMSG_TXT } else { 0 };
+
+                items.push((
+                    message.id.clone(),
+                    UBXConfigItem::new(INFMSG_NMEA_I2C + port, enabled),
+                ));
+
+                continue;
+            }
+
+            match msgout_key(&message.id) {
+                Some(key) => {
+                    let rate = if message.enabled { 1 } else { 0 };
+
+                    items.push((message.id.clone(), UBXConfigItem::new(key + port, rate)));
+                }
+                None => warn!("unable to set output rate for {}", message.id),
+            }
+        }
+
+        if let Some(rate) = self.config.measurement_rate {
+            items.push((
+                "measurement rate".to_string(),
+                UBXConfigItem::new(RATE_MEAS, rate.into()),
+            ));
+        }
+
+        if let Some(rate) = self.config.navigation_rate {
+            items.push((
+                "navigation rate".to_string(),
+                UBXConfigItem::new(RATE_NAV, rate.into()),
+            ));
+        }
+
+        if let Some(model) = &self.config.dynamic_model {
+            items.push((
+                "dynamic model".to_string(),
+                UBXConfigItem::new(NAVSPG_DYNMODEL, model.value()),
+            ));
+        }
+
+        if let Some(enabled) = &self.config.constellations {
+            for constellation in &CONSTELLATIONS {
+                let enable = enabled.contains(constellation);
+
+                items.push((
+                    format!("{:?}", constellation),
+                    UBXConfigItem::new(constellation.key(), enable.into()),
+                ));
+            }
+        }
+
+        items
+    }
+
+    // Returns true when the receiver acknowledges the VALSET
+    async fn valset(&self, serial: &mut SerialCodec, items: &[UBXConfigItem]) -> bool {
+        let frame = valset(&self.config.layers(), items);
+
+        acknowledged(serial, frame, "CFG-VALSET", CFG_VALSET).await
+    }
+
+    // Deletes keys from the clear layers so stored values don't override the new settings
+    async fn valdel(&self, serial: &mut SerialCodec, keys: &[u32]) {
+        let frame = valdel(&self.config.clear_layers(), keys);
+
+        acknowledged(serial, frame, "CFG-VALDEL", CFG_VALDEL).await;
+    }
+
+    /// CFG-VALGET polls reading back `items` from each configured layer
+    pub fn valget_polls(&self, items: &[(String, UBXConfigItem)]) -> Vec<(UBXLayer, UBXFrame)> {
+        let mut polls = vec![];
+
+        for layer in self.config.layers() {
+            for chunk in items.chunks(MAX_ITEMS) {
+                let keys: Vec<u32> = chunk.iter().map(|(_, item)| item.key).collect();
+
+                polls.push((layer.clone(), valget(&layer, &keys)));
+            }
+        }
+
+        polls
+    }
+
+    // Reads back each configured layer and logs any value that didn't take effect
+    async fn verify(&self, serial: &mut SerialCodec, items: &[(String, UBXConfigItem)]) {
+        let mut values = HashMap::new();
+
+        for (layer, poll) in self.valget_polls(items) {
+            if let Err(e) = serial.send(poll).await {
+                error!("unable to send CFG-VALGET: {:?}", e);
+                return;
+            }
+
+            let response = response(serial, |message| match message {
+                UBXMessage::CfgValGet(_) => true,
+                UBXMessage::Nak(a) => (a.class, a.id) == CFG_VALGET,
+                _ => false,
+            })
+            .await;
+
+            match response {
+                Some(UBXMessage::CfgValGet(v)) => {
+                    for item in v.items {
+                        values.insert((layer.clone(), item.key), item.value);
+                    }
+                }
+                _ => {
+                    error!(
+                        "unable to read back {:?} configuration with CFG-VALGET",
+                        layer
+                    );
+                    return;
+                }
+            }
+        }
+
+        for layer in self.config.layers() {
+            for (name, item) in items {
+                match values.get(&(layer.clone(), item.key)) {
+                    Some(value) if *value == item.value => {
+                        debug!("verified {} is {} in {:?}", name, value, layer)
+                    }
+                    Some(value) => error!(
+                        "{} is {} in {:?}, expected {}",
+                        name, value, layer, item.value
+                    ),
+                    None => error!("{} was not returned by CFG-VALGET for {:?}", name, layer),
+                }
             }
         }
     }
@@ -63,6 +265,15 @@ impl UBloxNMEA {
             }
         }
 
+        for message in HEADING_OUTPUT_MESSAGES
+            .iter()
+            .chain(UBX_OUTPUT_MESSAGES.iter())
+        {
+            let enabled = messages.contains(&message.to_string());
+
+            add_message(&mut message_settings, message, enabled);
+        }
+
         message_settings
     }
 
@@ -88,6 +299,19 @@ pub enum UBXData {
     Time(UBXTime),
 }
 
+impl Serialize for UBXData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            UBXData::Position(p) => p.serialize(serializer),
+            UBXData::Satellites(s) => s.serialize(serializer),
+            UBXData::Time(t) => t.serialize(serializer),
+        }
+    }
+}
+
 #[derive(Clone, Eq, Debug, PartialEq, Serialize)]
 pub struct UBXRate {
     pub message: String,
@@ -99,6 +323,75 @@ pub struct UBXRate {
     pub reserved: u32,
 }
 
+async fn configure_pubx(serial: &mut SerialCodec, messages: &Vec<MessageSetting>) {
+    for message in messages {
+        if message.id.starts_with("UBX-") {
+            continue;
+        }
+
+        let rate = rate_for(message.id.clone(), message.enabled);
+
+        match serial.send(rate).await {
+            Ok(_) => info!("setting {} to {}", message.id, message.enabled),
+            Err(e) => error!(
+                "unable to set {} to {}: {:?}",
+                message.id, message.enabled, e
+            ),
+        }
+    }
+}
+
+// Sends a configuration message and returns true when the receiver acknowledges it
+async fn acknowledged(
+    serial: &mut SerialCodec,
+    frame: UBXFrame,
+    name: &str,
+    class_id: (u8, u8),
+) -> bool {
+    if let Err(e) = serial.send(frame).await {
+        error!("unable to send {}: {:?}", name, e);
+        return false;
+    }
+
+    let response = response(serial, |message| match message {
+        UBXMessage::Ack(a) | UBXMessage::Nak(a) => (a.class, a.id) == class_id,
+        _ => false,
+    })
+    .await;
+
+    match response {
+        Some(UBXMessage::Ack(_)) => true,
+        Some(_) => {
+            error!("{} rejected", name);
+            false
+        }
+        None => {
+            error!("{} not acknowledged", name);
+            false
+        }
+    }
+}
+
+// Waits for the first UBX message from the receiver that `matches`
+async fn response<F>(serial: &mut SerialCodec, matches: F) -> Option<UBXMessage>
+where
+    F: Fn(&UBXMessage) -> bool,
+{
+    let deadline = Instant::now() + RESPONSE_TIMEOUT;
+
+    loop {
+        match timeout_at(deadline, serial.next()).await {
+            Ok(Some(Ok(NMEA::UBX(message)))) if matches(&message) => return Some(message),
+            Ok(Some(Ok(_))) => continue,
+            Ok(Some(Err(e))) => {
+                error!("error waiting for configuration response: {:?}", e);
+                return None;
+            }
+            Ok(None) | Err(_) => return None,
+        }
+    }
+}
+
 fn rate_for(msg_id: String, enabled: bool) -> UBXRate {
     let rus1 = if enabled { 1 } else { 0 };
 
@@ -113,10 +406,13 @@ fn rate_for(msg_id: String, enabled: bool) -> UBXRate {
     }
 }
 
-#[derive(Clone, Eq, Debug, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[serde(rename_all = "lowercase")]
 pub enum UBXPort {
     I2C = 0,
+    #[serde(alias = "uart1")]
     USART1 = 1,
+    #[serde(alias = "uart2")]
     USART2 = 2,
     USB = 3,
     SPI = 4,
@@ -187,6 +483,25 @@ pub enum UBXNavigationStatus {
     Unknown(String),
 }
 
+impl Serialize for UBXNavigationStatus {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(match self {
+            UBXNavigationStatus::NoFix => "NF",
+            UBXNavigationStatus::DeadRecokning => "DR",
+            UBXNavigationStatus::Standalone2D => "G2",
+            UBXNavigationStatus::Standalone3D => "G3",
+            UBXNavigationStatus::Differential2D => "D2",
+            UBXNavigationStatus::Differential3D => "D3",
+            UBXNavigationStatus::Combined => "RK",
+            UBXNavigationStatus::TimeOnly => "TT",
+            UBXNavigationStatus::Unknown(s) => s,
+        })
+    }
+}
+
 pub(crate) fn ubx_nav_stat<'a>(input: &'a str) -> ParseResult<&'a str, UBXNavigationStatus> {
     context(
         "UBX navigation status",
@@ -224,6 +539,32 @@ pub struct UBXPosition {
     pub dead_reckoning: bool,
 }
 
+impl Serialize for UBXPosition {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("UBXPosition", 16)?;
+        state.serialize_field("time", &ser_util::time(&self.time))?;
+        state.serialize_field("lat_lon", &ser_util::latlon(&self.lat_lon))?;
+        state.serialize_field("alt_ref", &self.alt_ref)?;
+        state.serialize_field("nav_status", &self.nav_status)?;
+        state.serialize_field("horizontal_accuracy", &self.horizontal_accuracy)?;
+        state.serialize_field("vertical_accuracy", &self.vertical_accuracy)?;
+        state.serialize_field("speed_over_ground", &self.speed_over_ground)?;
+        state.serialize_field("course_over_ground", &self.course_over_ground)?;
+        state.serialize_field("vertical_velocity", &self.vertical_velocity)?;
+        state.serialize_field("diff_age", &self.diff_age)?;
+        state.serialize_field("hdop", &self.hdop)?;
+        state.serialize_field("vdop", &self.vdop)?;
+        state.serialize_field("tdop", &self.tdop)?;
+        state.serialize_field("num_satellites", &self.num_satellites)?;
+        state.serialize_field("reserved", &self.reserved)?;
+        state.serialize_field("dead_reckoning", &self.dead_reckoning)?;
+        state.end()
+    }
+}
+
 pub(crate) fn ubx_00<'a>(input: &'a str) -> ParseResult<&'a str, UBXPosition> {
     parse_message(
         "UBX 00",
@@ -296,6 +637,19 @@ pub enum UBXSatelliteStatus {
     EphemerisAvailable,
 }
 
+impl Serialize for UBXSatelliteStatus {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_char(match self {
+            UBXSatelliteStatus::NotUsed => '-',
+            UBXSatelliteStatus::Used => 'U',
+            UBXSatelliteStatus::EphemerisAvailable => 'e',
+        })
+    }
+}
+
 pub(crate) fn ubx_sat_status<'a>(input: &'a str) -> ParseResult<&'a str, UBXSatelliteStatus> {
     map(one_of("-Ue"), |c| match c {
         '-' => UBXSatelliteStatus::NotUsed,
@@ -344,6 +698,27 @@ pub struct UBXSatellites {
     pub satellites: Vec<UBXSatellite>,
 }
 
+impl Serialize for UBXSatellites {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("UBXSatellites", 1 + self.satellites.len())?;
+        state.serialize_field("num_satellites", &self.satellites.len())?;
+
+        for satellite in &self.satellites {
+            state.serialize_field("id", &ser_util::padded(Some(satellite.id), 2))?;
+            state.serialize_field("status", &satellite.status)?;
+            state.serialize_field("azimuth", &ser_util::padded(satellite.azimuth, 3))?;
+            state.serialize_field("elevation", &ser_util::padded(satellite.elevation, 2))?;
+            state.serialize_field("cno", &ser_util::padded(Some(satellite.cno), 2))?;
+            state.serialize_field("lock_time", &ser_util::padded(Some(satellite.lock_time), 3))?;
+        }
+
+        state.end()
+    }
+}
+
 pub(crate) fn ubx_03<'a>(input: &'a str) -> ParseResult<&'a str, UBXSatellites> {
     parse_message(
         "UBX 03",
@@ -363,6 +738,7 @@ pub struct UBXTimePoll {}
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct UBXTime {
+    pub received: Option<Duration>,
     pub time: NaiveTime,
     pub date: NaiveDate,
     pub time_of_week: f32,
@@ -374,6 +750,32 @@ pub struct UBXTime {
     pub time_pulse_granularity: u32,
 }
 
+impl Serialize for UBXTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let leap_seconds = if self.leap_second_default {
+            format!("{}D", self.leap_seconds)
+        } else {
+            self.leap_seconds.to_string()
+        };
+
+        let mut state = serializer.serialize_struct("UBXTime", 10)?;
+        state.serialize_field("time", &ser_util::time(&self.time))?;
+        state.serialize_field("date", &ser_util::date(&self.date))?;
+        state.serialize_field("time_of_week", &self.time_of_week)?;
+        state.serialize_field("week", &self.week)?;
+        state.serialize_field("leap_seconds", &leap_seconds)?;
+        state.serialize_field("clock_bias", &self.clock_bias)?;
+        state.serialize_field("clock_drift", &self.clock_drift)?;
+        state.serialize_field("time_pulse_granularity", &self.time_pulse_granularity)?;
+        // the sentence ends with an empty field
+        state.serialize_field("reserved", "")?;
+        state.end()
+    }
+}
+
 pub(crate) fn ubx_04<'a>(input: &'a str) -> ParseResult<&'a str, UBXTime> {
     parse_message(
         "UBX 04",
@@ -402,6 +804,7 @@ pub(crate) fn ubx_04<'a>(input: &'a str) -> ParseResult<&'a str, UBXTime> {
             clock_drift,
             time_pulse_granularity,
         )| UBXTime {
+            received: None,
             time,
             date,
             time_of_week,
@@ -0,0 +1,240 @@
+use crate::ais::{AISDimensions, AISMessage, AISReport, AISStaticDataReport};
+use serde::Serialize;
+
+/// A decoded AIS message with scaled values: degrees, knots, and meters
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename = "AIS", tag = "class")]
+pub struct Ais {
+    pub device: String,
+    #[serde(rename = "type")]
+    pub message_type: u32,
+    pub repeat: u32,
+    pub mmsi: u32,
+    pub scaled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u32>,
+    /// Degrees per minute
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub turn: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accuracy: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lon: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lat: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub course: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub second: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maneuver: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raim: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub radio: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epfd: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ais_version: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imo: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callsign: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shipname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shiptype: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_bow: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_stern: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_port: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_starboard: Option<u32>,
+    /// Estimated time of arrival as `MM-DDTHH:MMZ`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eta: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draught: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dte: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dsc: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub band: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub msg22: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aid_type: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub off_position: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virtual_aid: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partno: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vendorid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub serial: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mothership_mmsi: Option<u32>,
+}
+
+impl Ais {
+    pub fn new(device: &str, message: &AISMessage) -> Self {
+        let mut ais = Ais {
+            device: device.to_string(),
+            message_type: message.message_type,
+            repeat: message.repeat,
+            mmsi: message.mmsi,
+            scaled: true,
+            ..Default::default()
+        };
+
+        match &message.report {
+            AISReport::Position(p) => {
+                ais.status = Some(p.status);
+                ais.turn = p.rate_of_turn();
+                ais.speed = p.speed;
+                ais.accuracy = Some(p.accuracy);
+                ais.lon = p.longitude;
+                ais.lat = p.latitude;
+                ais.course = p.course;
+                ais.heading = p.heading;
+                ais.second = Some(p.second);
+                ais.maneuver = Some(p.maneuver);
+                ais.raim = Some(p.raim);
+                ais.radio = Some(p.radio);
+            }
+            AISReport::BaseStation(b) => {
+                ais.timestamp = b
+                    .timestamp
+                    .map(|t| t.format("%Y-%m-%dT%H:%M:%SZ").to_string());
+                ais.accuracy = Some(b.accuracy);
+                ais.lon = b.longitude;
+                ais.lat = b.latitude;
+                ais.epfd = Some(b.epfd);
+                ais.raim = Some(b.raim);
+                ais.radio = Some(b.radio);
+            }
+            AISReport::StaticAndVoyage(s) => {
+                ais.ais_version = Some(s.ais_version);
+                ais.imo = Some(s.imo);
+                ais.callsign = Some(s.callsign.clone());
+                ais.shipname = Some(s.shipname.clone());
+                ais.shiptype = Some(s.shiptype);
+                ais.set_dimensions(&s.dimensions);
+                ais.epfd = Some(s.epfd);
+                ais.eta = Some(format!(
+                    "{:02}-{:02}T{:02}:{:02}Z",
+                    s.eta_month, s.eta_day, s.eta_hour, s.eta_minute
+                ));
+                ais.draught = Some(s.draught);
+                ais.destination = Some(s.destination.clone());
+                ais.dte = Some(s.dte);
+            }
+            AISReport::ClassBPosition(b) => {
+                ais.speed = b.speed;
+                ais.accuracy = Some(b.accuracy);
+                ais.lon = b.longitude;
+                ais.lat = b.latitude;
+                ais.course = b.course;
+                ais.heading = b.heading;
+                ais.second = Some(b.second);
+                ais.cs = Some(b.cs);
+                ais.display = Some(b.display);
+                ais.dsc = Some(b.dsc);
+                ais.band = Some(b.band);
+                ais.msg22 = Some(b.msg22);
+                ais.assigned = Some(b.assigned);
+                ais.raim = Some(b.raim);
+                ais.radio = Some(b.radio);
+            }
+            AISReport::ExtendedClassBPosition(b) => {
+                ais.speed = b.speed;
+                ais.accuracy = Some(b.accuracy);
+                ais.lon = b.longitude;
+                ais.lat = b.latitude;
+                ais.course = b.course;
+                ais.heading = b.heading;
+                ais.second = Some(b.second);
+                ais.shipname = Some(b.shipname.clone());
+                ais.shiptype = Some(b.shiptype);
+                ais.set_dimensions(&b.dimensions);
+                ais.epfd = Some(b.epfd);
+                ais.raim = Some(b.raim);
+                ais.dte = Some(b.dte);
+                ais.assigned = Some(b.assigned);
+            }
+            AISReport::AidToNavigation(a) => {
+                ais.aid_type = Some(a.aid_type);
+                ais.name = Some(a.name.clone());
+                ais.accuracy = Some(a.accuracy);
+                ais.lon = a.longitude;
+                ais.lat = a.latitude;
+                ais.set_dimensions(&a.dimensions);
+                ais.epfd = Some(a.epfd);
+                ais.second = Some(a.second);
+                ais.off_position = Some(a.off_position);
+                ais.raim = Some(a.raim);
+                ais.virtual_aid = Some(a.virtual_aid);
+                ais.assigned = Some(a.assigned);
+            }
+            AISReport::StaticData(AISStaticDataReport::PartA { shipname }) => {
+                ais.partno = Some(0);
+                ais.shipname = Some(shipname.clone());
+            }
+            AISReport::StaticData(AISStaticDataReport::PartB {
+                shiptype,
+                vendor_id,
+                model,
+                serial,
+                callsign,
+                dimensions,
+                mothership_mmsi,
+            }) => {
+                ais.partno = Some(1);
+                ais.shiptype = Some(*shiptype);
+                ais.vendorid = Some(vendor_id.clone());
+                ais.model = Some(*model);
+                ais.serial = Some(*serial);
+                ais.callsign = Some(callsign.clone());
+
+                if let Some(dimensions) = dimensions {
+                    ais.set_dimensions(dimensions);
+                }
+
+                ais.mothership_mmsi = *mothership_mmsi;
+            }
+            AISReport::Unsupported => (),
+        }
+
+        ais
+    }
+
+    fn set_dimensions(&mut self, dimensions: &AISDimensions) {
+        self.to_bow = Some(dimensions.to_bow);
+        self.to_stern = Some(dimensions.to_stern);
+        self.to_port = Some(dimensions.to_port);
+        self.to_starboard = Some(dimensions.to_starboard);
+    }
+}
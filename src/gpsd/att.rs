@@ -0,0 +1,17 @@
+use serde::Serialize;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename = "ATT", tag = "class")]
+pub struct Att {
+    pub device: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pitch: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rot: Option<f32>,
+    #[serde(rename = "baseL", skip_serializing_if = "Option::is_none")]
+    pub baseline_length: Option<f32>,
+}
@@ -17,6 +17,7 @@ use std::io;
 use std::net::SocketAddr;
 use std::ops::Deref;
 use std::sync::Arc;
+use std::time::SystemTime;
 use tokio::net::tcp::OwnedReadHalf;
 use tokio::net::tcp::OwnedWriteHalf;
 use tokio::net::TcpStream;
@@ -26,7 +27,7 @@ use tokio::sync::watch;
 use tokio::sync::Mutex;
 use tokio_util::codec::FramedRead;
 use tokio_util::codec::FramedWrite;
-use tracing::{debug, debug_span, error, info, Instrument};
+use tracing::{debug, debug_span, error, info, warn, Instrument};
 
 pub struct Client {
     server: Arc<Mutex<Server>>,
@@ -92,12 +93,7 @@ impl Client {
                     ..Device::default()
                 }),
                 Command::Error(e) => Response::Error(ErrorMessage { message: e }),
-                Command::Poll => Response::Poll(Poll {
-                    time: 0.0,
-                    active: 0,
-                    tpv: vec![],
-                    sky: vec![],
-                }),
+                Command::Poll => self.command_poll().await,
                 Command::Version => Response::Version(Version {
                     release: "release-3.10".to_string(),
                     rev: "3.10".to_string(),
@@ -125,6 +121,33 @@ impl Client {
         Response::Devices(devices)
     }
 
+    async fn command_poll(&self) -> Response {
+        let guard = self.server.lock().await;
+        let gps_devices = guard.devices.gps_devices();
+
+        let tpv = gps_devices
+            .iter()
+            .filter_map(|gps| gps.tpv.borrow().clone())
+            .collect();
+
+        let sky = gps_devices
+            .iter()
+            .filter_map(|gps| gps.sky.borrow().clone())
+            .collect();
+
+        let time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        Response::Poll(Poll {
+            time,
+            active: gps_devices.len() as u32,
+            tpv,
+            sky,
+        })
+    }
+
     async fn command_watch(&self, updates: Option<Watch>) -> Response {
         let original;
         let updated;
@@ -206,6 +229,10 @@ async fn relay(tx: mpsc::Sender<Response>, mut rx: broadcast::Receiver<Response>
 
         let value = match message {
             Ok(v) => v,
+            Err(broadcast::error::RecvError::Lagged(n)) => {
+                warn!("client fell behind, skipped {} messages", n);
+                continue;
+            }
             Err(e) => {
                 error!("error receiving message to relay: {:?}", e);
                 break;
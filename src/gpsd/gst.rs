@@ -0,0 +1,28 @@
+use serde::Serialize;
+
+/// Pseudorange noise statistics.  Deviations are one sigma in meters.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename = "GST", tag = "class")]
+pub struct Gst {
+    pub device: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+    /// RMS of the pseudorange residuals
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rms: Option<f64>,
+    /// Semi-major axis of the error ellipse
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub major: Option<f64>,
+    /// Semi-minor axis of the error ellipse
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minor: Option<f64>,
+    /// Orientation of the semi-major axis in degrees from true north
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orient: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lat: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lon: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alt: Option<f64>,
+}
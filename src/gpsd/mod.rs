@@ -1,24 +1,33 @@
+mod ais;
+mod att;
 mod client;
 mod codec;
 mod device;
 mod devices;
+mod gst;
 mod parser;
 mod pps;
 mod response;
 mod server;
+mod sky;
 mod toff;
 mod tpv;
 mod watch;
 
+pub use ais::Ais;
+pub use att::Att;
 pub use client::Client;
 pub use device::Device;
 pub use devices::Devices;
+pub use gst::Gst;
 pub use response::ErrorMessage;
 pub use response::Poll;
 pub use response::Response;
-pub use response::Sky;
 pub use response::Version;
 pub use server::Server;
+pub use sky::satellite_system;
+pub use sky::Sky;
+pub use sky::SkySatellite;
 pub use toff::Toff;
 pub use tpv::Tpv;
 pub use watch::Watch;
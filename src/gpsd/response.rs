@@ -1,5 +1,9 @@
+use crate::gpsd::Ais;
+use crate::gpsd::Att;
 use crate::gpsd::Device;
 use crate::gpsd::Devices;
+use crate::gpsd::Gst;
+use crate::gpsd::Sky;
 use crate::gpsd::Toff;
 use crate::gpsd::Tpv;
 use crate::gpsd::Watch;
@@ -8,13 +12,17 @@ use serde::Serialize;
 
 #[derive(Clone, Debug, Serialize)]
 pub enum Response {
+    Ais(Box<Ais>),
+    Att(Att),
     Device(Device),
     Devices(Devices),
     Error(ErrorMessage),
+    Gst(Gst),
     Poll(Poll),
     Toff(Toff),
-    Tpv(Tpv),
+    Tpv(Box<Tpv>),
     PPS(PPS),
+    Sky(Box<Sky>),
     Version(Version),
     Watch(Watch),
 }
@@ -60,10 +68,6 @@ pub struct PPS {
     pub precision: i32,
 }
 
-#[derive(Clone, Debug, Serialize)]
-#[serde(rename = "SKY", tag = "class")]
-pub struct Sky {}
-
 #[derive(Clone, Debug, Serialize)]
 #[serde(rename = "VERSION", tag = "class")]
 pub struct Version {
@@ -0,0 +1,127 @@
+use crate::nmea::System;
+use serde::Serialize;
+
+/// A satellite view report with the fields of gpsd 3.2x.  Fields that aren't known are omitted.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename = "SKY", tag = "class")]
+pub struct Sky {
+    pub device: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gdop: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hdop: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pdop: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tdop: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vdop: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xdop: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ydop: Option<f64>,
+    /// Number of satellites in `satellites`
+    #[serde(rename = "nSat")]
+    pub n_sat: u32,
+    /// Number of satellites used in the navigation solution
+    #[serde(rename = "uSat")]
+    pub u_sat: u32,
+    pub satellites: Vec<SkySatellite>,
+}
+
+impl Sky {
+    pub fn new(device: &str, satellites: Vec<SkySatellite>) -> Self {
+        let n_sat = satellites.len() as u32;
+        let u_sat = satellites.iter().filter(|s| s.used).count() as u32;
+
+        Sky {
+            device: device.to_string(),
+            n_sat,
+            u_sat,
+            satellites,
+            ..Default::default()
+        }
+    }
+}
+
+/// One satellite of a SKY report
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct SkySatellite {
+    /// gpsd's satellite ID, unique across systems
+    #[serde(rename = "PRN")]
+    pub prn: u32,
+    /// Azimuth in degrees from true north
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub az: Option<f64>,
+    /// Elevation in degrees
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub el: Option<f64>,
+    /// Signal to noise ratio in dBHz
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ss: Option<f64>,
+    pub used: bool,
+    /// u-blox GNSS ID: 0 GPS, 1 SBAS, 2 Galileo, 3 BeiDou, 5 QZSS, 6 GLONASS, 7 NavIC
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gnssid: Option<u32>,
+    /// Satellite ID within the GNSS
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub svid: Option<u32>,
+}
+
+impl SkySatellite {
+    /// Creates a satellite from an NMEA satellite ID.  The system is guessed from the ID when it is
+    /// not known.
+    pub fn new(system: Option<&System>, id: u32) -> Self {
+        let (prn, gnssid, svid) = match satellite_system(system, id) {
+            Some(System::GPS) => match id {
+                33..=64 => (id + 87, Some(1), Some(id + 87)),
+                120..=158 => (id, Some(1), Some(id)),
+                _ => (id, Some(0), Some(id)),
+            },
+            Some(System::GLONASS) => match id {
+                65..=96 => (id, Some(6), Some(id - 64)),
+                _ => (id + 64, Some(6), Some(id)),
+            },
+            Some(System::Galileo) => match id {
+                301..=336 => (id, Some(2), Some(id - 300)),
+                _ => (id + 300, Some(2), Some(id)),
+            },
+            Some(System::BeiDuo) => match id {
+                201..=263 => (id + 200, Some(3), Some(id - 200)),
+                401..=463 => (id, Some(3), Some(id - 400)),
+                _ => (id + 400, Some(3), Some(id)),
+            },
+            Some(System::QZSS) => match id {
+                193..=200 => (id, Some(5), Some(id - 192)),
+                _ => (id + 192, Some(5), Some(id)),
+            },
+            Some(System::NavIC) => (id + 800, Some(7), Some(id)),
+            Some(System::Unknown(_)) | None => (id, None, None),
+        };
+
+        SkySatellite {
+            prn,
+            gnssid,
+            svid,
+            ..Default::default()
+        }
+    }
+}
+
+/// The system of an NMEA satellite ID, guessed from the ID when the system isn't known
+pub fn satellite_system(system: Option<&System>, id: u32) -> Option<System> {
+    if let Some(system) = system {
+        return Some(system.clone());
+    }
+
+    match id {
+        1..=64 | 120..=158 => Some(System::GPS),
+        65..=96 => Some(System::GLONASS),
+        193..=200 => Some(System::QZSS),
+        201..=263 | 401..=463 => Some(System::BeiDuo),
+        301..=336 => Some(System::Galileo),
+        _ => None,
+    }
+}
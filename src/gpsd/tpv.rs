@@ -1,9 +1,133 @@
 use serde::Serialize;
 
-#[derive(Clone, Debug, Serialize)]
+/// A time-position-velocity report with the fields of gpsd 3.2x.  Fields that aren't known are
+/// omitted.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
 #[serde(rename = "TPV", tag = "class")]
 pub struct Tpv {
     pub device: String,
-    pub time: String,
+    /// 0 unknown, 1 no fix, 2 2D fix, 3 3D fix
     pub mode: u32,
+    /// 0 unknown, 1 normal, 2 DGPS, 3 RTK fixed, 4 RTK floating, 5 dead reckoning, 6 GNSS and
+    /// dead reckoning, 7 time only, 8 simulated, 9 P(Y)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+    /// Estimated time error in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ept: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lat: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lon: Option<f64>,
+    /// Altitude above the WGS 84 ellipsoid in meters
+    #[serde(rename = "altHAE", skip_serializing_if = "Option::is_none")]
+    pub alt_hae: Option<f64>,
+    /// Altitude above mean sea level in meters
+    #[serde(rename = "altMSL", skip_serializing_if = "Option::is_none")]
+    pub alt_msl: Option<f64>,
+    /// Deprecated by gpsd, the same as `alt_msl`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alt: Option<f64>,
+    /// Longitude error estimate in meters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epx: Option<f64>,
+    /// Latitude error estimate in meters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epy: Option<f64>,
+    /// Vertical error estimate in meters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epv: Option<f64>,
+    /// Course over ground in degrees from true north
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track: Option<f64>,
+    /// Course over ground in degrees from magnetic north
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub magtrack: Option<f64>,
+    /// Magnetic variation in degrees, positive is east
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub magvar: Option<f64>,
+    /// Speed over ground in meters per second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f64>,
+    /// Climb (positive) or sink rate in meters per second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub climb: Option<f64>,
+    /// Direction error estimate in degrees
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epd: Option<f64>,
+    /// Speed error estimate in meters per second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eps: Option<f64>,
+    /// Climb error estimate in meters per second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epc: Option<f64>,
+    /// Horizontal position error estimate in meters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eph: Option<f64>,
+    /// Spherical position error estimate in meters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sep: Option<f64>,
+    /// Height of the geoid above the WGS 84 ellipsoid in meters
+    #[serde(rename = "geoidSep", skip_serializing_if = "Option::is_none")]
+    pub geoid_sep: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub datum: Option<String>,
+    /// Depth in meters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth: Option<f64>,
+    /// Age of the differential corrections in seconds
+    #[serde(rename = "dgpsAge", skip_serializing_if = "Option::is_none")]
+    pub dgps_age: Option<f64>,
+    /// Differential reference station ID
+    #[serde(rename = "dgpsSta", skip_serializing_if = "Option::is_none")]
+    pub dgps_sta: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub leapseconds: Option<i32>,
+    /// ECEF position and velocity in meters and meters per second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ecefx: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ecefy: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ecefz: Option<f64>,
+    #[serde(rename = "ecefpAcc", skip_serializing_if = "Option::is_none")]
+    pub ecefp_acc: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ecefvx: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ecefvy: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ecefvz: Option<f64>,
+    #[serde(rename = "ecefvAcc", skip_serializing_if = "Option::is_none")]
+    pub ecefv_acc: Option<f64>,
+    /// North, east, and down velocity in meters per second
+    #[serde(rename = "velN", skip_serializing_if = "Option::is_none")]
+    pub vel_n: Option<f64>,
+    #[serde(rename = "velE", skip_serializing_if = "Option::is_none")]
+    pub vel_e: Option<f64>,
+    #[serde(rename = "velD", skip_serializing_if = "Option::is_none")]
+    pub vel_d: Option<f64>,
+    /// North, east, and down position relative to a base station in meters
+    #[serde(rename = "relN", skip_serializing_if = "Option::is_none")]
+    pub rel_n: Option<f64>,
+    #[serde(rename = "relE", skip_serializing_if = "Option::is_none")]
+    pub rel_e: Option<f64>,
+    #[serde(rename = "relD", skip_serializing_if = "Option::is_none")]
+    pub rel_d: Option<f64>,
+    /// Wind angle and speed, magnetic, relative, and true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wanglem: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wangler: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wanglet: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wspeedr: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wspeedt: Option<f64>,
+    /// Water temperature in degrees Celsius
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wtemp: Option<f64>,
 }
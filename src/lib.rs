@@ -1,3 +1,4 @@
+pub mod ais;
 pub mod configuration;
 pub mod device;
 pub mod devices;
@@ -7,8 +8,10 @@ pub mod nmea;
 pub mod pps;
 pub mod precision;
 pub mod prometheus;
+pub mod rtcm;
 pub mod shm;
 pub mod timestamp;
+pub mod ubx;
 
 #[macro_use]
 extern crate bitflags;
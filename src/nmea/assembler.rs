@@ -0,0 +1,293 @@
+// Collects multi-sentence GSV, TXT, and AIS groups into complete satellite lists and messages
+
+use crate::ais::{self, AISFragment, AISMessage};
+use crate::nmea::{
+    parser::{GSVsatellite, MessageType, Signal},
+    GSVData, System, TXTData, Talker,
+};
+use lazy_static::lazy_static;
+use prometheus::{register_int_counter_vec, IntCounterVec};
+use std::{collections::HashMap, hash::Hash, time::Duration};
+use tracing::debug;
+
+/// Longest time between the first and last sentence of a group
+pub const GROUP_TIMEOUT: Duration = Duration::from_secs(2);
+
+lazy_static! {
+    static ref INCOMPLETE_GROUPS: IntCounterVec = register_int_counter_vec!(
+        "where_am_i_nmea_incomplete_groups_count",
+        "Count of multi-sentence groups dropped before all parts arrived",
+        &["device", "sentence"]
+    )
+    .unwrap();
+    static ref DROPPED_SENTENCES: IntCounterVec = register_int_counter_vec!(
+        "where_am_i_nmea_dropped_sentences_count",
+        "Count of multi-sentence group parts dropped for an out-of-range part count",
+        &["device", "sentence"]
+    )
+    .unwrap();
+}
+
+/// The satellites in view for one talker and signal, from a complete GSV group
+#[derive(Clone, Debug, PartialEq)]
+pub struct SatelliteView {
+    /// When the first sentence of the group was received
+    pub received: Option<Duration>,
+    pub talker: Talker,
+    pub system: Option<System>,
+    pub signal: Option<Signal>,
+    pub num_satellites: u32,
+    pub satellites: Vec<GSVsatellite>,
+}
+
+impl SatelliteView {
+    fn new(parts: Vec<GSVData>) -> Self {
+        let first = &parts[0];
+
+        let received = first.received;
+        let talker = first.talker.clone();
+        let system = first.system.clone();
+        let signal = first.signal.clone();
+        let num_satellites = first.num_satellites;
+
+        let satellites = parts.into_iter().flat_map(|p| p.satellites).collect();
+
+        SatelliteView {
+            received,
+            talker,
+            system,
+            signal,
+            num_satellites,
+            satellites,
+        }
+    }
+}
+
+/// A text message from a complete TXT group
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextMessage {
+    /// When the first sentence of the group was received
+    pub received: Option<Duration>,
+    pub talker: Talker,
+    pub msg_type: MessageType,
+    pub text: String,
+}
+
+impl TextMessage {
+    fn new(parts: Vec<TXTData>) -> Self {
+        let first = &parts[0];
+
+        let received = first.received;
+        let talker = first.talker.clone();
+        let msg_type = first.msg_type.clone();
+
+        let text = parts.into_iter().map(|p| p.text).collect();
+
+        TextMessage {
+            received,
+            talker,
+            msg_type,
+            text,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Group<T> {
+    started: Option<Duration>,
+    parts: Vec<Option<T>>,
+}
+
+impl<T> Group<T> {
+    fn new(num_msgs: usize, started: Option<Duration>) -> Self {
+        let mut parts = Vec::with_capacity(num_msgs);
+        parts.resize_with(num_msgs, || None);
+
+        Group { started, parts }
+    }
+
+    fn expired(&self, now: Option<Duration>) -> bool {
+        match (self.started, now) {
+            (Some(started), Some(now)) => now.saturating_sub(started) > GROUP_TIMEOUT,
+            _ => false,
+        }
+    }
+
+    fn complete(&self) -> bool {
+        self.parts.iter().all(Option::is_some)
+    }
+}
+
+// Partially received groups of one sentence type
+#[derive(Debug)]
+struct Groups<K, T> {
+    sentence: &'static str,
+    // Largest part count the protocol allows for a group
+    max_msgs: u32,
+    groups: HashMap<K, Group<T>>,
+}
+
+impl<K: Clone + Eq + Hash, T> Groups<K, T> {
+    fn new(sentence: &'static str, max_msgs: u32) -> Self {
+        Groups {
+            sentence,
+            max_msgs,
+            groups: HashMap::new(),
+        }
+    }
+
+    // Adds part `msg` of `num_msgs`, returning all parts in order when the group is complete
+    fn add(
+        &mut self,
+        key: K,
+        num_msgs: u32,
+        msg: u32,
+        received: Option<Duration>,
+        part: T,
+        name: &str,
+    ) -> Option<Vec<T>> {
+        let before = self.groups.len();
+        self.groups.retain(|_, group| !group.expired(received));
+        let expired = before - self.groups.len();
+
+        if expired > 0 {
+            debug!(
+                "{}: {} incomplete {} groups timed out",
+                name, expired, self.sentence
+            );
+            INCOMPLETE_GROUPS
+                .with_label_values(&[name, self.sentence])
+                .inc_by(expired as u64);
+        }
+
+        if num_msgs > self.max_msgs {
+            debug!(
+                "{}: dropping {} with {} parts, at most {} allowed",
+                name, self.sentence, num_msgs, self.max_msgs
+            );
+            DROPPED_SENTENCES
+                .with_label_values(&[name, self.sentence])
+                .inc();
+            return None;
+        }
+
+        if msg == 0 || msg > num_msgs {
+            debug!(
+                "{}: ignoring {} part {} of {}",
+                name, self.sentence, msg, num_msgs
+            );
+            return None;
+        }
+
+        let index = (msg - 1) as usize;
+
+        // a part we already have or a different part count means the next group has started
+        let restarted = match self.groups.get(&key) {
+            Some(group) => group.parts.len() != num_msgs as usize || group.parts[index].is_some(),
+            None => false,
+        };
+
+        if restarted {
+            self.groups.remove(&key);
+
+            debug!("{}: dropped incomplete {} group", name, self.sentence);
+            INCOMPLETE_GROUPS
+                .with_label_values(&[name, self.sentence])
+                .inc();
+        }
+
+        let group = self
+            .groups
+            .entry(key.clone())
+            .or_insert_with(|| Group::new(num_msgs as usize, received));
+
+        group.parts[index] = Some(part);
+
+        if !group.complete() {
+            return None;
+        }
+
+        self.groups
+            .remove(&key)
+            .map(|group| group.parts.into_iter().flatten().collect())
+    }
+}
+
+// Fragments of one AIS message share a talker, sentence, sequential message ID, and channel
+type AISKey = (Talker, bool, Option<u32>, Option<char>);
+
+/// Assembles GSV, TXT, and AIS groups from their sentences.
+///
+/// Parts may arrive in any order.  A group with a missing part is dropped when a part of the
+/// next group with the same key arrives or when `GROUP_TIMEOUT` passes.
+#[derive(Debug)]
+pub struct Assembler {
+    gsv: Groups<(Talker, Option<Signal>), GSVData>,
+    txt: Groups<(Talker, MessageType), TXTData>,
+    ais: Groups<AISKey, AISFragment>,
+}
+
+impl Default for Assembler {
+    fn default() -> Self {
+        Assembler {
+            gsv: Groups::new("GSV", 9),
+            txt: Groups::new("TXT", 99),
+            ais: Groups::new("VDM", 9),
+        }
+    }
+}
+
+impl Assembler {
+    /// Adds a GSV sentence, returning the satellite view for its talker and signal when the group
+    /// is complete
+    pub fn gsv(&mut self, gsv: GSVData, name: &str) -> Option<SatelliteView> {
+        let key = (gsv.talker.clone(), gsv.signal.clone());
+        let (num_msgs, msg, received) = (gsv.num_msgs, gsv.msg, gsv.received);
+
+        self.gsv
+            .add(key, num_msgs, msg, received, gsv, name)
+            .map(SatelliteView::new)
+    }
+
+    /// Adds a TXT sentence, returning the message when the group is complete
+    pub fn txt(&mut self, txt: TXTData, name: &str) -> Option<TextMessage> {
+        let key = (txt.talker.clone(), txt.msg_type.clone());
+        let (num_msgs, msg, received) = (txt.num_msgs, txt.msg, txt.received);
+
+        self.txt
+            .add(key, num_msgs, msg, received, txt, name)
+            .map(TextMessage::new)
+    }
+
+    /// Adds an AIS fragment, returning the decoded message when all fragments have arrived.
+    /// Messages that are too short for their type are dropped.
+    pub fn ais(&mut self, fragment: AISFragment, name: &str) -> Option<AISMessage> {
+        let key = (
+            fragment.talker.clone(),
+            fragment.own_vessel,
+            fragment.message_id,
+            fragment.channel,
+        );
+        let (num_msgs, msg, received) = (
+            fragment.fragment_count,
+            fragment.fragment,
+            fragment.received,
+        );
+
+        let fragments = self.ais.add(key, num_msgs, msg, received, fragment, name)?;
+
+        let first = &fragments[0];
+        let (own_vessel, received) = (first.own_vessel, first.received);
+
+        let payload: String = fragments.iter().map(|f| f.payload.as_str()).collect();
+        let fill_bits = fragments.last().map(|f| f.fill_bits).unwrap_or(0);
+
+        let message = ais::message(&payload, fill_bits, own_vessel, received);
+
+        if message.is_none() {
+            debug!("{}: AIS payload {:?} is too short", name, payload);
+        }
+
+        message
+    }
+}
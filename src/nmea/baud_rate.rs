@@ -0,0 +1,169 @@
+// Baud rate detection for baud_rate = "auto"
+
+use crate::{
+    gps::Driver,
+    nmea::{Codec, ParseProfile, SerialCodec, NMEA},
+    ubx::UBXMessage,
+};
+use futures_util::stream::StreamExt;
+use std::time::Duration;
+use tokio::time::{timeout_at, Instant};
+use tokio_serial::{ClearBuffer, SerialPort, SerialStream};
+use tokio_util::codec::Framed;
+use tracing::{debug, error, info};
+
+/// Rates to try, most common first
+pub const BAUD_RATES: [u32; 9] = [
+    38400, 9600, 115200, 4800, 19200, 57600, 230400, 460800, 921600,
+];
+
+/// Longest time to listen at each rate
+const SCORE_DURATION: Duration = Duration::from_secs(2);
+
+/// Stop listening at a rate after this many sentences
+const SCORE_SENTENCES: u32 = 10;
+
+/// Minimum valid sentences for a rate to be chosen
+const MIN_VALID: u32 = 2;
+
+/// A rate with at least this ratio of valid sentences is chosen without trying the rest
+const LOCK_RATIO: f32 = 0.9;
+
+/// Consecutive invalid sentences after detection before detecting again
+pub const INVALID_LIMIT: u32 = 20;
+
+/// Time without any sentences after detection before detecting again
+pub const SILENCE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BaudRateScore {
+    pub valid: u32,
+    pub invalid: u32,
+}
+
+impl BaudRateScore {
+    pub fn add(&mut self, nmea: &NMEA) {
+        if is_valid(nmea) {
+            self.valid += 1;
+        } else {
+            self.invalid += 1;
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.valid + self.invalid
+    }
+
+    /// Fraction of sentences that were valid
+    pub fn ratio(&self) -> f32 {
+        match self.total() {
+            0 => 0.0,
+            total => self.valid as f32 / total as f32,
+        }
+    }
+
+    /// True when this score is good enough to choose its rate
+    pub fn usable(&self) -> bool {
+        self.valid >= MIN_VALID
+    }
+}
+
+/// False for sentences and frames that fail their checksum or can't be framed, which is what
+/// the receiver's output looks like at the wrong baud rate
+pub fn is_valid(nmea: &NMEA) -> bool {
+    !matches!(
+        nmea,
+        NMEA::ParseError(_) | NMEA::UBX(UBXMessage::InvalidChecksum(_))
+    )
+}
+
+/// Listens to `serial` at each of `BAUD_RATES` and returns it switched to the rate with the
+/// highest ratio of valid sentences, or None if no rate produced valid sentences
+pub async fn detect(
+    serial: SerialStream,
+    driver: Driver,
+    profile: ParseProfile,
+) -> Option<SerialStream> {
+    let mut framed = Framed::new(serial, Codec::with_profile(driver, profile));
+    let mut best: Option<(u32, BaudRateScore)> = None;
+
+    for rate in &BAUD_RATES {
+        let score = match score(&mut framed, *rate).await {
+            Some(s) => s,
+            None => return None,
+        };
+
+        debug!(
+            "baud rate {} had {} valid of {} sentences",
+            rate,
+            score.valid,
+            score.total()
+        );
+
+        if !score.usable() {
+            continue;
+        }
+
+        let locked = score.ratio() >= LOCK_RATIO;
+
+        if best.as_ref().is_none_or(|(_, b)| score.ratio() > b.ratio()) {
+            best = Some((*rate, score));
+        }
+
+        if locked {
+            break;
+        }
+    }
+
+    let (rate, score) = best?;
+
+    if let Err(e) = framed.get_mut().set_baud_rate(rate) {
+        error!("unable to set baud rate {}: {:?}", rate, e);
+        return None;
+    }
+
+    discard_input(&mut framed, rate);
+
+    info!(
+        "detected baud rate {} with {:.0}% valid sentences",
+        rate,
+        score.ratio() * 100.0
+    );
+
+    Some(framed.into_inner())
+}
+
+// Scores the sentences received at rate, None if the port failed
+async fn score(framed: &mut SerialCodec, rate: u32) -> Option<BaudRateScore> {
+    if let Err(e) = framed.get_mut().set_baud_rate(rate) {
+        error!("unable to set baud rate {}: {:?}", rate, e);
+        return None;
+    }
+
+    discard_input(framed, rate);
+
+    let mut score = BaudRateScore::default();
+    let deadline = Instant::now() + SCORE_DURATION;
+
+    while score.total() < SCORE_SENTENCES {
+        match timeout_at(deadline, framed.next()).await {
+            Ok(Some(Ok(nmea))) => score.add(&nmea),
+            Ok(Some(Err(e))) => {
+                error!("error reading at baud rate {}: {:?}", rate, e);
+                return None;
+            }
+            Ok(None) => return None,
+            Err(_) => break,
+        }
+    }
+
+    Some(score)
+}
+
+// Discards anything received at the previous rate so it isn't parsed at `rate`
+fn discard_input(framed: &mut SerialCodec, rate: u32) {
+    if let Err(e) = framed.get_ref().clear(ClearBuffer::Input) {
+        error!("unable to clear input at baud rate {}: {:?}", rate, e);
+    }
+    framed.read_buffer_mut().clear();
+}
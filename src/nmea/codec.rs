@@ -2,16 +2,18 @@ use crate::gps::Driver;
 use crate::nmea::parser::Parser;
 use crate::nmea::parser::NMEA;
 use crate::nmea::ser;
+use crate::nmea::ParseProfile;
+use crate::rtcm::RTCMFrame;
+use crate::ubx::UBXFrame;
 
+use bytes::Buf;
 use bytes::BufMut;
-use bytes::Bytes;
 use bytes::BytesMut;
 
 use nom::Err;
 
 use serde::Serialize;
 
-use std::borrow::Borrow;
 use std::fmt;
 use std::io;
 use std::time::Duration;
@@ -33,6 +35,14 @@ impl Codec {
 
         Codec { parser }
     }
+
+    /// A codec that frames and parses sentences according to `profile`
+    pub fn with_profile(driver: Driver, profile: ParseProfile) -> Self {
+        let mut codec = Codec::new(driver);
+        codec.parser.profile = profile;
+
+        codec
+    }
 }
 
 impl Decoder for Codec {
@@ -48,23 +58,19 @@ impl Decoder for Codec {
     /// timestamps we produce.
     fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
         let now = timestamp();
-        let bytes = buf.split_to(buf.len());
-        let input = bytes.borrow();
-
-        match self.parser.parse(input, now) {
-            Ok((input, nmea)) => {
-                buf.extend_from_slice(&Bytes::copy_from_slice(input));
 
-                Ok(Some(nmea))
-            }
-            Err(Err::Incomplete(_)) => {
-                buf.extend_from_slice(&Bytes::copy_from_slice(input));
-
-                Ok(None)
-            }
+        // parse in place and only advance past what was consumed so unconsumed bytes are never
+        // copied
+        let (consumed, nmea) = match self.parser.parse(buf, now) {
+            Ok((input, nmea)) => (buf.len() - input.len(), nmea),
+            Err(Err::Incomplete(_)) => return Ok(None),
             Err(Err::Error(_)) => panic!("impossible error!"),
             Err(Err::Failure(_)) => panic!("impossible failure!"),
-        }
+        };
+
+        buf.advance(consumed);
+
+        Ok(Some(nmea))
     }
 }
 
@@ -81,14 +87,11 @@ where
     type Error = CodecError;
 
     fn encode(&mut self, nmea: T, buf: &mut BytesMut) -> Result<(), CodecError> {
-        let message = match ser::to_string(&nmea) {
-            Ok(m) => m,
+        let line = match ser::to_sentence(&nmea) {
+            Ok(l) => l,
             Err(_) => return Err(CodecError::InternalError),
         };
 
-        let checksum = message.bytes().fold(0, |c, b| c ^ b);
-        let line = format!("${}*{:02X}\r\n", message, checksum);
-
         debug!("sending serial message: {:?}", line);
 
         buf.reserve(line.len());
@@ -98,6 +101,40 @@ where
     }
 }
 
+impl Encoder<UBXFrame> for Codec {
+    type Error = CodecError;
+
+    fn encode(&mut self, frame: UBXFrame, buf: &mut BytesMut) -> Result<(), CodecError> {
+        let bytes = frame.to_bytes();
+
+        debug!(
+            "sending UBX message class {:#04X} id {:#04X}",
+            frame.class, frame.id
+        );
+
+        buf.reserve(bytes.len());
+        buf.put(bytes.as_slice());
+
+        Ok(())
+    }
+}
+
+/// Writes an RTCM 3 frame, for example to send corrections to a rover
+impl Encoder<RTCMFrame> for Codec {
+    type Error = CodecError;
+
+    fn encode(&mut self, frame: RTCMFrame, buf: &mut BytesMut) -> Result<(), CodecError> {
+        let bytes = frame.to_bytes();
+
+        debug!("sending RTCM message {}", frame.message_type);
+
+        buf.reserve(bytes.len());
+        buf.put(bytes.as_slice());
+
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub enum CodecError {
     InternalError,
@@ -1,20 +1,22 @@
 use crate::{
     configuration::GpsConfig,
     device::DEVICE_OPENS,
-    gps::{Driver, Generic, GpsType, UBloxNMEA, MKT},
-    nmea::{Codec, Device, MessageSetting, NMEA},
+    gps::{self, Driver, Garmin, Generic, GpsType, Quectel, SiRF, UBloxNMEA, GPS_TYPES, MKT},
+    nmea::{
+        baud_rate, device::SerialCodec, quarantine::Quarantine, Codec, Device, ParseError, NMEA,
+    },
 };
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use backoff::{ExponentialBackoff, SystemClock};
 use futures_util::StreamExt;
 use lazy_static::lazy_static;
-use prometheus::{register_int_counter_vec, IntCounterVec};
+use prometheus::{register_int_counter_vec, register_int_gauge_vec, IntCounterVec, IntGaugeVec};
 use std::{
     convert::TryFrom,
     sync::Arc,
     time::{Duration, Instant},
 };
-use tokio::sync::broadcast;
+use tokio::{sync::broadcast, time::timeout};
 use tokio_serial::{SerialPortBuilder, SerialPortBuilderExt, SerialStream};
 use tokio_util::codec::Framed;
 use tracing::{debug, error, info, info_span, Instrument};
@@ -32,14 +34,26 @@ lazy_static! {
         &["device"]
     )
     .unwrap();
+    static ref NMEA_PARSE_ERRORS: IntCounterVec = register_int_counter_vec!(
+        "where_am_i_nmea_parse_errors_count",
+        "Count of sentences from a device that couldn't be parsed by kind of error",
+        &["device", "kind"]
+    )
+    .unwrap();
+    static ref GPS_TYPE: IntGaugeVec = register_int_gauge_vec!(
+        "where_am_i_gps_type_info",
+        "Receiver type detected for a gps_type = \"auto\" device, 1 for the detected type",
+        &["device", "gps_type"]
+    )
+    .unwrap();
 }
 
 pub struct DeviceBuilder {
     device: String,
+    config: GpsConfig,
     driver: Driver,
     backoff: ExponentialBackoff,
     serial_port_builder: SerialPortBuilder,
-    message_settings: Vec<MessageSetting>,
 }
 
 impl DeviceBuilder {
@@ -47,20 +61,15 @@ impl DeviceBuilder {
         let device = config.device.clone();
         let serial_port_builder = SerialPortBuilder::try_from(config.clone())?;
 
-        let driver = match config.gps_type {
-            GpsType::UBloxNMEA => Driver::UBloxNMEA(UBloxNMEA::default()),
-            GpsType::MKT => Driver::MKT(MKT::default()),
-            GpsType::Generic => Driver::Generic(Generic::default()),
-        };
-
-        let message_settings = driver.message_settings(&config.messages());
+        // auto detection starts with the generic driver so private sentences are left unparsed
+        let driver = driver(&config.gps_type, config);
 
         Ok(DeviceBuilder {
             device,
+            config: config.clone(),
             driver,
             backoff: default_backoff(),
             serial_port_builder,
-            message_settings,
         })
     }
 
@@ -95,33 +104,92 @@ impl DeviceBuilder {
 
             debug!("Opened NMEA serial port {}", self.device);
 
+            if !self.config.auto_baud_rate() {
+                return Ok(serial);
+            }
+
+            let serial =
+                baud_rate::detect(serial, self.driver.clone(), self.config.parse_profile())
+                    .await
+                    .ok_or_else(|| anyhow!("Unable to detect baud rate of {}", self.device))
+                    .map_err(|e| log_error(&self.device, e))?;
+
             Ok(serial)
         })
         .await
     }
 
     async fn start(&self, sender: broadcast::Sender<NMEA>) {
+        let mut quarantine = self.quarantine().await;
+
         loop {
             let serial = match self.open().await {
                 Ok(t) => t,
                 Err(_) => unreachable!("open retries opening the device forever"),
             };
 
-            let mut framed = Framed::new(serial, Codec::new(self.driver.clone()));
+            let mut framed = Framed::new(
+                serial,
+                Codec::with_profile(self.driver.clone(), self.config.parse_profile()),
+            );
+
+            let driver = match self.config.gps_type {
+                GpsType::Auto => {
+                    let gps_type = self.detect(&mut framed).await;
+                    let driver = driver(&gps_type, &self.config);
+                    framed.codec_mut().parser.driver = driver.clone();
+                    driver
+                }
+                _ => self.driver.clone(),
+            };
 
-            self.driver
-                .configure(&mut framed, &self.message_settings)
-                .await;
+            let message_settings = driver.message_settings(&self.config.messages());
+
+            driver.configure(&mut framed, &message_settings).await;
 
             let nmea_messages = NMEA_MESSAGES.with_label_values(&[&self.device]);
             let nmea_errors = NMEA_ERRORS.with_label_values(&[&self.device]);
 
+            let auto_baud_rate = self.config.auto_baud_rate();
+            let mut invalid = 0;
+
             // send NMEA messages
             loop {
-                match framed.next().await {
+                let next = if auto_baud_rate {
+                    match timeout(baud_rate::SILENCE_TIMEOUT, framed.next()).await {
+                        Ok(next) => next,
+                        Err(_) => {
+                            error!("NMEA device {} is silent, detecting baud rate", self.device);
+                            break;
+                        }
+                    }
+                } else {
+                    framed.next().await
+                };
+
+                match next {
                     Some(Ok(nmea)) => {
                         nmea_messages.inc();
+
+                        if let NMEA::ParseError(e) = &nmea {
+                            self.parse_error(e, &mut quarantine).await;
+                        }
+
+                        if baud_rate::is_valid(&nmea) {
+                            invalid = 0;
+                        } else {
+                            invalid += 1;
+                        }
+
                         sender.send(nmea).unwrap_or(0);
+
+                        if auto_baud_rate && invalid >= baud_rate::INVALID_LIMIT {
+                            error!(
+                                "NMEA device {} sent {} invalid sentences, detecting baud rate",
+                                self.device, invalid
+                            );
+                            break;
+                        }
                     }
                     Some(Err(e)) => {
                         nmea_errors.inc();
@@ -138,6 +206,73 @@ impl DeviceBuilder {
             info!("Device {} hung up, retrying", self.device);
         }
     }
+
+    // Opens the quarantine file, if configured
+    async fn quarantine(&self) -> Option<Quarantine> {
+        let path = self.config.quarantine.as_ref()?;
+
+        match Quarantine::open(path).await {
+            Ok(q) => Some(q),
+            Err(e) => {
+                error!("Unable to open quarantine file {}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    async fn parse_error(&self, error: &ParseError, quarantine: &mut Option<Quarantine>) {
+        NMEA_PARSE_ERRORS
+            .with_label_values(&[&self.device, error.kind.name()])
+            .inc();
+
+        if let Some(q) = quarantine {
+            if let Err(e) = q.write(error).await {
+                error!("Unable to write to quarantine file: {}", e);
+            }
+        }
+    }
+
+    // Detects the receiver type, generic if detection fails
+    async fn detect(&self, serial: &mut SerialCodec) -> GpsType {
+        let gps_type = match gps::detect(serial).await {
+            Some(gps_type) => {
+                info!("Detected {} receiver on {}", gps_type.name(), self.device);
+                gps_type
+            }
+            None => {
+                error!(
+                    "Unable to detect receiver type on {}, using generic",
+                    self.device
+                );
+                GpsType::Generic
+            }
+        };
+
+        for candidate in &GPS_TYPES {
+            let chosen = if *candidate == gps_type { 1 } else { 0 };
+
+            GPS_TYPE
+                .with_label_values(&[&self.device, candidate.name()])
+                .set(chosen);
+        }
+
+        gps_type
+    }
+}
+
+fn driver(gps_type: &GpsType, config: &GpsConfig) -> Driver {
+    match gps_type {
+        GpsType::UBloxNMEA => {
+            Driver::UBloxNMEA(UBloxNMEA::new(config.ublox.clone().unwrap_or_default()))
+        }
+        GpsType::MKT => Driver::MKT(MKT::new(config.mkt.clone().unwrap_or_default())),
+        GpsType::Quectel => {
+            Driver::Quectel(Quectel::new(config.quectel.clone().unwrap_or_default()))
+        }
+        GpsType::SiRF => Driver::SiRF(SiRF::default()),
+        GpsType::Garmin => Driver::Garmin(Garmin::default()),
+        GpsType::Auto | GpsType::Generic => Driver::Generic(Generic::default()),
+    }
 }
 
 fn default_backoff() -> ExponentialBackoff {
@@ -1,23 +1,39 @@
+mod assembler;
+pub mod baud_rate;
 mod codec;
 pub mod device;
 pub mod device_builder;
 pub mod message_setting;
+mod parse_error;
+mod parse_profile;
 pub mod parser;
 pub mod parser_util;
+pub mod quarantine;
 mod sentence_parser;
 mod ser;
+pub(crate) mod ser_util;
+pub mod view;
 
+pub use assembler::Assembler;
+pub use assembler::SatelliteView;
+pub use assembler::TextMessage;
+pub use assembler::GROUP_TIMEOUT;
 pub use codec::Codec;
 pub use device::Device;
 pub use device::SerialCodec;
 pub use device_builder::DeviceBuilder;
 pub use message_setting::MessageSetting;
+pub use parse_error::ParseError;
+pub use parse_error::ParseErrorKind;
+pub use parse_profile::ParseProfile;
+pub use parse_profile::MAX_SENTENCE_LENGTH;
 pub use parser::message;
 pub use parser::DTMData;
 pub use parser::GAQData;
 pub use parser::GBQData;
 pub use parser::GBSData;
 pub use parser::GGAData;
+pub use parser::GGAView;
 pub use parser::GLLData;
 pub use parser::GLQData;
 pub use parser::GNQData;
@@ -25,23 +41,43 @@ pub use parser::GNSData;
 pub use parser::GPQData;
 pub use parser::GRSData;
 pub use parser::GSAData;
+pub use parser::GSAView;
 pub use parser::GSTData;
 pub use parser::GSVData;
+pub use parser::GSVView;
+pub use parser::HDTData;
+pub use parser::HeadingMode;
 pub use parser::NavigationMode;
 pub use parser::OperationMode;
 pub use parser::Quality;
 pub use parser::RMCData;
+pub use parser::ROTData;
+pub use parser::Status;
 pub use parser::System;
+pub use parser::THSData;
 pub use parser::TXTData;
+pub use parser::TXTView;
 pub use parser::Talker;
 pub use parser::VLWData;
 pub use parser::VTGData;
+pub use parser::VTGView;
 pub use parser::ZDAData;
 pub use parser::NMEA;
 pub use parser_util::EastWest;
 pub use parser_util::LatLon;
 pub use parser_util::NorthSouth;
+pub use ser::to_sentence;
 pub use ser::ToNMEA;
+pub use view::view;
+pub use view::NMEAView;
+pub use view::Sentence;
+pub use view::TypedSentence;
+
+#[cfg(test)]
+mod test_assembler;
+
+#[cfg(test)]
+mod test_baud_rate;
 
 #[cfg(test)]
 mod test_codec;
@@ -49,8 +85,14 @@ mod test_codec;
 #[cfg(test)]
 mod test_parser;
 
+#[cfg(test)]
+mod test_quarantine;
+
 #[cfg(test)]
 mod test_sentence_parser;
 
 #[cfg(test)]
 mod test_ser;
+
+#[cfg(test)]
+mod test_view;
@@ -0,0 +1,137 @@
+use nom::error::{VerboseError, VerboseErrorKind};
+use std::{fmt, time::Duration};
+
+/// Why input from a receiver couldn't be parsed
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseErrorKind {
+    /// The checksum in the sentence doesn't match its data
+    ChecksumMismatch { given: u8, calculated: u8 },
+    /// The sentence has no checksum and the parse profile requires one
+    MissingChecksum,
+    /// The checksum has lowercase hex digits and the parse profile requires uppercase
+    LowercaseChecksum,
+    /// The sentence is longer than the parse profile allows
+    TooLong { length: usize, max: usize },
+    /// The sentence isn't a standard sentence or a proprietary sentence of the driver
+    UnknownSentence,
+    /// Field `field` of the sentence couldn't be parsed, numbered from zero after the address
+    InvalidField { field: usize, expected: String },
+    /// The sentence parsed but its layout isn't accepted by the parse profile
+    UnsupportedLayout,
+    /// The sentence ended before all its fields were read
+    Truncated,
+    /// Bytes that weren't part of a sentence or frame were skipped
+    Garbage { skipped: usize },
+    /// The sentence isn't valid UTF-8
+    InvalidUTF8,
+}
+
+impl ParseErrorKind {
+    /// Name of the kind for metric labels and the quarantine file
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParseErrorKind::ChecksumMismatch { .. } => "checksum_mismatch",
+            ParseErrorKind::MissingChecksum => "missing_checksum",
+            ParseErrorKind::LowercaseChecksum => "lowercase_checksum",
+            ParseErrorKind::TooLong { .. } => "too_long",
+            ParseErrorKind::UnknownSentence => "unknown_sentence",
+            ParseErrorKind::InvalidField { .. } => "invalid_field",
+            ParseErrorKind::UnsupportedLayout => "unsupported_layout",
+            ParseErrorKind::Truncated => "truncated",
+            ParseErrorKind::Garbage { .. } => "garbage",
+            ParseErrorKind::InvalidUTF8 => "invalid_utf8",
+        }
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::ChecksumMismatch { given, calculated } => write!(
+                f,
+                "checksum mismatch, given {:02X}, calculated {:02X}",
+                given, calculated
+            ),
+            ParseErrorKind::MissingChecksum => write!(f, "missing checksum"),
+            ParseErrorKind::LowercaseChecksum => write!(f, "lowercase checksum"),
+            ParseErrorKind::TooLong { length, max } => {
+                write!(f, "sentence too long, {} bytes of {}", length, max)
+            }
+            ParseErrorKind::UnknownSentence => write!(f, "unknown sentence"),
+            ParseErrorKind::InvalidField { field, expected } => {
+                write!(f, "field {} invalid, expected {}", field, expected)
+            }
+            ParseErrorKind::UnsupportedLayout => write!(f, "layout not accepted by profile"),
+            ParseErrorKind::Truncated => write!(f, "truncated"),
+            ParseErrorKind::Garbage { skipped } => write!(f, "skipped {} garbage bytes", skipped),
+            ParseErrorKind::InvalidUTF8 => write!(f, "invalid UTF-8"),
+        }
+    }
+}
+
+/// Input that couldn't be parsed
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub received: Option<Duration>,
+    pub kind: ParseErrorKind,
+    /// The sentence between the `$` and `*`, or the skipped bytes for garbage
+    pub sentence: String,
+    /// Byte offset in `sentence` where the error was found
+    pub offset: usize,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, sentence: &str, offset: usize) -> Self {
+        ParseError {
+            received: None,
+            kind,
+            sentence: sentence.to_string(),
+            offset,
+        }
+    }
+
+    /// Classifies the error from parsing `sentence` with nom
+    pub(crate) fn from_verbose(sentence: &str, error: &VerboseError<&str>) -> Self {
+        let (rest, kind) = match error.errors.first() {
+            Some(e) => e,
+            None => return ParseError::new(ParseErrorKind::UnknownSentence, sentence, 0),
+        };
+
+        let offset = offset(sentence, rest);
+
+        let kind = match sentence[..offset].matches(',').count() {
+            // the address was not recognized
+            0 => ParseErrorKind::UnknownSentence,
+            _ if offset == sentence.len() => ParseErrorKind::Truncated,
+            commas => ParseErrorKind::InvalidField {
+                field: commas - 1,
+                expected: expected(kind),
+            },
+        };
+
+        ParseError::new(kind, sentence, offset)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {} in {:?}", self.kind, self.offset, self.sentence)
+    }
+}
+
+// Offset of the error input within sentence, errors may be reported on a sub-slice
+fn offset(sentence: &str, rest: &str) -> usize {
+    let start = sentence.as_ptr() as usize;
+    let error = rest.as_ptr() as usize;
+
+    error.saturating_sub(start).min(sentence.len())
+}
+
+fn expected(kind: &VerboseErrorKind) -> String {
+    match kind {
+        VerboseErrorKind::Char(c) => format!("{:?}", c),
+        VerboseErrorKind::Context(c) => c.to_string(),
+        VerboseErrorKind::Nom(nom::error::ErrorKind::Eof) => "end of sentence".to_string(),
+        VerboseErrorKind::Nom(k) => k.description().to_lowercase(),
+    }
+}
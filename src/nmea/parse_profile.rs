@@ -0,0 +1,79 @@
+use crate::nmea::NMEA;
+use serde::Deserialize;
+
+/// Longest sentence allowed by the standard including the `$` and line ending
+pub const MAX_SENTENCE_LENGTH: usize = 82;
+
+/// Sentence framing and field layouts the parser accepts from a receiver.
+///
+/// | profile | checksum            | length    | layouts              |
+/// |---------|---------------------|-----------|----------------------|
+/// | strict  | required, uppercase | 82 bytes  | NMEA 3.x and 4.x     |
+/// | nmea2   | required            | unlimited | NMEA 2.x             |
+/// | nmea3   | required            | unlimited | NMEA 3.x             |
+/// | nmea4   | required            | unlimited | NMEA 3.x and 4.x     |
+/// | lenient | optional            | unlimited | NMEA 2.x through 4.x |
+///
+/// NMEA 2.x layouts include RMC and GLL without the mode indicator added in NMEA 2.3.  NMEA 4.x
+/// layouts include the navigational status of RMC and the system and signal IDs of GSA and GSV
+/// added in NMEA 4.10.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[serde(rename_all = "lowercase")]
+pub enum ParseProfile {
+    Strict,
+    NMEA2,
+    NMEA3,
+    #[default]
+    NMEA4,
+    Lenient,
+}
+
+impl ParseProfile {
+    /// Name of the profile in the configuration file
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParseProfile::Strict => "strict",
+            ParseProfile::NMEA2 => "nmea2",
+            ParseProfile::NMEA3 => "nmea3",
+            ParseProfile::NMEA4 => "nmea4",
+            ParseProfile::Lenient => "lenient",
+        }
+    }
+
+    /// True when sentences without a checksum are rejected
+    pub fn checksum_required(&self) -> bool {
+        !matches!(self, ParseProfile::Lenient)
+    }
+
+    /// True when checksums with lowercase hex digits are rejected
+    pub fn uppercase_checksum(&self) -> bool {
+        matches!(self, ParseProfile::Strict)
+    }
+
+    /// The longest sentence accepted, if limited
+    pub fn max_length(&self) -> Option<usize> {
+        match self {
+            ParseProfile::Strict => Some(MAX_SENTENCE_LENGTH),
+            _ => None,
+        }
+    }
+
+    /// True when NMEA 2.x layouts without a mode indicator are accepted
+    pub fn legacy_layouts(&self) -> bool {
+        matches!(self, ParseProfile::NMEA2 | ParseProfile::Lenient)
+    }
+
+    /// False when `nmea` contains fields added after the NMEA revision of this profile
+    pub fn accepts(&self, nmea: &NMEA) -> bool {
+        if !matches!(self, ParseProfile::NMEA2 | ParseProfile::NMEA3) {
+            return true;
+        }
+
+        match nmea {
+            NMEA::GSA(gsa) => gsa.system.is_none(),
+            NMEA::GSV(gsv) => gsv.signal.is_none(),
+            NMEA::RMC(rmc) => rmc.nav_status.is_none(),
+            _ => true,
+        }
+    }
+}
@@ -1,35 +1,47 @@
-use crate::gps::{Driver, MKTData, UBXData};
+use crate::ais::AISFragment;
+use crate::configuration::ConfigurationError;
+use crate::gps::{Driver, GarminData, MKTData, QuectelData, SiRFData, UBXData};
 use crate::nmea::{
-    parser_util::*,
-    sentence_parser::{parse_sentence, NMEASentence},
-    EastWest, NorthSouth,
+    parser_util::*, ser_util, view::view, EastWest, NorthSouth, ParseError, ParseErrorKind,
+    ParseProfile,
 };
+use crate::rtcm::RTCMFrame;
+use crate::ubx::UBXMessage;
 use chrono::naive::{NaiveDate, NaiveTime};
 use nom::{
     branch::*, bytes::complete::*, character::complete::*, combinator::*, error::*, multi::*,
     sequence::*, Err, IResult,
 };
-use std::time::Duration;
+use serde::{
+    ser::{Error as _, SerializeStruct, Serializer},
+    Deserialize, Serialize,
+};
+use std::{convert::TryFrom, time::Duration};
 
 pub type Result<I, T> = IResult<I, T, nom::error::VerboseError<I>>;
 
 #[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Parser {
     pub driver: Driver,
+    pub profile: ParseProfile,
 }
 
 impl Parser {
     pub fn new(driver: Driver) -> Self {
-        Parser { driver }
+        Parser {
+            driver,
+            profile: ParseProfile::default(),
+        }
     }
 
     pub fn parse<'a>(&'a self, input: &'a [u8], received: Duration) -> Result<&'a [u8], NMEA> {
-        parse(input, &self.driver, received)
+        parse(input, &self.driver, self.profile, received)
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum NMEA {
+    AIS(AISFragment),
     DTM(DTMData),
     GAQ(GAQData),
     GBQ(GBQData),
@@ -44,152 +56,302 @@ pub enum NMEA {
     GSA(GSAData),
     GST(GSTData),
     GSV(GSVData),
+    HDT(HDTData),
+    PGRM(GarminData),
     PMKT(MKTData),
+    PSRF(SiRFData),
     PUBX(UBXData),
+    Quectel(QuectelData),
     RMC(RMCData),
+    ROT(ROTData),
+    RTCM(RTCMFrame),
+    THS(THSData),
     TXT(TXTData),
+    UBX(UBXMessage),
     VLW(VLWData),
     VTG(VTGData),
     ZDA(ZDAData),
-    InvalidChecksum(ChecksumMismatch),
-    ParseError(String),
-    ParseFailure(String),
+    ParseError(ParseError),
     Unsupported(String),
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ChecksumMismatch {
-    pub message: String,
-    pub given: u8,
-    pub calculated: u8,
+impl Serialize for NMEA {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            NMEA::DTM(d) => d.serialize(serializer),
+            NMEA::GAQ(d) => d.serialize(serializer),
+            NMEA::GBQ(d) => d.serialize(serializer),
+            NMEA::GBS(d) => d.serialize(serializer),
+            NMEA::GGA(d) => d.serialize(serializer),
+            NMEA::GLL(d) => d.serialize(serializer),
+            NMEA::GLQ(d) => d.serialize(serializer),
+            NMEA::GNQ(d) => d.serialize(serializer),
+            NMEA::GNS(d) => d.serialize(serializer),
+            NMEA::GPQ(d) => d.serialize(serializer),
+            NMEA::GRS(d) => d.serialize(serializer),
+            NMEA::GSA(d) => d.serialize(serializer),
+            NMEA::GST(d) => d.serialize(serializer),
+            NMEA::GSV(d) => d.serialize(serializer),
+            NMEA::HDT(d) => d.serialize(serializer),
+            NMEA::PGRM(d) => d.serialize(serializer),
+            NMEA::PMKT(d) => d.serialize(serializer),
+            NMEA::PSRF(d) => d.serialize(serializer),
+            NMEA::PUBX(d) => d.serialize(serializer),
+            NMEA::Quectel(d) => d.serialize(serializer),
+            NMEA::RMC(d) => d.serialize(serializer),
+            NMEA::ROT(d) => d.serialize(serializer),
+            NMEA::THS(d) => d.serialize(serializer),
+            NMEA::TXT(d) => d.serialize(serializer),
+            NMEA::VLW(d) => d.serialize(serializer),
+            NMEA::VTG(d) => d.serialize(serializer),
+            NMEA::ZDA(d) => d.serialize(serializer),
+            NMEA::Unsupported(sentence) => serializer.serialize_str(sentence),
+            NMEA::UBX(_) | NMEA::RTCM(_) => Err(S::Error::custom("binary frames are not NMEA")),
+            NMEA::AIS(_) => Err(S::Error::custom(
+                "encapsulated sentences can't be serialized",
+            )),
+            NMEA::ParseError(_) => Err(S::Error::custom("unparsed sentences can't be serialized")),
+        }
+    }
 }
 
 pub(crate) fn parse<'a>(
     input: &'a [u8],
     driver: &Driver,
+    profile: ParseProfile,
     received: Duration,
 ) -> Result<&'a [u8], NMEA> {
-    let result = parse_sentence(input, received);
+    let (input, view) = view(input, profile)?;
 
-    let (input, data) = match result {
-        Ok((input, sentence)) => match sentence {
-            NMEASentence::InvalidChecksum(cm) => {
-                return Ok((input, NMEA::InvalidChecksum(cm)));
-            }
-            NMEASentence::ParseError(e) => return Ok((input, NMEA::ParseError(e))),
-            NMEASentence::Valid(d) => (input, d),
-        },
-        Err(Err::Incomplete(n)) => {
-            return Err(Err::Incomplete(n));
-        }
-        Err(_) => unreachable!(),
-    };
+    Ok((input, view.to_nmea(driver, profile, received)))
+}
 
-    match message(data, driver, received) {
-        Err(Err::Error(_)) => Ok((input, NMEA::ParseError(String::from(data)))),
-        Err(Err::Failure(_)) => Ok((input, NMEA::ParseFailure(String::from(data)))),
-        Err(Err::Incomplete(_)) => unreachable!(
-            "Got Incomplete when complete parsers were used on: {:?}",
-            data
-        ),
-        // discard input from sub-parser, it was fully consumed
-        Ok((_, nmea)) => Ok((input, nmea)),
+/// Parses a sentence with a field layout accepted by `profile`
+pub fn message<'a>(
+    input: &'a str,
+    driver: &Driver,
+    profile: ParseProfile,
+    received: Duration,
+) -> Result<&'a str, NMEA> {
+    let layout = verify(
+        |i| nmea_message(i, received),
+        |nmea: &NMEA| profile.accepts(nmea),
+    )(input);
+
+    match layout {
+        Ok(r) => Ok(r),
+        Err(_) if profile.legacy_layouts() => match legacy_message(input, received) {
+            Ok(r) => Ok(r),
+            Err(_) => private_message(input, driver, received),
+        },
+        Err(_) => private_message(input, driver, received),
     }
 }
 
-pub fn message<'a>(input: &'a str, driver: &Driver, received: Duration) -> Result<&'a str, NMEA> {
-    match nmea_message(input, received) {
-        Ok(r) => Ok(r),
-        Err(_) => private_message(input, driver),
+/// Reparses a standard sentence that `message()` couldn't parse to find the invalid field
+pub(crate) fn standard_error(input: &str, sentence_id: &str) -> ParseError {
+    // alt() reports only the error from its last parser
+    let result = match sentence_id {
+        "DTM" => dtm(input).map(|_| ()),
+        "GAQ" => gaq(input).map(|_| ()),
+        "GBQ" => gbq(input).map(|_| ()),
+        "GBS" => gbs(input).map(|_| ()),
+        "GGA" => gga(input).map(|_| ()),
+        "GLL" => gll(input).map(|_| ()),
+        "GLQ" => glq(input).map(|_| ()),
+        "GNQ" => gnq(input).map(|_| ()),
+        "GNS" => gns(input).map(|_| ()),
+        "GPQ" => gpq(input).map(|_| ()),
+        "GRS" => grs(input).map(|_| ()),
+        "GSA" => gsa(input).map(|_| ()),
+        "GST" => gst(input).map(|_| ()),
+        "GSV" => gsv(input).map(|_| ()),
+        "HDT" => hdt(input).map(|_| ()),
+        "RMC" => rmc(input).map(|_| ()),
+        "ROT" => rot(input).map(|_| ()),
+        "THS" => ths(input).map(|_| ()),
+        "TXT" => txt(input).map(|_| ()),
+        "VLW" => vlw(input).map(|_| ()),
+        "VTG" => vtg(input).map(|_| ()),
+        "ZDA" => zda(input).map(|_| ()),
+        _ => return ParseError::new(ParseErrorKind::UnknownSentence, input, 0),
+    };
+
+    match result {
+        Err(Err::Error(e)) | Err(Err::Failure(e)) => ParseError::from_verbose(input, &e),
+        // the parser accepts the sentence, so the profile rejected its layout
+        _ => ParseError::new(ParseErrorKind::UnsupportedLayout, input, 0),
     }
 }
 
-pub fn nmea_message<'a>(input: &'a str, received: Duration) -> Result<&'a str, NMEA> {
+/// Parses NMEA 2.x sentences that lack the mode indicator added in NMEA 2.3
+pub fn legacy_message(input: &str, received: Duration) -> Result<&str, NMEA> {
     alt((
-        map(dtm, |mut msg: DTMData| {
-            msg.received = Some(received);
-            NMEA::DTM(msg)
-        }),
-        map(gaq, |mut msg: GAQData| {
-            msg.received = Some(received);
-            NMEA::GAQ(msg)
-        }),
-        map(gbq, |mut msg: GBQData| {
-            msg.received = Some(received);
-            NMEA::GBQ(msg)
-        }),
-        map(gbs, |mut msg: GBSData| {
-            msg.received = Some(received);
-            NMEA::GBS(msg)
-        }),
-        map(gga, |mut msg: GGAData| {
-            msg.received = Some(received);
-            NMEA::GGA(msg)
-        }),
-        map(gll, |mut msg: GLLData| {
+        map(gll_legacy, |mut msg: GLLData| {
             msg.received = Some(received);
             NMEA::GLL(msg)
         }),
-        map(glq, |mut msg: GLQData| {
-            msg.received = Some(received);
-            NMEA::GLQ(msg)
-        }),
-        map(gnq, |mut msg: GNQData| {
-            msg.received = Some(received);
-            NMEA::GNQ(msg)
-        }),
-        map(gns, |mut msg: GNSData| {
-            msg.received = Some(received);
-            NMEA::GNS(msg)
-        }),
-        map(gpq, |mut msg: GPQData| {
-            msg.received = Some(received);
-            NMEA::GPQ(msg)
-        }),
-        map(grs, |mut msg: GRSData| {
-            msg.received = Some(received);
-            NMEA::GRS(msg)
-        }),
-        map(gsa, |mut msg: GSAData| {
-            msg.received = Some(received);
-            NMEA::GSA(msg)
-        }),
-        map(gst, |mut msg: GSTData| {
-            msg.received = Some(received);
-            NMEA::GST(msg)
-        }),
-        map(gsv, |mut msg: GSVData| {
-            msg.received = Some(received);
-            NMEA::GSV(msg)
-        }),
-        map(rmc, |mut msg: RMCData| {
+        map(rmc_legacy, |mut msg: RMCData| {
             msg.received = Some(received);
             NMEA::RMC(msg)
         }),
-        map(txt, |mut msg: TXTData| {
-            msg.received = Some(received);
-            NMEA::TXT(msg)
-        }),
-        map(vlw, |mut msg: VLWData| {
-            msg.received = Some(received);
-            NMEA::VLW(msg)
-        }),
-        map(vtg, |mut msg: VTGData| {
-            msg.received = Some(received);
-            NMEA::VTG(msg)
-        }),
-        map(zda, |mut msg: ZDAData| {
-            msg.received = Some(received);
-            NMEA::ZDA(msg)
-        }),
     ))(input)
 }
 
-pub(crate) fn private_message<'a>(input: &'a str, driver: &Driver) -> Result<&'a str, NMEA> {
-    driver.parse_private(input)
+pub fn nmea_message<'a>(input: &'a str, received: Duration) -> Result<&'a str, NMEA> {
+    // alt() accepts at most 21 parsers
+    alt((
+        alt((
+            map(dtm, |mut msg: DTMData| {
+                msg.received = Some(received);
+                NMEA::DTM(msg)
+            }),
+            map(gaq, |mut msg: GAQData| {
+                msg.received = Some(received);
+                NMEA::GAQ(msg)
+            }),
+            map(gbq, |mut msg: GBQData| {
+                msg.received = Some(received);
+                NMEA::GBQ(msg)
+            }),
+            map(gbs, |mut msg: GBSData| {
+                msg.received = Some(received);
+                NMEA::GBS(msg)
+            }),
+            map(gga, |mut msg: GGAData| {
+                msg.received = Some(received);
+                NMEA::GGA(msg)
+            }),
+            map(gll, |mut msg: GLLData| {
+                msg.received = Some(received);
+                NMEA::GLL(msg)
+            }),
+            map(glq, |mut msg: GLQData| {
+                msg.received = Some(received);
+                NMEA::GLQ(msg)
+            }),
+            map(gnq, |mut msg: GNQData| {
+                msg.received = Some(received);
+                NMEA::GNQ(msg)
+            }),
+            map(gns, |mut msg: GNSData| {
+                msg.received = Some(received);
+                NMEA::GNS(msg)
+            }),
+            map(gpq, |mut msg: GPQData| {
+                msg.received = Some(received);
+                NMEA::GPQ(msg)
+            }),
+            map(grs, |mut msg: GRSData| {
+                msg.received = Some(received);
+                NMEA::GRS(msg)
+            }),
+            map(gsa, |mut msg: GSAData| {
+                msg.received = Some(received);
+                NMEA::GSA(msg)
+            }),
+            map(gst, |mut msg: GSTData| {
+                msg.received = Some(received);
+                NMEA::GST(msg)
+            }),
+            map(gsv, |mut msg: GSVData| {
+                msg.received = Some(received);
+                NMEA::GSV(msg)
+            }),
+            map(hdt, |mut msg: HDTData| {
+                msg.received = Some(received);
+                NMEA::HDT(msg)
+            }),
+        )),
+        alt((
+            map(rmc, |mut msg: RMCData| {
+                msg.received = Some(received);
+                NMEA::RMC(msg)
+            }),
+            map(rot, |mut msg: ROTData| {
+                msg.received = Some(received);
+                NMEA::ROT(msg)
+            }),
+            map(ths, |mut msg: THSData| {
+                msg.received = Some(received);
+                NMEA::THS(msg)
+            }),
+            map(txt, |mut msg: TXTData| {
+                msg.received = Some(received);
+                NMEA::TXT(msg)
+            }),
+            map(vlw, |mut msg: VLWData| {
+                msg.received = Some(received);
+                NMEA::VLW(msg)
+            }),
+            map(vtg, |mut msg: VTGData| {
+                msg.received = Some(received);
+                NMEA::VTG(msg)
+            }),
+            map(zda, |mut msg: ZDAData| {
+                msg.received = Some(received);
+                NMEA::ZDA(msg)
+            }),
+        )),
+    ))(input)
+}
+
+pub(crate) fn private_message<'a>(
+    input: &'a str,
+    driver: &Driver,
+    received: Duration,
+) -> Result<&'a str, NMEA> {
+    map(
+        |i| driver.parse_private(i),
+        |nmea| match nmea {
+            NMEA::PUBX(UBXData::Time(mut msg)) => {
+                msg.received = Some(received);
+                NMEA::PUBX(UBXData::Time(msg))
+            }
+            nmea => nmea,
+        },
+    )(input)
 }
 
 #[derive(Clone, Debug, PartialEq)]
+pub enum HeadingMode {
+    Autonomous,
+    Estimated,
+    Manual,
+    Simulator,
+    Invalid,
+}
+
+impl Serialize for HeadingMode {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_char(match self {
+            HeadingMode::Autonomous => 'A',
+            HeadingMode::Estimated => 'E',
+            HeadingMode::Manual => 'M',
+            HeadingMode::Simulator => 'S',
+            HeadingMode::Invalid => 'V',
+        })
+    }
+}
+
+pub(crate) fn heading_mode(input: &str) -> Result<&str, HeadingMode> {
+    map(one_of("AEMSV"), |c| match c {
+        'A' => HeadingMode::Autonomous,
+        'E' => HeadingMode::Estimated,
+        'M' => HeadingMode::Manual,
+        'S' => HeadingMode::Simulator,
+        'V' => HeadingMode::Invalid,
+        _ => panic!("Unhandled heading mode {:?}", c),
+    })(input)
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum MessageType {
     Error,
     Notice,
@@ -198,6 +360,23 @@ pub enum MessageType {
     Unknown(u32),
 }
 
+impl Serialize for MessageType {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let code = match self {
+            MessageType::Error => 0,
+            MessageType::Warning => 1,
+            MessageType::Notice => 2,
+            MessageType::User => 7,
+            MessageType::Unknown(t) => *t,
+        };
+
+        serializer.serialize_str(&format!("{:02}", code))
+    }
+}
+
 pub(crate) fn msg_type<'a>(input: &'a str) -> Result<&'a str, MessageType> {
     map(two_digit, |t| match t {
         0 => MessageType::Error,
@@ -215,6 +394,19 @@ pub enum NavigationMode {
     Fix3D,
 }
 
+impl Serialize for NavigationMode {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_char(match self {
+            NavigationMode::FixNone => '1',
+            NavigationMode::Fix2D => '2',
+            NavigationMode::Fix3D => '3',
+        })
+    }
+}
+
 pub(crate) fn nav_mode<'a>(input: &'a str) -> Result<&'a str, NavigationMode> {
     map(one_of("123"), |c| match c {
         '1' => NavigationMode::FixNone,
@@ -230,6 +422,18 @@ pub enum OperationMode {
     Manual,
 }
 
+impl Serialize for OperationMode {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_char(match self {
+            OperationMode::Automatic => 'A',
+            OperationMode::Manual => 'M',
+        })
+    }
+}
+
 pub(crate) fn op_mode<'a>(input: &'a str) -> Result<&'a str, OperationMode> {
     map(one_of("AM"), |c| match c {
         'A' => OperationMode::Automatic,
@@ -248,6 +452,29 @@ pub enum PositionMode {
     RTKFloat,
 }
 
+impl PositionMode {
+    /// The mode indicator character
+    pub fn code(&self) -> char {
+        match self {
+            PositionMode::AutonomousGNSSFix => 'A',
+            PositionMode::DifferentialGNSSFix => 'D',
+            PositionMode::EstimatedDeadReckoningFix => 'E',
+            PositionMode::RTKFloat => 'F',
+            PositionMode::NoFix => 'N',
+            PositionMode::RTKFixed => 'R',
+        }
+    }
+}
+
+impl Serialize for PositionMode {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_char(self.code())
+    }
+}
+
 pub(crate) fn pos_mode<'a>(input: &'a str) -> Result<&'a str, PositionMode> {
     map(one_of("ADEFNR"), |c| match c {
         'A' => PositionMode::AutonomousGNSSFix,
@@ -278,6 +505,22 @@ impl Default for Quality {
     }
 }
 
+impl Serialize for Quality {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_char(match self {
+            Quality::NoFix => '0',
+            Quality::AutonomousGNSSFix | Quality::Fix2D | Quality::Fix3D => '1',
+            Quality::DifferentialGNSSFix => '2',
+            Quality::RTKFixed => '4',
+            Quality::RTKFloat => '5',
+            Quality::EstimatedDeadReckoningFix => '6',
+        })
+    }
+}
+
 pub(crate) fn quality<'a>(input: &'a str) -> Result<&'a str, Quality> {
     map(one_of("012456"), |c| match c {
         '0' => Quality::NoFix,
@@ -290,57 +533,186 @@ pub(crate) fn quality<'a>(input: &'a str) -> Result<&'a str, Quality> {
     })(input)
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+/// Signals from the NMEA 4.11 signal ID table.
+///
+/// Signal IDs are only unique within a system, use `Signal::new()` to look up a signal.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Signal {
-    // GPS L1C/A
-    // SBAS L1C/A
-    // BeiDou B1I D1
-    // BeiDou B1I D1
-    // QZSS L1C/A
-    // GLONASS L1 OF
-    L1,
-
-    // Galileo E5 bI
-    // Galileo E5 bQ
-    E5,
-
-    // GLONASS L2 OF
-    L2OF,
-
-    // QZSS L1S
-    L1S,
-
-    // GPS L2 CM
-    // QZSS L2 CM
-    L2CM,
-
-    // GPS L2 CL
-    // QZSS L2 CL
-    L2CL,
+    // Signal ID 0, all signals of the system
+    All,
+
+    BeiDuoB1I,
+    BeiDuoB1Q,
+    BeiDuoB1C,
+    BeiDuoB1A,
+    BeiDuoB2a,
+    BeiDuoB2b,
+    BeiDuoB2ab,
+    BeiDuoB3I,
+    BeiDuoB3Q,
+    BeiDuoB3A,
+    BeiDuoB2I,
+    BeiDuoB2Q,
+
+    GLONASSG1CA,
+    GLONASSG1P,
+    GLONASSG2CA,
+    GLONASSG2P,
+
+    GPSL1CA,
+    GPSL1PY,
+    GPSL1M,
+    GPSL2PY,
+    GPSL2CM,
+    GPSL2CL,
+    GPSL5I,
+    GPSL5Q,
+
+    GalileoE5a,
+    GalileoE5b,
+    GalileoE5ab,
+    GalileoE6A,
+    GalileoE6BC,
+    GalileoE1A,
+    GalileoE1BC,
+
+    NavICL5SPS,
+    NavICSSPS,
+    NavICL5RS,
+    NavICSRS,
+    NavICL1SPS,
+
+    QZSSL1CA,
+    QZSSL1CD,
+    QZSSL1CP,
+    QZSSL1S,
+    QZSSL2CM,
+    QZSSL2CL,
+    QZSSL5I,
+    QZSSL5Q,
+    QZSSL6D,
+    QZSSL6E,
+
+    // The signal ID is not known for the system, or the system is not known
+    Unknown(u32),
+}
 
-    // Galileo E1 C
-    // Galileo E1 B
-    E1,
+impl Signal {
+    pub fn new(system: Option<&System>, id: u32) -> Self {
+        let system = match system {
+            Some(s) => s,
+            None => return Signal::Unknown(id),
+        };
+
+        match (system, id) {
+            (_, 0) => Signal::All,
+            (System::BeiDuo, 1) => Signal::BeiDuoB1I,
+            (System::BeiDuo, 2) => Signal::BeiDuoB1Q,
+            (System::BeiDuo, 3) => Signal::BeiDuoB1C,
+            (System::BeiDuo, 4) => Signal::BeiDuoB1A,
+            (System::BeiDuo, 5) => Signal::BeiDuoB2a,
+            (System::BeiDuo, 6) => Signal::BeiDuoB2b,
+            (System::BeiDuo, 7) => Signal::BeiDuoB2ab,
+            (System::BeiDuo, 8) => Signal::BeiDuoB3I,
+            (System::BeiDuo, 9) => Signal::BeiDuoB3Q,
+            (System::BeiDuo, 10) => Signal::BeiDuoB3A,
+            (System::BeiDuo, 11) => Signal::BeiDuoB2I,
+            (System::BeiDuo, 12) => Signal::BeiDuoB2Q,
+            (System::GLONASS, 1) => Signal::GLONASSG1CA,
+            (System::GLONASS, 2) => Signal::GLONASSG1P,
+            (System::GLONASS, 3) => Signal::GLONASSG2CA,
+            (System::GLONASS, 4) => Signal::GLONASSG2P,
+            (System::GPS, 1) => Signal::GPSL1CA,
+            (System::GPS, 2) => Signal::GPSL1PY,
+            (System::GPS, 3) => Signal::GPSL1M,
+            (System::GPS, 4) => Signal::GPSL2PY,
+            (System::GPS, 5) => Signal::GPSL2CM,
+            (System::GPS, 6) => Signal::GPSL2CL,
+            (System::GPS, 7) => Signal::GPSL5I,
+            (System::GPS, 8) => Signal::GPSL5Q,
+            (System::Galileo, 1) => Signal::GalileoE5a,
+            (System::Galileo, 2) => Signal::GalileoE5b,
+            (System::Galileo, 3) => Signal::GalileoE5ab,
+            (System::Galileo, 4) => Signal::GalileoE6A,
+            (System::Galileo, 5) => Signal::GalileoE6BC,
+            (System::Galileo, 6) => Signal::GalileoE1A,
+            (System::Galileo, 7) => Signal::GalileoE1BC,
+            (System::NavIC, 1) => Signal::NavICL5SPS,
+            (System::NavIC, 2) => Signal::NavICSSPS,
+            (System::NavIC, 3) => Signal::NavICL5RS,
+            (System::NavIC, 4) => Signal::NavICSRS,
+            (System::NavIC, 5) => Signal::NavICL1SPS,
+            (System::QZSS, 1) => Signal::QZSSL1CA,
+            (System::QZSS, 2) => Signal::QZSSL1CD,
+            (System::QZSS, 3) => Signal::QZSSL1CP,
+            (System::QZSS, 4) => Signal::QZSSL1S,
+            (System::QZSS, 5) => Signal::QZSSL2CM,
+            (System::QZSS, 6) => Signal::QZSSL2CL,
+            (System::QZSS, 7) => Signal::QZSSL5I,
+            (System::QZSS, 8) => Signal::QZSSL5Q,
+            (System::QZSS, 9) => Signal::QZSSL6D,
+            (System::QZSS, 10) => Signal::QZSSL6E,
+            _ => Signal::Unknown(id),
+        }
+    }
 
-    // BeiDou B2I D1
-    // BeiDou B2I D1
-    B2I,
+    /// The signal ID within its system
+    pub fn id(&self) -> u32 {
+        match self {
+            Signal::All => 0,
+            Signal::BeiDuoB1I
+            | Signal::GLONASSG1CA
+            | Signal::GPSL1CA
+            | Signal::GalileoE5a
+            | Signal::NavICL5SPS
+            | Signal::QZSSL1CA => 1,
+            Signal::BeiDuoB1Q
+            | Signal::GLONASSG1P
+            | Signal::GPSL1PY
+            | Signal::GalileoE5b
+            | Signal::NavICSSPS
+            | Signal::QZSSL1CD => 2,
+            Signal::BeiDuoB1C
+            | Signal::GLONASSG2CA
+            | Signal::GPSL1M
+            | Signal::GalileoE5ab
+            | Signal::NavICL5RS
+            | Signal::QZSSL1CP => 3,
+            Signal::BeiDuoB1A
+            | Signal::GLONASSG2P
+            | Signal::GPSL2PY
+            | Signal::GalileoE6A
+            | Signal::NavICSRS
+            | Signal::QZSSL1S => 4,
+            Signal::BeiDuoB2a
+            | Signal::GPSL2CM
+            | Signal::GalileoE6BC
+            | Signal::NavICL1SPS
+            | Signal::QZSSL2CM => 5,
+            Signal::BeiDuoB2b | Signal::GPSL2CL | Signal::GalileoE1A | Signal::QZSSL2CL => 6,
+            Signal::BeiDuoB2ab | Signal::GPSL5I | Signal::GalileoE1BC | Signal::QZSSL5I => 7,
+            Signal::BeiDuoB3I | Signal::GPSL5Q | Signal::QZSSL5Q => 8,
+            Signal::BeiDuoB3Q | Signal::QZSSL6D => 9,
+            Signal::BeiDuoB3A | Signal::QZSSL6E => 10,
+            Signal::BeiDuoB2I => 11,
+            Signal::BeiDuoB2Q => 12,
+            Signal::Unknown(id) => *id,
+        }
+    }
+}
 
-    Unknown,
+impl Serialize for Signal {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("{:X}", self.id()))
+    }
 }
 
-pub(crate) fn signal<'a>(input: &'a str) -> Result<&'a str, Signal> {
-    map(uint32, |c| match c {
-        1 => Signal::L1,
-        2 => Signal::E5,
-        3 => Signal::L2OF,
-        4 => Signal::L1S,
-        5 => Signal::L2CM,
-        6 => Signal::L2CL,
-        7 => Signal::E1,
-        11 => Signal::B2I,
-        _ => Signal::Unknown,
-    })(input)
+// Signal IDs are a single hexadecimal digit
+pub(crate) fn signal_id<'a>(input: &'a str) -> Result<&'a str, u32> {
+    map(one_of("0123456789ABCDEF"), |c| c.to_digit(16).unwrap())(input)
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -349,6 +721,18 @@ pub enum Status {
     Invalid,
 }
 
+impl Serialize for Status {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_char(match self {
+            Status::Valid => 'A',
+            Status::Invalid => 'V',
+        })
+    }
+}
+
 pub(crate) fn status<'a>(input: &'a str) -> Result<&'a str, Status> {
     map(one_of("AV"), |c| match c {
         'A' => Status::Valid,
@@ -357,14 +741,57 @@ pub(crate) fn status<'a>(input: &'a str) -> Result<&'a str, Status> {
     })(input)
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[serde(try_from = "String")]
 pub enum System {
     BeiDuo,
     GLONASS,
     GPS,
     Galileo,
+    NavIC,
     QZSS,
-    Unknown,
+    Unknown(u32),
+}
+
+impl System {
+    /// The NMEA system ID
+    pub fn id(&self) -> u32 {
+        match self {
+            System::GPS => 1,
+            System::GLONASS => 2,
+            System::Galileo => 3,
+            System::BeiDuo => 4,
+            System::QZSS => 5,
+            System::NavIC => 6,
+            System::Unknown(id) => *id,
+        }
+    }
+}
+
+// A system is written by name in the configuration file
+impl TryFrom<String> for System {
+    type Error = ConfigurationError;
+
+    fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "beidou" => Ok(System::BeiDuo),
+            "glonass" => Ok(System::GLONASS),
+            "gps" => Ok(System::GPS),
+            "galileo" => Ok(System::Galileo),
+            "navic" => Ok(System::NavIC),
+            "qzss" => Ok(System::QZSS),
+            _ => Err(ConfigurationError::InvalidSystem(value)),
+        }
+    }
+}
+
+impl Serialize for System {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u32(self.id())
+    }
 }
 
 pub(crate) fn system<'a>(input: &'a str) -> Result<&'a str, System> {
@@ -374,32 +801,76 @@ pub(crate) fn system<'a>(input: &'a str) -> Result<&'a str, System> {
         3 => System::Galileo,
         4 => System::BeiDuo,
         5 => System::QZSS,
-        _ => System::Unknown,
+        6 => System::NavIC,
+        _ => System::Unknown(c),
     })(input)
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Talker {
+    AIS,
     BeiDuo,
     Combination,
     ECDIS,
     GLONASS,
     GPS,
     Galileo,
+    NavIC,
     Private,
+    QZSS,
     Unknown(String),
 }
 
+impl Talker {
+    /// The system of a single-system talker
+    pub fn system(&self) -> Option<System> {
+        match self {
+            Talker::BeiDuo => Some(System::BeiDuo),
+            Talker::GLONASS => Some(System::GLONASS),
+            Talker::GPS => Some(System::GPS),
+            Talker::Galileo => Some(System::Galileo),
+            Talker::NavIC => Some(System::NavIC),
+            Talker::QZSS => Some(System::QZSS),
+            _ => None,
+        }
+    }
+}
+
+impl Serialize for Talker {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(match self {
+            Talker::AIS => "AI",
+            Talker::BeiDuo => "GB",
+            Talker::Combination => "GN",
+            Talker::ECDIS => "EI",
+            Talker::GLONASS => "GL",
+            Talker::GPS => "GP",
+            Talker::Galileo => "GA",
+            Talker::NavIC => "GI",
+            Talker::Private => "P",
+            Talker::QZSS => "GQ",
+            Talker::Unknown(t) => t,
+        })
+    }
+}
+
 pub(crate) fn talker<'a>(input: &'a str) -> Result<&'a str, Talker> {
     map(
         alt((tag("P"), take_while_m_n(2, 2, is_upper_alphanum))),
         |t| match t {
+            "AI" => Talker::AIS,
+            "BD" => Talker::BeiDuo,
             "EI" => Talker::ECDIS,
             "GA" => Talker::Galileo,
             "GB" => Talker::BeiDuo,
+            "GI" => Talker::NavIC,
             "GL" => Talker::GLONASS,
             "GN" => Talker::Combination,
             "GP" => Talker::GPS,
+            "GQ" => Talker::QZSS,
             "P" => Talker::Private,
             _ => Talker::Unknown(t.to_string()),
         },
@@ -420,6 +891,26 @@ pub struct DTMData {
     pub ref_datum: String,
 }
 
+impl Serialize for DTMData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("DTMData", 10)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "DTM")?;
+        state.serialize_field("datum", &self.datum)?;
+        state.serialize_field("sub_datum", &self.sub_datum)?;
+        state.serialize_field("lat", &self.lat)?;
+        state.serialize_field("north_south", &self.north_south)?;
+        state.serialize_field("lon", &self.lon)?;
+        state.serialize_field("east_west", &self.east_west)?;
+        state.serialize_field("alt", &self.alt)?;
+        state.serialize_field("ref_datum", &self.ref_datum)?;
+        state.end()
+    }
+}
+
 pub(crate) fn dtm<'a>(input: &'a str) -> Result<&'a str, DTMData> {
     parse_message(
         "DTM",
@@ -456,6 +947,19 @@ pub struct GAQData {
     pub message_id: String,
 }
 
+impl Serialize for GAQData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GAQData", 3)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "GAQ")?;
+        state.serialize_field("message_id", &self.message_id)?;
+        state.end()
+    }
+}
+
 pub(crate) fn gaq<'a>(input: &'a str) -> Result<&'a str, GAQData> {
     context(
         "GAQ",
@@ -477,6 +981,19 @@ pub struct GBQData {
     pub message_id: String,
 }
 
+impl Serialize for GBQData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GBQData", 3)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "GBQ")?;
+        state.serialize_field("message_id", &self.message_id)?;
+        state.end()
+    }
+}
+
 pub(crate) fn gbq<'a>(input: &'a str) -> Result<&'a str, GBQData> {
     parse_message(
         "GBQ",
@@ -505,6 +1022,28 @@ pub struct GBSData {
     pub signal: Option<Signal>,
 }
 
+impl Serialize for GBSData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GBSData", 12)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "GBS")?;
+        state.serialize_field("time", &ser_util::time(&self.time))?;
+        state.serialize_field("err_lat", &self.err_lat)?;
+        state.serialize_field("err_lon", &self.err_lon)?;
+        state.serialize_field("err_alt", &self.err_alt)?;
+        state.serialize_field("svid", &ser_util::padded(self.svid, 2))?;
+        state.serialize_field("prob", &self.prob)?;
+        state.serialize_field("bias", &self.bias)?;
+        state.serialize_field("stddev", &self.stddev)?;
+        state.serialize_field("system", &self.system)?;
+        state.serialize_field("signal", &self.signal)?;
+        state.end()
+    }
+}
+
 pub(crate) fn gbs<'a>(input: &'a str) -> Result<&'a str, GBSData> {
     parse_message(
         "GBS",
@@ -519,9 +1058,11 @@ pub(crate) fn gbs<'a>(input: &'a str) -> Result<&'a str, GBSData> {
             terminated(opt(flt32), comma),
             terminated(opt(flt32), comma),
             terminated(opt(system), comma),
-            opt(signal),
+            opt(signal_id),
         )),
         |(talker, time, err_lat, err_lon, err_alt, svid, prob, bias, stddev, system, signal)| {
+            let signal = signal.map(|id| Signal::new(system.as_ref(), id));
+
             GBSData {
                 received: None,
                 talker,
@@ -557,7 +1098,74 @@ pub struct GGAData {
     pub diff_station: Option<u32>,
 }
 
+impl Serialize for GGAData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GGAData", 13)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "GGA")?;
+        state.serialize_field("time", &ser_util::time(&self.time))?;
+        state.serialize_field("lat_lon", &ser_util::latlon(&self.lat_lon))?;
+        state.serialize_field("quality", &self.quality)?;
+        state.serialize_field(
+            "num_satellites",
+            &ser_util::padded(Some(self.num_satellites), 2),
+        )?;
+        state.serialize_field("hdop", &self.hdop)?;
+        state.serialize_field("alt", &self.alt)?;
+        state.serialize_field("alt_unit", &self.alt_unit)?;
+        state.serialize_field("sep", &self.sep)?;
+        state.serialize_field("sep_unit", &self.sep_unit)?;
+        state.serialize_field("diff_age", &self.diff_age)?;
+        state.serialize_field("diff_station", &self.diff_station)?;
+        state.end()
+    }
+}
+
 pub(crate) fn gga<'a>(input: &'a str) -> Result<&'a str, GGAData> {
+    map(gga_view, GGAData::from)(input)
+}
+
+/// A GGA sentence borrowing its units from the input
+#[derive(Clone, Debug, PartialEq)]
+pub struct GGAView<'a> {
+    pub talker: Talker,
+    pub time: NaiveTime,
+    pub lat_lon: Option<LatLon>,
+    pub quality: Quality,
+    pub num_satellites: u32,
+    pub hdop: Option<f32>,
+    pub alt: Option<f32>,
+    pub alt_unit: &'a str,
+    pub sep: Option<f32>,
+    pub sep_unit: &'a str,
+    pub diff_age: Option<u32>,
+    pub diff_station: Option<u32>,
+}
+
+impl From<GGAView<'_>> for GGAData {
+    fn from(view: GGAView<'_>) -> Self {
+        GGAData {
+            received: None,
+            talker: view.talker,
+            time: view.time,
+            lat_lon: view.lat_lon,
+            quality: view.quality,
+            num_satellites: view.num_satellites,
+            hdop: view.hdop,
+            alt: view.alt,
+            alt_unit: view.alt_unit.to_string(),
+            sep: view.sep,
+            sep_unit: view.sep_unit.to_string(),
+            diff_age: view.diff_age,
+            diff_station: view.diff_station,
+        }
+    }
+}
+
+pub(crate) fn gga_view<'a>(input: &'a str) -> Result<&'a str, GGAView<'a>> {
     parse_message(
         "GGA",
         tuple((
@@ -568,9 +1176,9 @@ pub(crate) fn gga<'a>(input: &'a str) -> Result<&'a str, GGAData> {
             terminated(uint32, comma),
             terminated(opt(flt32), comma),
             terminated(opt(flt32), comma),
-            terminated(any, comma),
+            terminated(field, comma),
             terminated(opt(flt32), comma),
-            terminated(any, comma),
+            terminated(field, comma),
             terminated(opt(uint32), comma),
             opt(uint32),
         )),
@@ -587,8 +1195,7 @@ pub(crate) fn gga<'a>(input: &'a str) -> Result<&'a str, GGAData> {
             sep_unit,
             diff_age,
             diff_station,
-        )| GGAData {
-            received: None,
+        )| GGAView {
             talker,
             time,
             lat_lon,
@@ -615,6 +1222,22 @@ pub struct GLLData {
     pub position_mode: PositionMode,
 }
 
+impl Serialize for GLLData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GLLData", 6)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "GLL")?;
+        state.serialize_field("lat_lon", &ser_util::latlon(&self.lat_lon))?;
+        state.serialize_field("time", &ser_util::time(&self.time))?;
+        state.serialize_field("status", &self.status)?;
+        state.serialize_field("position_mode", &self.position_mode)?;
+        state.end()
+    }
+}
+
 pub(crate) fn gll<'a>(input: &'a str) -> Result<&'a str, GLLData> {
     parse_message(
         "GLL",
@@ -636,6 +1259,35 @@ pub(crate) fn gll<'a>(input: &'a str) -> Result<&'a str, GLLData> {
     )(input)
 }
 
+// NMEA 2.x GLL ends at the status
+pub(crate) fn gll_legacy(input: &str) -> Result<&str, GLLData> {
+    parse_message(
+        "GLL legacy",
+        tuple((
+            terminated(talker, tag("GLL")),
+            preceded(comma, latlon),
+            preceded(comma, time),
+            preceded(comma, status),
+        )),
+        |(talker, lat_lon, time, status)| GLLData {
+            received: None,
+            talker,
+            lat_lon,
+            time,
+            position_mode: legacy_pos_mode(&status),
+            status,
+        },
+    )(input)
+}
+
+// The closest mode indicator for NMEA 2.x sentences with only a status
+fn legacy_pos_mode(status: &Status) -> PositionMode {
+    match status {
+        Status::Valid => PositionMode::AutonomousGNSSFix,
+        Status::Invalid => PositionMode::NoFix,
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct GLQData {
     pub received: Option<Duration>,
@@ -643,6 +1295,19 @@ pub struct GLQData {
     pub message_id: String,
 }
 
+impl Serialize for GLQData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GLQData", 3)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "GLQ")?;
+        state.serialize_field("message_id", &self.message_id)?;
+        state.end()
+    }
+}
+
 pub(crate) fn glq<'a>(input: &'a str) -> Result<&'a str, GLQData> {
     parse_message(
         "GLQ",
@@ -662,6 +1327,19 @@ pub struct GNQData {
     pub message_id: String,
 }
 
+impl Serialize for GNQData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GNQData", 3)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "GNQ")?;
+        state.serialize_field("message_id", &self.message_id)?;
+        state.end()
+    }
+}
+
 pub(crate) fn gnq<'a>(input: &'a str) -> Result<&'a str, GNQData> {
     parse_message(
         "GNQ",
@@ -684,6 +1362,8 @@ pub struct GNSData {
     pub glonass_position_mode: PositionMode,
     pub galileo_position_mode: PositionMode,
     pub beiduo_position_mode: PositionMode,
+    pub qzss_position_mode: Option<PositionMode>,
+    pub navic_position_mode: Option<PositionMode>,
     pub num_satellites: u32,
     pub hdop: f32,
     pub alt: f32,
@@ -693,6 +1373,44 @@ pub struct GNSData {
     pub nav_status: Status,
 }
 
+impl Serialize for GNSData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GNSData", 12)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "GNS")?;
+        let modes: String = [
+            Some(&self.gps_position_mode),
+            Some(&self.glonass_position_mode),
+            Some(&self.galileo_position_mode),
+            Some(&self.beiduo_position_mode),
+            self.qzss_position_mode.as_ref(),
+            self.navic_position_mode.as_ref(),
+        ]
+        .iter()
+        .flatten()
+        .map(|m| m.code())
+        .collect();
+
+        state.serialize_field("time", &ser_util::time(&self.time))?;
+        state.serialize_field("lat_lon", &ser_util::latlon(&self.lat_lon))?;
+        state.serialize_field("position_mode", &modes)?;
+        state.serialize_field(
+            "num_satellites",
+            &ser_util::padded(Some(self.num_satellites), 2),
+        )?;
+        state.serialize_field("hdop", &self.hdop)?;
+        state.serialize_field("alt", &self.alt)?;
+        state.serialize_field("sep", &self.sep)?;
+        state.serialize_field("diff_age", &self.diff_age)?;
+        state.serialize_field("diff_station", &self.diff_station)?;
+        state.serialize_field("nav_status", &self.nav_status)?;
+        state.end()
+    }
+}
+
 pub(crate) fn gns<'a>(input: &'a str) -> Result<&'a str, GNSData> {
     parse_message(
         "GNS",
@@ -703,7 +1421,8 @@ pub(crate) fn gns<'a>(input: &'a str) -> Result<&'a str, GNSData> {
             pos_mode,
             pos_mode,
             pos_mode,
-            terminated(pos_mode, comma),
+            pos_mode,
+            terminated(tuple((opt(pos_mode), opt(pos_mode))), comma),
             terminated(uint32, comma),
             terminated(flt32, comma),
             terminated(flt32, comma),
@@ -720,6 +1439,7 @@ pub(crate) fn gns<'a>(input: &'a str) -> Result<&'a str, GNSData> {
             glonass_position_mode,
             galileo_position_mode,
             beiduo_position_mode,
+            (qzss_position_mode, navic_position_mode),
             num_satellites,
             hdop,
             alt,
@@ -736,6 +1456,8 @@ pub(crate) fn gns<'a>(input: &'a str) -> Result<&'a str, GNSData> {
             glonass_position_mode,
             galileo_position_mode,
             beiduo_position_mode,
+            qzss_position_mode,
+            navic_position_mode,
             num_satellites,
             hdop,
             alt,
@@ -754,6 +1476,19 @@ pub struct GPQData {
     pub message_id: String,
 }
 
+impl Serialize for GPQData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GPQData", 3)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "GPQ")?;
+        state.serialize_field("message_id", &self.message_id)?;
+        state.end()
+    }
+}
+
 pub(crate) fn gpq<'a>(input: &'a str) -> Result<&'a str, GPQData> {
     parse_message(
         "GPQ",
@@ -777,6 +1512,25 @@ pub struct GRSData {
     pub signal: Option<Signal>,
 }
 
+impl Serialize for GRSData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GRSData", 6)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "GRS")?;
+        state.serialize_field("time", &ser_util::time(&self.time))?;
+        state.serialize_field("gga_includes_residuals", &self.gga_includes_residuals)?;
+        for residual in &self.residuals {
+            state.serialize_field("residual", residual)?;
+        }
+        state.serialize_field("system", &self.system)?;
+        state.serialize_field("signal", &self.signal)?;
+        state.end()
+    }
+}
+
 pub(crate) fn grs<'a>(input: &'a str) -> Result<&'a str, GRSData> {
     parse_message(
         "GRS",
@@ -786,7 +1540,7 @@ pub(crate) fn grs<'a>(input: &'a str) -> Result<&'a str, GRSData> {
             preceded(comma, map(one_of("10"), |c| c == '1')),
             map(many_m_n(12, 12, preceded(comma, opt(flt32))), Vec::from),
             preceded(comma, system),
-            preceded(comma, opt(signal)),
+            preceded(comma, opt(signal_id)),
         )),
         |(talker, time, gga_includes_residuals, residuals, system, signal)| GRSData {
             received: None,
@@ -794,8 +1548,8 @@ pub(crate) fn grs<'a>(input: &'a str) -> Result<&'a str, GRSData> {
             time,
             gga_includes_residuals,
             residuals,
+            signal: signal.map(|id| Signal::new(Some(&system), id)),
             system,
-            signal,
         },
     )(input)
 }
@@ -813,22 +1567,95 @@ pub struct GSAData {
     pub system: Option<System>,
 }
 
+impl Serialize for GSAData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GSAData", 7)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "GSA")?;
+        state.serialize_field("operation_mode", &self.operation_mode)?;
+        state.serialize_field("navigation_mode", &self.navigation_mode)?;
+        for id in &self.satellite_ids {
+            state.serialize_field("satellite_id", &ser_util::padded(*id, 2))?;
+        }
+        state.serialize_field("pdop", &self.pdop)?;
+        state.serialize_field("hdop", &self.hdop)?;
+        state.serialize_field("vdop", &self.vdop)?;
+
+        if let Some(system) = &self.system {
+            state.serialize_field("system", system)?;
+        }
+        state.end()
+    }
+}
+
 pub(crate) fn gsa<'a>(input: &'a str) -> Result<&'a str, GSAData> {
+    map(gsa_view, GSAData::from)(input)
+}
+
+/// A GSA sentence whose satellite IDs are parsed from the input on demand
+#[derive(Clone, Debug, PartialEq)]
+pub struct GSAView<'a> {
+    pub talker: Talker,
+    pub operation_mode: OperationMode,
+    pub navigation_mode: NavigationMode,
+    satellite_ids: &'a str,
+    pub pdop: Option<f32>,
+    pub hdop: Option<f32>,
+    pub vdop: Option<f32>,
+    pub system: Option<System>,
+}
+
+impl<'a> GSAView<'a> {
+    /// The 12 satellite ID fields
+    pub fn satellite_ids(&self) -> impl Iterator<Item = Option<u32>> + 'a {
+        let mut input = self.satellite_ids;
+
+        std::iter::from_fn(move || {
+            let (rest, id) = gsa_satellite_id(input).ok()?;
+            input = rest;
+            Some(id)
+        })
+    }
+}
+
+impl From<GSAView<'_>> for GSAData {
+    fn from(view: GSAView<'_>) -> Self {
+        GSAData {
+            received: None,
+            satellite_ids: view.satellite_ids().collect(),
+            talker: view.talker,
+            operation_mode: view.operation_mode,
+            navigation_mode: view.navigation_mode,
+            pdop: view.pdop,
+            hdop: view.hdop,
+            vdop: view.vdop,
+            system: view.system,
+        }
+    }
+}
+
+fn gsa_satellite_id(input: &str) -> Result<&str, Option<u32>> {
+    terminated(opt(uint32), comma)(input)
+}
+
+pub(crate) fn gsa_view<'a>(input: &'a str) -> Result<&'a str, GSAView<'a>> {
     parse_message(
         "GSA",
         tuple((
             terminated(talker, terminated(tag("GSA"), comma)),
             terminated(op_mode, comma),
             terminated(nav_mode, comma),
-            map(many_m_n(12, 12, terminated(opt(uint32), comma)), Vec::from),
+            recognize(fold_many_m_n(12, 12, gsa_satellite_id, || (), |_, _| ())),
             terminated(opt(flt32), comma),
             terminated(opt(flt32), comma),
             opt(flt32),
             opt(preceded(comma, system)),
         )),
         |(talker, operation_mode, navigation_mode, satellite_ids, pdop, hdop, vdop, system)| {
-            GSAData {
-                received: None,
+            GSAView {
                 talker,
                 operation_mode,
                 navigation_mode,
@@ -856,6 +1683,26 @@ pub struct GSTData {
     pub std_alt: Option<f32>,
 }
 
+impl Serialize for GSTData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GSTData", 10)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "GST")?;
+        state.serialize_field("time", &ser_util::time(&self.time))?;
+        state.serialize_field("range_rms", &self.range_rms)?;
+        state.serialize_field("std_major", &self.std_major)?;
+        state.serialize_field("std_minor", &self.std_minor)?;
+        state.serialize_field("orientation", &self.orientation)?;
+        state.serialize_field("std_lat", &self.std_lat)?;
+        state.serialize_field("std_lon", &self.std_lon)?;
+        state.serialize_field("std_alt", &self.std_alt)?;
+        state.end()
+    }
+}
+
 pub(crate) fn gst<'a>(input: &'a str) -> Result<&'a str, GSTData> {
     parse_message(
         "GST",
@@ -931,10 +1778,83 @@ pub struct GSVData {
     pub msg: u32,
     pub num_satellites: u32,
     pub satellites: Vec<GSVsatellite>,
+    pub system: Option<System>,
     pub signal: Option<Signal>,
 }
 
+impl Serialize for GSVData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GSVData", 5)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "GSV")?;
+        state.serialize_field("num_msgs", &self.num_msgs)?;
+        state.serialize_field("msg", &self.msg)?;
+        state.serialize_field(
+            "num_satellites",
+            &ser_util::padded(Some(self.num_satellites), 2),
+        )?;
+        for satellite in &self.satellites {
+            state.serialize_field("id", &ser_util::padded(Some(satellite.id), 2))?;
+            state.serialize_field("elevation", &ser_util::padded(satellite.elevation, 2))?;
+            state.serialize_field("azimuth", &ser_util::padded(satellite.azimuth, 3))?;
+            state.serialize_field("cno", &ser_util::padded(satellite.cno, 2))?;
+        }
+
+        if let Some(signal) = &self.signal {
+            state.serialize_field("signal", signal)?;
+        }
+        state.end()
+    }
+}
+
 pub(crate) fn gsv<'a>(input: &'a str) -> Result<&'a str, GSVData> {
+    map(gsv_view, GSVData::from)(input)
+}
+
+/// A GSV sentence whose satellites are parsed from the input on demand
+#[derive(Clone, Debug, PartialEq)]
+pub struct GSVView<'a> {
+    pub talker: Talker,
+    pub num_msgs: u32,
+    pub msg: u32,
+    pub num_satellites: u32,
+    satellites: &'a str,
+    pub system: Option<System>,
+    pub signal: Option<Signal>,
+}
+
+impl<'a> GSVView<'a> {
+    /// The up to four satellites in this sentence
+    pub fn satellites(&self) -> impl Iterator<Item = GSVsatellite> + 'a {
+        let mut input = self.satellites;
+
+        std::iter::from_fn(move || {
+            let (rest, satellite) = gsv_sat(input).ok()?;
+            input = rest;
+            Some(satellite)
+        })
+    }
+}
+
+impl From<GSVView<'_>> for GSVData {
+    fn from(view: GSVView<'_>) -> Self {
+        GSVData {
+            received: None,
+            satellites: view.satellites().collect(),
+            talker: view.talker,
+            num_msgs: view.num_msgs,
+            msg: view.msg,
+            num_satellites: view.num_satellites,
+            system: view.system,
+            signal: view.signal,
+        }
+    }
+}
+
+pub(crate) fn gsv_view<'a>(input: &'a str) -> Result<&'a str, GSVView<'a>> {
     parse_message(
         "GSV",
         tuple((
@@ -942,17 +1862,61 @@ pub(crate) fn gsv<'a>(input: &'a str) -> Result<&'a str, GSVData> {
             preceded(comma, uint32),
             preceded(comma, uint32),
             preceded(comma, uint32),
-            many_m_n(0, 4, gsv_sat),
-            opt(preceded(comma, opt(signal))),
+            recognize(fold_many_m_n(0, 4, gsv_sat, || (), |_, _| ())),
+            opt(preceded(comma, opt(signal_id))),
+        )),
+        |(talker, num_msgs, msg, num_satellites, satellites, signal)| {
+            let system = talker.system();
+            let signal = signal
+                .unwrap_or(None)
+                .map(|id| Signal::new(system.as_ref(), id));
+
+            GSVView {
+                talker,
+                num_msgs,
+                msg,
+                num_satellites,
+                satellites,
+                system,
+                signal,
+            }
+        },
+    )(input)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HDTData {
+    pub received: Option<Duration>,
+    pub talker: Talker,
+    /// True heading in degrees
+    pub heading: Option<f32>,
+}
+
+impl Serialize for HDTData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("HDTData", 4)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "HDT")?;
+        state.serialize_field("heading", &self.heading)?;
+        state.serialize_field("true", "T")?;
+        state.end()
+    }
+}
+
+pub(crate) fn hdt(input: &str) -> Result<&str, HDTData> {
+    parse_message(
+        "HDT",
+        tuple((
+            terminated(talker, terminated(tag("HDT"), comma)),
+            terminated(opt(flt32), tuple((comma, char('T')))),
         )),
-        |(talker, num_msgs, msg, num_satellites, satellites, signal)| GSVData {
+        |(talker, heading)| HDTData {
             received: None,
             talker,
-            num_msgs,
-            msg,
-            num_satellites,
-            satellites,
-            signal: signal.unwrap_or(None),
+            heading,
         },
     )(input)
 }
@@ -973,6 +1937,34 @@ pub struct RMCData {
     pub nav_status: Option<Status>,
 }
 
+impl Serialize for RMCData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("RMCData", 11)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "RMC")?;
+        state.serialize_field("time", &ser_util::time(&self.time))?;
+        state.serialize_field("status", &self.status)?;
+        state.serialize_field("lat_lon", &ser_util::latlon(&self.lat_lon))?;
+        state.serialize_field("speed", &self.speed)?;
+        state.serialize_field("course_over_ground", &self.course_over_ground)?;
+        state.serialize_field("date", &ser_util::date(&self.date))?;
+        state.serialize_field("magnetic_variation", &self.magnetic_variation)?;
+        state.serialize_field(
+            "magnetic_variation_east_west",
+            &self.magnetic_variation_east_west,
+        )?;
+        state.serialize_field("position_mode", &self.position_mode)?;
+
+        if let Some(nav_status) = &self.nav_status {
+            state.serialize_field("nav_status", nav_status)?;
+        }
+        state.end()
+    }
+}
+
 pub(crate) fn rmc<'a>(input: &'a str) -> Result<&'a str, RMCData> {
     parse_message(
         "RMC",
@@ -1018,6 +2010,128 @@ pub(crate) fn rmc<'a>(input: &'a str) -> Result<&'a str, RMCData> {
     )(input)
 }
 
+// NMEA 2.x RMC ends at the magnetic variation direction
+pub(crate) fn rmc_legacy(input: &str) -> Result<&str, RMCData> {
+    parse_message(
+        "RMC legacy",
+        tuple((
+            terminated(talker, tag("RMC")),
+            preceded(comma, time),
+            preceded(comma, status),
+            preceded(comma, latlon),
+            preceded(comma, flt32),
+            preceded(comma, opt(flt32)),
+            preceded(comma, date),
+            preceded(comma, opt(flt32)),
+            preceded(comma, opt(east_west)),
+        )),
+        |(
+            talker,
+            time,
+            status,
+            lat_lon,
+            speed,
+            course_over_ground,
+            date,
+            magnetic_variation,
+            magnetic_variation_east_west,
+        )| RMCData {
+            received: None,
+            talker,
+            time,
+            position_mode: legacy_pos_mode(&status),
+            status,
+            lat_lon,
+            speed,
+            course_over_ground,
+            date,
+            magnetic_variation,
+            magnetic_variation_east_west,
+            nav_status: None,
+        },
+    )(input)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ROTData {
+    pub received: Option<Duration>,
+    pub talker: Talker,
+    /// Degrees per minute, negative when turning to port
+    pub rate_of_turn: Option<f32>,
+    pub status: Status,
+}
+
+impl Serialize for ROTData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("ROTData", 4)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "ROT")?;
+        state.serialize_field("rate_of_turn", &self.rate_of_turn)?;
+        state.serialize_field("status", &self.status)?;
+        state.end()
+    }
+}
+
+pub(crate) fn rot(input: &str) -> Result<&str, ROTData> {
+    parse_message(
+        "ROT",
+        tuple((
+            terminated(talker, terminated(tag("ROT"), comma)),
+            terminated(opt(flt32), comma),
+            status,
+        )),
+        |(talker, rate_of_turn, status)| ROTData {
+            received: None,
+            talker,
+            rate_of_turn,
+            status,
+        },
+    )(input)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct THSData {
+    pub received: Option<Duration>,
+    pub talker: Talker,
+    /// True heading in degrees
+    pub heading: Option<f32>,
+    pub mode: HeadingMode,
+}
+
+impl Serialize for THSData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("THSData", 4)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "THS")?;
+        state.serialize_field("heading", &self.heading)?;
+        state.serialize_field("mode", &self.mode)?;
+        state.end()
+    }
+}
+
+pub(crate) fn ths(input: &str) -> Result<&str, THSData> {
+    parse_message(
+        "THS",
+        tuple((
+            terminated(talker, terminated(tag("THS"), comma)),
+            terminated(opt(flt32), comma),
+            heading_mode,
+        )),
+        |(talker, heading, mode)| THSData {
+            received: None,
+            talker,
+            heading,
+            mode,
+        },
+    )(input)
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct TXTData {
     pub received: Option<Duration>,
@@ -1028,7 +2142,50 @@ pub struct TXTData {
     pub text: String,
 }
 
+impl Serialize for TXTData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("TXTData", 6)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "TXT")?;
+        state.serialize_field("num_msgs", &ser_util::padded(Some(self.num_msgs), 2))?;
+        state.serialize_field("msg", &ser_util::padded(Some(self.msg), 2))?;
+        state.serialize_field("msg_type", &self.msg_type)?;
+        state.serialize_field("text", &self.text)?;
+        state.end()
+    }
+}
+
 pub(crate) fn txt<'a>(input: &'a str) -> Result<&'a str, TXTData> {
+    map(txt_view, TXTData::from)(input)
+}
+
+/// A TXT sentence borrowing its text from the input
+#[derive(Clone, Debug, PartialEq)]
+pub struct TXTView<'a> {
+    pub talker: Talker,
+    pub num_msgs: u32,
+    pub msg: u32,
+    pub msg_type: MessageType,
+    pub text: &'a str,
+}
+
+impl From<TXTView<'_>> for TXTData {
+    fn from(view: TXTView<'_>) -> Self {
+        TXTData {
+            received: None,
+            talker: view.talker,
+            num_msgs: view.num_msgs,
+            msg: view.msg,
+            msg_type: view.msg_type,
+            text: view.text.to_string(),
+        }
+    }
+}
+
+pub(crate) fn txt_view<'a>(input: &'a str) -> Result<&'a str, TXTView<'a>> {
     parse_message(
         "TXT",
         tuple((
@@ -1036,10 +2193,9 @@ pub(crate) fn txt<'a>(input: &'a str) -> Result<&'a str, TXTData> {
             terminated(uint32, comma),
             terminated(uint32, comma),
             terminated(msg_type, comma),
-            any,
+            field,
         )),
-        |(talker, num_msgs, msg, msg_type, text)| TXTData {
-            received: None,
+        |(talker, num_msgs, msg, msg_type, text)| TXTView {
             talker,
             num_msgs,
             msg,
@@ -1063,6 +2219,29 @@ pub struct VLWData {
     pub ground_distance_unit: String,
 }
 
+impl Serialize for VLWData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("VLWData", 10)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "VLW")?;
+        state.serialize_field("total_water_distance", &self.total_water_distance)?;
+        state.serialize_field("total_water_distance_unit", &self.total_water_distance_unit)?;
+        state.serialize_field("water_distance", &self.water_distance)?;
+        state.serialize_field("water_distance_unit", &self.water_distance_unit)?;
+        state.serialize_field("total_ground_distance", &self.total_ground_distance)?;
+        state.serialize_field(
+            "total_ground_distance_unit",
+            &self.total_ground_distance_unit,
+        )?;
+        state.serialize_field("ground_distance", &self.ground_distance)?;
+        state.serialize_field("ground_distance_unit", &self.ground_distance_unit)?;
+        state.end()
+    }
+}
+
 pub(crate) fn vlw<'a>(input: &'a str) -> Result<&'a str, VLWData> {
     parse_message(
         "VLW",
@@ -1117,19 +2296,89 @@ pub struct VTGData {
     pub position_mode: PositionMode,
 }
 
+impl Serialize for VTGData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("VTGData", 11)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "VTG")?;
+        state.serialize_field("course_over_ground_true", &self.course_over_ground_true)?;
+        state.serialize_field(
+            "course_over_ground_true_unit",
+            &self.course_over_ground_true_unit,
+        )?;
+        state.serialize_field(
+            "course_over_ground_magnetic",
+            &self.course_over_ground_magnetic,
+        )?;
+        state.serialize_field(
+            "course_over_ground_magnetic_unit",
+            &self.course_over_ground_magnetic_unit,
+        )?;
+        state.serialize_field("speed_over_ground_knots", &self.speed_over_ground_knots)?;
+        state.serialize_field(
+            "speed_over_ground_knots_unit",
+            &self.speed_over_ground_knots_unit,
+        )?;
+        state.serialize_field("speed_over_ground_km", &self.speed_over_ground_km)?;
+        state.serialize_field("speed_over_ground_km_unit", &self.speed_over_ground_km_unit)?;
+        state.serialize_field("position_mode", &self.position_mode)?;
+        state.end()
+    }
+}
+
 pub(crate) fn vtg<'a>(input: &'a str) -> Result<&'a str, VTGData> {
+    map(vtg_view, VTGData::from)(input)
+}
+
+/// A VTG sentence borrowing its units from the input
+#[derive(Clone, Debug, PartialEq)]
+pub struct VTGView<'a> {
+    pub talker: Talker,
+    pub course_over_ground_true: Option<f32>,
+    pub course_over_ground_true_unit: &'a str,
+    pub course_over_ground_magnetic: Option<f32>,
+    pub course_over_ground_magnetic_unit: &'a str,
+    pub speed_over_ground_knots: f32,
+    pub speed_over_ground_knots_unit: &'a str,
+    pub speed_over_ground_km: f32,
+    pub speed_over_ground_km_unit: &'a str,
+    pub position_mode: PositionMode,
+}
+
+impl From<VTGView<'_>> for VTGData {
+    fn from(view: VTGView<'_>) -> Self {
+        VTGData {
+            received: None,
+            talker: view.talker,
+            course_over_ground_true: view.course_over_ground_true,
+            course_over_ground_true_unit: view.course_over_ground_true_unit.to_string(),
+            course_over_ground_magnetic: view.course_over_ground_magnetic,
+            course_over_ground_magnetic_unit: view.course_over_ground_magnetic_unit.to_string(),
+            speed_over_ground_knots: view.speed_over_ground_knots,
+            speed_over_ground_knots_unit: view.speed_over_ground_knots_unit.to_string(),
+            speed_over_ground_km: view.speed_over_ground_km,
+            speed_over_ground_km_unit: view.speed_over_ground_km_unit.to_string(),
+            position_mode: view.position_mode,
+        }
+    }
+}
+
+pub(crate) fn vtg_view<'a>(input: &'a str) -> Result<&'a str, VTGView<'a>> {
     parse_message(
         "VTG",
         tuple((
             terminated(talker, tag("VTG")),
             preceded(comma, opt(flt32)),
-            preceded(comma, any),
+            preceded(comma, field),
             preceded(comma, opt(flt32)),
-            preceded(comma, any),
+            preceded(comma, field),
             preceded(comma, flt32),
-            preceded(comma, any),
+            preceded(comma, field),
             preceded(comma, flt32),
-            preceded(comma, any),
+            preceded(comma, field),
             preceded(comma, pos_mode),
         )),
         |(
@@ -1143,8 +2392,7 @@ pub(crate) fn vtg<'a>(input: &'a str) -> Result<&'a str, VTGData> {
             speed_over_ground_km,
             speed_over_ground_km_unit,
             position_mode,
-        )| VTGData {
-            received: None,
+        )| VTGView {
             talker,
             course_over_ground_true,
             course_over_ground_true_unit,
@@ -1171,6 +2419,27 @@ pub struct ZDAData {
     pub local_tz_minute: u32,
 }
 
+impl Serialize for ZDAData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("ZDAData", 8)?;
+        state.serialize_field("no comma", &self.talker)?;
+        state.serialize_field("no comma", "ZDA")?;
+        state.serialize_field("time", &self.time.as_ref().map(ser_util::time))?;
+        state.serialize_field("day", &ser_util::padded(self.day, 2))?;
+        state.serialize_field("month", &ser_util::padded(self.month, 2))?;
+        state.serialize_field("year", &self.year.map(|y| format!("{:04}", y)))?;
+        state.serialize_field("local_tz_hour", &format!("{:02}", self.local_tz_hour))?;
+        state.serialize_field(
+            "local_tz_minute",
+            &ser_util::padded(Some(self.local_tz_minute), 2),
+        )?;
+        state.end()
+    }
+}
+
 pub(crate) fn zda<'a>(input: &'a str) -> Result<&'a str, ZDAData> {
     parse_message(
         "ZDA",
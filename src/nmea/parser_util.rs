@@ -1,3 +1,4 @@
+use crate::gps::{build_date, full_year};
 use crate::nmea::parser::Result;
 use chrono::naive::{NaiveDate, NaiveTime};
 use nom::{
@@ -9,22 +10,29 @@ use nom::{
     number::complete::recognize_float,
     sequence::{preceded, terminated, tuple},
 };
+use serde::{Serialize, Serializer};
 
 pub(crate) fn any<'a>(input: &'a str) -> Result<&'a str, String> {
-    map(take_while(|c| c != ','), |m: &str| m.to_string())(input)
+    map(field, |m: &str| m.to_string())(input)
 }
 
 pub(crate) fn comma<'a>(input: &'a str) -> Result<&'a str, &'a str> {
     tag(",")(input)
 }
 
+// A ddmmyy date in the century nearest the build date, GPS week number rollover is corrected later
 pub(crate) fn date<'a>(input: &'a str) -> Result<&'a str, NaiveDate> {
     map_opt(
         tuple((two_digit, two_digit, two_digit_i)),
-        |(day, month, year)| NaiveDate::from_ymd_opt(year, month, day),
+        |(day, month, year)| NaiveDate::from_ymd_opt(full_year(year, build_date()), month, day),
     )(input)
 }
 
+// A field borrowed from the input
+pub(crate) fn field(input: &str) -> Result<&str, &str> {
+    take_while(|c| c != ',')(input)
+}
+
 pub(crate) fn dot<'a>(input: &'a str) -> Result<&'a str, &'a str> {
     tag(".")(input)
 }
@@ -35,6 +43,18 @@ pub enum EastWest {
     West,
 }
 
+impl Serialize for EastWest {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_char(match self {
+            EastWest::East => 'E',
+            EastWest::West => 'W',
+        })
+    }
+}
+
 pub(crate) fn east_west<'a>(input: &'a str) -> Result<&'a str, EastWest> {
     map(one_of("EW"), |ew| match ew {
         'E' => EastWest::East,
@@ -47,6 +67,10 @@ pub(crate) fn flt32<'a>(input: &'a str) -> Result<&'a str, f32> {
     map_res(recognize_float, |s: &str| s.parse())(input)
 }
 
+pub(crate) fn flt64(input: &str) -> Result<&str, f64> {
+    map_res(recognize_float, |s: &str| s.parse())(input)
+}
+
 pub(crate) fn is_digit(chr: char) -> bool {
     chr.is_ascii_digit()
 }
@@ -113,6 +137,18 @@ pub enum NorthSouth {
     South,
 }
 
+impl Serialize for NorthSouth {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_char(match self {
+            NorthSouth::North => 'N',
+            NorthSouth::South => 'S',
+        })
+    }
+}
+
 pub(crate) fn north_south<'a>(input: &'a str) -> Result<&'a str, NorthSouth> {
     map(one_of("NS"), |ns| match ns {
         'N' => NorthSouth::North,
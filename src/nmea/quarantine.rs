@@ -0,0 +1,45 @@
+// Records input a device sent that couldn't be parsed
+
+use crate::nmea::ParseError;
+use std::{io, path::Path};
+use tokio::{
+    fs::{File, OpenOptions},
+    io::AsyncWriteExt,
+};
+
+/// Appends parse errors to a file, one per line
+pub struct Quarantine {
+    file: File,
+}
+
+impl Quarantine {
+    pub async fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?;
+
+        Ok(Quarantine { file })
+    }
+
+    pub async fn write(&mut self, error: &ParseError) -> io::Result<()> {
+        self.file.write_all(line(error).as_bytes()).await
+    }
+}
+
+/// The tab-separated received time, error kind, offset, and escaped sentence for `error`
+pub fn line(error: &ParseError) -> String {
+    let received = match error.received {
+        Some(r) => format!("{}.{:09}", r.as_secs(), r.subsec_nanos()),
+        None => "-".to_string(),
+    };
+
+    format!(
+        "{}\t{}\t{}\t{}\n",
+        received,
+        error.kind.name(),
+        error.offset,
+        error.sentence.escape_default()
+    )
+}
@@ -1,35 +1,44 @@
-use crate::nmea::parser::{ChecksumMismatch, Result};
-use chrono::NaiveDateTime;
+use crate::nmea::{parser::Result, ParseErrorKind, ParseProfile};
+use crate::rtcm::PREAMBLE;
+use crate::ubx::frame::SYNC_CHAR_1;
 use nom::{
+    branch::alt,
     bytes::streaming::{tag, take_while_m_n},
     character::is_hex_digit,
     combinator::{cut, map, opt, peek, recognize},
     error::context,
-    sequence::{delimited, preceded, terminated, tuple},
+    sequence::{preceded, terminated, tuple},
     Err, Needed,
 };
-use std::convert::TryInto;
-use std::time::Duration;
 use tracing::{error, trace};
 
 #[derive(Debug)]
 pub enum NMEASentence<'a> {
-    InvalidChecksum(ChecksumMismatch),
-    ParseError(String),
+    /// Input that couldn't be framed as a sentence or failed its checksum
+    Invalid {
+        data: &'a [u8],
+        kind: ParseErrorKind,
+        offset: usize,
+    },
     Valid(&'a str),
+    /// A `!` sentence encapsulating binary data like AIS
+    Encapsulated(&'a str),
 }
 
-pub(crate) fn parse_sentence<'a>(
-    input: &'a [u8],
-    received: Duration,
-) -> Result<&'a [u8], NMEASentence<'a>> {
-    let result = delimited(
-        preceded(garbage, tag(b"$")),
-        tuple((terminated(non_star, star), checksum)),
+pub(crate) fn parse_sentence(
+    input: &[u8],
+    profile: ParseProfile,
+) -> Result<&[u8], NMEASentence<'_>> {
+    let result = terminated(
+        tuple((
+            preceded(garbage, alt((tag(b"$"), tag(b"!")))),
+            non_star,
+            opt(preceded(star, checksum_digits)),
+        )),
         terminated(opt(tag(b"\r")), tag(b"\n")),
     )(input);
 
-    let (input, (data, given)) = match result {
+    let (input, (start, data, given)) = match result {
         Err(Err::Incomplete(_)) => {
             return Err(result.err().unwrap());
         }
@@ -37,41 +46,78 @@ pub(crate) fn parse_sentence<'a>(
         Ok(t) => t,
     };
 
+    let invalid = |kind, offset| Ok((input, NMEASentence::Invalid { data, kind, offset }));
+    let valid = |text| match start {
+        b"!" => NMEASentence::Encapsulated(text),
+        _ => NMEASentence::Valid(text),
+    };
+
     let calculated = data.iter().fold(0, |c, b| c ^ b);
-    let data = std::str::from_utf8(data).unwrap();
+    let text = match std::str::from_utf8(data) {
+        Ok(d) => d,
+        Err(e) => {
+            error!("Invalid UTF-8 in sentence {:?}: {}", data, e);
 
-    let result = if given == calculated {
-        trace!(
-            "received {:?} parsing \"{}\" (checksum OK), {} bytes remaining",
-            NaiveDateTime::from_timestamp(
-                received.as_secs().try_into().unwrap_or(0),
-                received.subsec_nanos()
-            ),
-            data,
-            input.len()
-        );
+            return invalid(ParseErrorKind::InvalidUTF8, e.valid_up_to());
+        }
+    };
+
+    // the $, *, checksum, and line ending are counted as if present
+    let length = data.len() + 6;
+
+    if let Some(max) = profile.max_length().filter(|max| length > *max) {
+        error!("sentence \"{}\" is too long ({} bytes)", text, length);
+
+        return invalid(ParseErrorKind::TooLong { length, max }, max);
+    }
+
+    let given = match given {
+        Some(g) if profile.uppercase_checksum() && g.iter().any(u8::is_ascii_lowercase) => {
+            error!("lowercase checksum for \"{}\"", text);
+
+            return invalid(ParseErrorKind::LowercaseChecksum, data.len());
+        }
+        Some(g) => hex(g),
+        None if profile.checksum_required() => {
+            error!("missing checksum for \"{}\"", text);
 
-        NMEASentence::Valid(data)
-    } else {
+            return invalid(ParseErrorKind::MissingChecksum, data.len());
+        }
+        None => {
+            trace!(
+                "parsing \"{}\" (no checksum), {} bytes remaining",
+                text,
+                input.len()
+            );
+
+            return Ok((input, valid(text)));
+        }
+    };
+
+    if given != calculated {
         trace!(
             "invalid checksum for \"{}\" ({} != {})",
-            data,
+            text,
             given,
             calculated
         );
 
-        let message = String::from(data);
+        return invalid(
+            ParseErrorKind::ChecksumMismatch { given, calculated },
+            data.len(),
+        );
+    }
 
-        NMEASentence::InvalidChecksum(ChecksumMismatch {
-            message,
-            given,
-            calculated,
-        })
-    };
+    trace!(
+        "parsing \"{}\" (checksum OK), {} bytes remaining",
+        text,
+        input.len()
+    );
 
-    Ok((input, result))
+    Ok((input, valid(text)))
 }
 
+// Discards the input, too much garbage was found or the sentence couldn't be framed
 fn parse_error<'a>(
     input: &'a [u8],
     e: nom::Err<nom::error::VerboseError<&'a [u8]>>,
@@ -83,47 +129,55 @@ fn parse_error<'a>(
         Err::Failure(_) => "(failure)".to_string(),
     };
 
-    match std::str::from_utf8(input) {
-        Ok(i) => {
-            error!("Error {:?} parsing {}", error, i);
+    error!("Error {:?} parsing {:?}", error, input);
 
-            Ok((b"", NMEASentence::ParseError(String::from(i))))
-        }
-        Err(e) => {
-            error!("Some error parsing: {:?} (invalid UTF-8: {})", input, e);
-
-            let next_invalid = e.valid_up_to() + e.error_len().unwrap_or(1);
+    let invalid = NMEASentence::Invalid {
+        data: input,
+        kind: ParseErrorKind::Garbage {
+            skipped: input.len(),
+        },
+        offset: 0,
+    };
 
-            Ok((
-                &input[next_invalid..],
-                NMEASentence::ParseError(String::from("Invalid UTF-8")),
-            ))
-        }
-    }
+    Ok((b"", invalid))
 }
 
+// Skips bytes up to the start of an NMEA or encapsulated sentence, UBX frame, or RTCM 3 frame
 pub(crate) fn garbage<'a>(input: &'a [u8]) -> Result<&'a [u8], usize> {
     context(
         "garbage",
         cut(terminated(
-            map(take_while_m_n(0, 164, |c| c != b'$'), |g: &[u8]| g.len()),
-            peek(tag(b"$")),
+            map(
+                take_while_m_n(0, 164, |c| {
+                    c != b'$' && c != b'!' && c != SYNC_CHAR_1 && c != PREAMBLE
+                }),
+                |g: &[u8]| g.len(),
+            ),
+            peek(alt((
+                tag(b"$"),
+                tag(b"!"),
+                tag([SYNC_CHAR_1]),
+                tag([PREAMBLE]),
+            ))),
         )),
     )(input)
 }
 
+// Sentence data ends at the checksum or, when the checksum is missing, at the line ending
 pub(crate) fn non_star<'a>(input: &'a [u8]) -> Result<&'a [u8], &'a [u8]> {
     use nom::bytes::streaming::take_till;
 
-    recognize(take_till(|c| c == b'*'))(input)
+    recognize(take_till(|c| c == b'*' || c == b'\r' || c == b'\n'))(input)
 }
 
 pub(crate) fn star<'a>(input: &'a [u8]) -> Result<&'a [u8], &'a [u8]> {
     tag(b"*")(input)
 }
 
-pub(crate) fn checksum<'a>(input: &'a [u8]) -> Result<&'a [u8], u8> {
-    map(recognize(take_while_m_n(2, 2, is_hex_digit)), |c| {
-        u8::from_str_radix(std::str::from_utf8(c).unwrap(), 16).unwrap()
-    })(input)
+fn checksum_digits<'a>(input: &'a [u8]) -> Result<&'a [u8], &'a [u8]> {
+    recognize(take_while_m_n(2, 2, is_hex_digit))(input)
+}
+
+fn hex(digits: &[u8]) -> u8 {
+    u8::from_str_radix(std::str::from_utf8(digits).unwrap(), 16).unwrap()
 }
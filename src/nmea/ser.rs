@@ -46,6 +46,18 @@ where
     Ok(serializer.output)
 }
 
+/// Serializes `value` as a complete sentence with leading `$`, checksum, and line ending
+pub fn to_sentence<T>(value: &T) -> NResult<String>
+where
+    T: Serialize,
+{
+    let message = to_string(value)?;
+
+    let checksum = message.bytes().fold(0, |c, b| c ^ b);
+
+    Ok(format!("${}*{:02X}\r\n", message, checksum))
+}
+
 impl<'a> ser::Serializer for &'a mut ToNMEA {
     type Ok = ();
     type Error = Error;
@@ -173,7 +185,45 @@ impl<'a> ser::Serializer for &'a mut ToNMEA {
             "UBXConfig" => self.output += "PUBX,41",
             "UBXPort" => {}
             "UBXPortMask" => {}
-            "MKTSetNMEAOutput" => self.output += "PMKT314",
+            "MKTSetNMEAOutput" => self.output += "PMTK314",
+            "UBXPosition" => self.output += "PUBX,00",
+            "UBXSatellites" => self.output += "PUBX,03",
+            "UBXTime" => self.output += "PUBX,04",
+            "MKTAcknowledge" => self.output += "PMTK001",
+            "MKTSystemMessage" => self.output += "PMTK010",
+            "MKTTextMessage" => self.output += "PMTK011",
+            "MKTHotRestart" => self.output += "PMTK101",
+            "MKTWarmRestart" => self.output += "PMTK102",
+            "MKTColdRestart" => self.output += "PMTK103",
+            "MKTFullColdRestart" => self.output += "PMTK104",
+            "MKTSetFixInterval" => self.output += "PMTK220",
+            "MKTSetBaudRate" => self.output += "PMTK251",
+            "MKTSetPPSSync" => self.output += "PMTK255",
+            "MKTSetPPS" => self.output += "PMTK285",
+            "MKTReleasePoll" => self.output += "PMTK605",
+            "MKTRelease" => self.output += "PMTK705",
+            "QuectelAcknowledge" => self.output += "PAIR001",
+            "QuectelSetFixInterval" => self.output += "PAIR050",
+            "QuectelSetNMEAOutputRate" => self.output += "PAIR062",
+            "QuectelSetPPS" => self.output += "PAIR752",
+            "QuectelPVT" => self.output += "PQTMPVT",
+            "QuectelVersion" => self.output += "PQTMVERNO",
+            "QuectelVersionPoll" => self.output += "PQTMVERNO",
+            "SiRFOkToSend" => self.output += "PSRF150",
+            "SiRFEphemerisRequest" => self.output += "PSRF151",
+            "SiRFExtendedEphemeris" => self.output += "PSRF156",
+            "SiRFSetRate" => self.output += "PSRF103",
+            "GarminErrorEstimate" => self.output += "PGRME",
+            "GarminFix" => self.output += "PGRMF",
+            "GarminSensorStatus" => self.output += "PGRMT",
+            "GarminSetOutput" => self.output += "PGRMO",
+            // the command name is the leading field
+            "QuectelResponse" => {}
+            // the talker and sentence ID are the leading fields
+            "DTMData" | "GAQData" | "GBQData" | "GBSData" | "GGAData" | "GLLData" | "GLQData"
+            | "GNQData" | "GNSData" | "GPQData" | "GRSData" | "GSAData" | "GSTData" | "GSVData"
+            | "HDTData" | "RMCData" | "ROTData" | "THSData" | "TXTData" | "VLWData" | "VTGData"
+            | "ZDAData" => {}
             _ => panic!("don't know how to serialize struct {}", name),
         }
 
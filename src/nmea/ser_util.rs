@@ -0,0 +1,82 @@
+// Field formatting for serializing parsed sentences, the inverse of parser_util
+
+use crate::nmea::LatLon;
+use chrono::{
+    naive::{NaiveDate, NaiveTime},
+    Datelike, Timelike,
+};
+
+// Formats a date as ddmmyy
+pub(crate) fn date(date: &NaiveDate) -> String {
+    format!(
+        "{:02}{:02}{:02}",
+        date.day(),
+        date.month(),
+        date.year().rem_euclid(100)
+    )
+}
+
+// Formats latitude and longitude as four fields: ddmm.mmmmm,N,dddmm.mmmmm,E
+pub(crate) fn latlon(lat_lon: &Option<LatLon>) -> String {
+    match lat_lon {
+        Some(l) => format!(
+            "{},{},{},{}",
+            degrees_minutes(l.latitude, 2),
+            if l.latitude < 0.0 { "S" } else { "N" },
+            degrees_minutes(l.longitude, 3),
+            if l.longitude < 0.0 { "W" } else { "E" },
+        ),
+        None => ",,,".to_string(),
+    }
+}
+
+fn degrees_minutes(value: f32, width: usize) -> String {
+    let value = f64::from(value).abs();
+    let mut degrees = value.trunc();
+    let mut minutes = ((value - degrees) * 60.0 * 100_000.0).round() / 100_000.0;
+
+    if minutes >= 60.0 {
+        degrees += 1.0;
+        minutes -= 60.0;
+    }
+
+    format!("{:0width$}{:08.5}", degrees as u32, minutes, width = width)
+}
+
+// Formats an optional number zero-padded to width
+pub(crate) fn padded(value: Option<u32>, width: usize) -> String {
+    match value {
+        Some(v) => format!("{:0width$}", v, width = width),
+        None => "".to_string(),
+    }
+}
+
+// Formats a time as hhmmss.ss, or hhmmss.sss when there are milliseconds
+pub(crate) fn time(time: &NaiveTime) -> String {
+    let mut second = time.second();
+    let mut millis = time.nanosecond() / 1_000_000;
+
+    // chrono represents a leap second as extra nanoseconds
+    if millis >= 1000 {
+        second += 1;
+        millis -= 1000;
+    }
+
+    if millis.is_multiple_of(10) {
+        format!(
+            "{:02}{:02}{:02}.{:02}",
+            time.hour(),
+            time.minute(),
+            second,
+            millis / 10
+        )
+    } else {
+        format!(
+            "{:02}{:02}{:02}.{:03}",
+            time.hour(),
+            time.minute(),
+            second,
+            millis
+        )
+    }
+}
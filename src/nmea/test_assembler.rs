@@ -0,0 +1,247 @@
+use crate::ais::{fragment::fragment, AISFragment, AISReport};
+use crate::nmea::{
+    parser::{self, MessageType, Signal},
+    Assembler, GSVData, System, TXTData, Talker, GROUP_TIMEOUT,
+};
+use std::time::Duration;
+
+fn gsv(input: &str, received: u64) -> GSVData {
+    let mut gsv = parser::gsv(input).unwrap().1;
+    gsv.received = Some(Duration::from_secs(received));
+
+    gsv
+}
+
+fn txt(input: &str, received: u64) -> TXTData {
+    let mut txt = parser::txt(input).unwrap().1;
+    txt.received = Some(Duration::from_secs(received));
+
+    txt
+}
+
+const GSV_1: &str = "GPGSV,3,1,10,23,38,230,44,29,71,156,47,07,29,116,41,08,09,081,36";
+const GSV_2: &str = "GPGSV,3,2,10,10,07,189,,05,05,220,,09,34,274,42,18,25,309,44";
+const GSV_3: &str = "GPGSV,3,3,10,26,82,187,47,28,43,056,46";
+
+#[test]
+fn test_gsv() {
+    let mut assembler = Assembler::default();
+
+    assert_eq!(None, assembler.gsv(gsv(GSV_1, 7), "test"));
+    assert_eq!(None, assembler.gsv(gsv(GSV_2, 7), "test"));
+
+    let view = assembler.gsv(gsv(GSV_3, 8), "test").unwrap();
+
+    assert_eq!(Some(Duration::from_secs(7)), view.received);
+    assert_eq!(Talker::GPS, view.talker);
+    assert_eq!(Some(System::GPS), view.system);
+    assert_eq!(None, view.signal);
+    assert_eq!(10, view.num_satellites);
+
+    let ids: Vec<u32> = view.satellites.iter().map(|s| s.id).collect();
+    assert_eq!(vec![23, 29, 7, 8, 10, 5, 9, 18, 26, 28], ids);
+}
+
+#[test]
+fn test_gsv_out_of_order() {
+    let mut assembler = Assembler::default();
+
+    assert_eq!(None, assembler.gsv(gsv(GSV_3, 7), "test"));
+    assert_eq!(None, assembler.gsv(gsv(GSV_1, 7), "test"));
+
+    let view = assembler.gsv(gsv(GSV_2, 7), "test").unwrap();
+
+    assert_eq!(23, view.satellites[0].id);
+    assert_eq!(28, view.satellites[9].id);
+}
+
+#[test]
+fn test_gsv_missing_part() {
+    let mut assembler = Assembler::default();
+
+    // part 2 of the first group was lost
+    assert_eq!(None, assembler.gsv(gsv(GSV_1, 7), "test"));
+    assert_eq!(None, assembler.gsv(gsv(GSV_3, 7), "test"));
+
+    assert_eq!(None, assembler.gsv(gsv(GSV_1, 8), "test"));
+    assert_eq!(None, assembler.gsv(gsv(GSV_2, 8), "test"));
+
+    let view = assembler.gsv(gsv(GSV_3, 8), "test").unwrap();
+
+    assert_eq!(Some(Duration::from_secs(8)), view.received);
+    assert_eq!(10, view.satellites.len());
+}
+
+#[test]
+fn test_gsv_timeout() {
+    let mut assembler = Assembler::default();
+    let late = 7 + GROUP_TIMEOUT.as_secs() + 1;
+
+    assert_eq!(None, assembler.gsv(gsv(GSV_1, 7), "test"));
+    assert_eq!(None, assembler.gsv(gsv(GSV_2, 7), "test"));
+    assert_eq!(None, assembler.gsv(gsv(GSV_3, late), "test"));
+}
+
+#[test]
+fn test_gsv_signal() {
+    let mut assembler = Assembler::default();
+
+    // the same talker with different signals are separate groups
+    let l1 = gsv(
+        "GPGSV,2,1,05,05,44,305,42,10,15,147,32,13,29,267,37,15,46,222,44,1",
+        7,
+    );
+    let l5 = gsv("GPGSV,1,1,02,05,44,305,38,13,29,267,35,8", 7);
+    let l1_2 = gsv("GPGSV,2,2,05,18,25,309,44,1", 7);
+
+    assert_eq!(None, assembler.gsv(l1, "test"));
+
+    let view = assembler.gsv(l5, "test").unwrap();
+
+    assert_eq!(Some(Signal::GPSL5Q), view.signal);
+    assert_eq!(2, view.satellites.len());
+
+    let view = assembler.gsv(l1_2, "test").unwrap();
+
+    assert_eq!(Some(Signal::GPSL1CA), view.signal);
+    assert_eq!(5, view.satellites.len());
+}
+
+#[test]
+fn test_gsv_invalid_part() {
+    let mut assembler = Assembler::default();
+
+    assert_eq!(
+        None,
+        assembler.gsv(gsv("GPGSV,2,3,05,18,25,309,44", 7), "test")
+    );
+    assert_eq!(
+        None,
+        assembler.gsv(gsv("GPGSV,2,0,05,18,25,309,44", 7), "test")
+    );
+}
+
+fn dropped(device: &str, sentence: &str) -> u64 {
+    let metric = prometheus::gather()
+        .into_iter()
+        .find(|m| m.get_name() == "where_am_i_nmea_dropped_sentences_count")
+        .unwrap();
+
+    let counter = metric
+        .get_metric()
+        .iter()
+        .find(|m| {
+            m.get_label()
+                .iter()
+                .any(|l| l.get_name() == "device" && l.get_value() == device)
+                && m.get_label()
+                    .iter()
+                    .any(|l| l.get_name() == "sentence" && l.get_value() == sentence)
+        })
+        .unwrap();
+
+    counter.get_counter().get_value() as u64
+}
+
+#[test]
+fn test_gsv_too_many_parts() {
+    let mut assembler = Assembler::default();
+
+    // a corrupt part count must not size the group
+    assert_eq!(
+        None,
+        assembler.gsv(gsv("GPGSV,4000000000,1,05,18,25,309,44", 7), "huge")
+    );
+    assert_eq!(
+        None,
+        assembler.gsv(gsv("GPGSV,10,1,05,18,25,309,44", 7), "huge")
+    );
+
+    assert_eq!(2, dropped("huge", "GSV"));
+
+    let view = assembler
+        .gsv(gsv("GPGSV,1,1,01,18,25,309,44", 7), "huge")
+        .unwrap();
+
+    assert_eq!(1, view.satellites.len());
+}
+
+#[test]
+fn test_txt_too_many_parts() {
+    let mut assembler = Assembler::default();
+
+    assert_eq!(
+        None,
+        assembler.txt(txt("GPTXT,4000000000,01,02,ANTSTATUS=OK", 7), "huge")
+    );
+    assert_eq!(
+        None,
+        assembler.txt(txt("GPTXT,100,01,02,ANTSTATUS=OK", 7), "huge")
+    );
+
+    assert_eq!(2, dropped("huge", "TXT"));
+}
+
+#[test]
+fn test_txt() {
+    let mut assembler = Assembler::default();
+
+    let view = assembler
+        .txt(txt("GPTXT,01,01,02,ANTSTATUS=OK", 7), "test")
+        .unwrap();
+
+    assert_eq!(MessageType::Notice, view.msg_type);
+    assert_eq!("ANTSTATUS=OK", view.text);
+
+    assert_eq!(
+        None,
+        assembler.txt(txt("GPTXT,02,02,02,FWVER=1.00", 7), "test")
+    );
+
+    let view = assembler
+        .txt(txt("GPTXT,02,01,02,HW UBX 9 00190000 ", 7), "test")
+        .unwrap();
+
+    assert_eq!("HW UBX 9 00190000 FWVER=1.00", view.text);
+}
+
+fn vdm(input: &str, received: u64) -> AISFragment {
+    let mut fragment = fragment(input).unwrap().1;
+    fragment.received = Some(Duration::from_secs(received));
+
+    fragment
+}
+
+#[test]
+fn test_ais() {
+    let mut assembler = Assembler::default();
+
+    let message = assembler
+        .ais(
+            vdm("AIVDM,1,1,,B,15M67FC000G?ufbE`FepT@3n00Sa,0", 7),
+            "test",
+        )
+        .unwrap();
+
+    assert_eq!(366053209, message.mmsi);
+
+    let part_1 = "AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0";
+    let part_2 = "AIVDM,2,2,1,A,88888888880,2";
+
+    assert_eq!(None, assembler.ais(vdm(part_1, 7), "test"));
+
+    // a fragment of another message on the other channel
+    let other = "AIVDM,2,2,1,B,88888888880,2";
+    assert_eq!(None, assembler.ais(vdm(other, 7), "test"));
+
+    let message = assembler.ais(vdm(part_2, 8), "test").unwrap();
+
+    assert_eq!(Some(Duration::from_secs(7)), message.received);
+    assert_eq!(5, message.message_type);
+    assert_eq!(351759000, message.mmsi);
+
+    match message.report {
+        AISReport::StaticAndVoyage(s) => assert_eq!("EVER DIADEM", s.shipname),
+        other => panic!("expected static and voyage data, got {:?}", other),
+    }
+}
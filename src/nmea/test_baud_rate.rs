@@ -0,0 +1,43 @@
+use crate::nmea::baud_rate::*;
+use crate::nmea::*;
+
+fn invalid_checksum() -> NMEA {
+    NMEA::ParseError(ParseError::new(
+        ParseErrorKind::ChecksumMismatch {
+            given: 0x65,
+            calculated: 0x64,
+        },
+        "GPZDA,082710.00,16,09,2002,00,00",
+        32,
+    ))
+}
+
+#[test]
+fn test_is_valid() {
+    assert!(is_valid(&NMEA::Unsupported("PUBX,00".to_string())));
+    assert!(!is_valid(&invalid_checksum()));
+    assert!(!is_valid(&NMEA::ParseError(ParseError::new(
+        ParseErrorKind::Garbage { skipped: 2 },
+        "\u{fffd}\u{fffd}",
+        0
+    ))));
+}
+
+#[test]
+fn test_score() {
+    let mut score = BaudRateScore::default();
+
+    assert_eq!(0.0, score.ratio());
+    assert!(!score.usable());
+
+    score.add(&invalid_checksum());
+    score.add(&NMEA::Unsupported("PUBX,00".to_string()));
+    score.add(&NMEA::Unsupported("PUBX,04".to_string()));
+    score.add(&NMEA::Unsupported("PUBX,03".to_string()));
+
+    assert_eq!(3, score.valid);
+    assert_eq!(1, score.invalid);
+    assert_eq!(4, score.total());
+    assert_approx_eq!(0.75, score.ratio());
+    assert!(score.usable());
+}
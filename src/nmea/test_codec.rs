@@ -1,7 +1,9 @@
 #[cfg(test)]
 mod test {
     use crate::nmea::Codec;
+    use crate::nmea::ParseErrorKind;
     use crate::nmea::NMEA;
+    use crate::ubx::UBXMessage;
 
     use bytes::BytesMut;
 
@@ -55,4 +57,76 @@ mod test {
 
         assert_ne!(first, second);
     }
+
+    #[test]
+    fn test_nmea_codec_ubx() {
+        let mut codec = Codec::default();
+
+        // UBX frames may arrive between NMEA sentences, garbage included
+        let mut bytes_mut = BytesMut::new();
+        bytes_mut.extend_from_slice(b"$EIGAQ,RMC*2B\r\n\xB5\x00");
+        bytes_mut.extend_from_slice(&[0xB5, 0x62, 0x05, 0x01, 0x02, 0x00, 0x06, 0x8A, 0x98]);
+
+        match codec.decode(&mut bytes_mut).unwrap().unwrap() {
+            NMEA::GAQ(_) => (),
+            other => unreachable!("first message must be GAQ, was {:?}", other),
+        };
+
+        match codec.decode(&mut bytes_mut).unwrap().unwrap() {
+            NMEA::ParseError(e) => assert_eq!(ParseErrorKind::Garbage { skipped: 2 }, e.kind),
+            other => unreachable!("garbage must be reported, was {:?}", other),
+        };
+
+        assert!(codec.decode(&mut bytes_mut).unwrap().is_none());
+
+        bytes_mut.extend_from_slice(&[0xC1]);
+        bytes_mut.extend_from_slice(b"$EIGAQ,RMC*2B\r\n");
+
+        match codec.decode(&mut bytes_mut).unwrap().unwrap() {
+            NMEA::UBX(UBXMessage::Ack(ack)) => assert_eq!((0x06, 0x8A), (ack.class, ack.id)),
+            other => unreachable!("second message must be UBX, was {:?}", other),
+        };
+
+        match codec.decode(&mut bytes_mut).unwrap().unwrap() {
+            NMEA::GAQ(_) => (),
+            other => unreachable!("third message must be GAQ, was {:?}", other),
+        };
+    }
+
+    #[test]
+    fn test_nmea_codec_rtcm() {
+        let mut codec = Codec::default();
+
+        // a lone preamble byte is skipped and reported
+        let mut bytes_mut = BytesMut::new();
+        bytes_mut.extend_from_slice(b"\xD3$EIGAQ,RMC*2B\r\n");
+        bytes_mut.extend_from_slice(&[
+            0xD3, 0x00, 0x13, 0x3E, 0xD7, 0xD3, 0x02, 0x02, 0x98, 0x0E, 0xDE, 0xEF, 0x34, 0xB4,
+            0xBD, 0x62, 0xAC, 0x09, 0x41, 0x98, 0x6F, 0x33, 0x36, 0x0B, 0x98,
+        ]);
+        bytes_mut.extend_from_slice(b"$EIGAQ,RMC*2B\r\n");
+
+        match codec.decode(&mut bytes_mut).unwrap().unwrap() {
+            NMEA::ParseError(e) => assert_eq!(ParseErrorKind::Garbage { skipped: 1 }, e.kind),
+            other => unreachable!("garbage must be reported, was {:?}", other),
+        };
+
+        match codec.decode(&mut bytes_mut).unwrap().unwrap() {
+            NMEA::GAQ(_) => (),
+            other => unreachable!("first message must be GAQ, was {:?}", other),
+        };
+
+        match codec.decode(&mut bytes_mut).unwrap().unwrap() {
+            NMEA::RTCM(frame) => {
+                assert_eq!(1005, frame.message_type);
+                assert_eq!(Some(2003), frame.station_id);
+            }
+            other => unreachable!("second message must be RTCM, was {:?}", other),
+        };
+
+        match codec.decode(&mut bytes_mut).unwrap().unwrap() {
+            NMEA::GAQ(_) => (),
+            other => unreachable!("third message must be GAQ, was {:?}", other),
+        };
+    }
 }
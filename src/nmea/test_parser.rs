@@ -2,7 +2,7 @@ use crate::{
     gps::{Driver, Generic},
     nmea::{
         parser::{self, *},
-        EastWest, NorthSouth,
+        EastWest, NorthSouth, ParseError, ParseErrorKind, ParseProfile,
     },
 };
 use chrono::naive::{NaiveDate, NaiveTime};
@@ -26,7 +26,9 @@ fn driver() -> Driver {
 fn parse<'a>(input: &'a [u8]) -> NMEA {
     let driver = driver();
 
-    parser::parse(input, &driver, timestamp()).unwrap().1
+    parser::parse(input, &driver, ParseProfile::default(), timestamp())
+        .unwrap()
+        .1
 }
 
 fn timestamp() -> Duration {
@@ -54,8 +56,8 @@ fn test_parse_sentence() {
 
 #[test]
 fn test_unknown() {
-    let parsed = parse(b"$GPROT,35.6,A*01\r\n");
-    let data = "GPROT,35.6,A".to_string();
+    let parsed = parse(b"$GPXTE,A,A,0.67,L,N,D*07\r\n");
+    let data = "GPXTE,A,A,0.67,L,N,D".to_string();
 
     assert_eq!(NMEA::Unsupported(data), parsed);
 }
@@ -64,19 +66,86 @@ fn test_unknown() {
 fn test_error_checksum() {
     let result = parse(b"$EIGAQ,RMC*2C\r\n");
 
-    let mismatch = ChecksumMismatch {
-        message: String::from("EIGAQ,RMC"),
-        given: 44,
-        calculated: 43,
-    };
+    let mut mismatch = ParseError::new(
+        ParseErrorKind::ChecksumMismatch {
+            given: 44,
+            calculated: 43,
+        },
+        "EIGAQ,RMC",
+        9,
+    );
+    mismatch.received = Some(timestamp());
+
+    assert_eq!(NMEA::ParseError(mismatch), result);
+}
+
+#[test]
+fn test_profile_legacy_layout() {
+    let driver = driver();
+    let input = "GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E";
+
+    let parsed = parser::message(input, &driver, ParseProfile::NMEA2, timestamp())
+        .unwrap()
+        .1;
+
+    match parsed {
+        NMEA::RMC(rmc) => {
+            assert_eq!(Status::Valid, rmc.status);
+            assert_eq!(PositionMode::AutonomousGNSSFix, rmc.position_mode);
+            assert_eq!(Some(20.3), rmc.magnetic_variation);
+            assert_eq!(None, rmc.nav_status);
+        }
+        other => panic!("expected RMC, got {:?}", other),
+    }
+
+    let parsed = parser::message(input, &driver, ParseProfile::NMEA4, timestamp())
+        .unwrap()
+        .1;
+
+    assert_eq!(NMEA::Unsupported(input.to_string()), parsed);
+
+    let input = "GPGLL,4916.45,N,12311.12,W,225444,V";
+
+    let parsed = parser::message(input, &driver, ParseProfile::Lenient, timestamp())
+        .unwrap()
+        .1;
+
+    match parsed {
+        NMEA::GLL(gll) => assert_eq!(PositionMode::NoFix, gll.position_mode),
+        other => panic!("expected GLL, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_profile_nmea41_fields() {
+    let driver = driver();
+    let input = "GPRMC,083559.00,A,4717.11437,N,00833.91522,E,0.004,77.52,091202,,,A,V";
+
+    let parsed = parser::message(input, &driver, ParseProfile::NMEA3, timestamp())
+        .unwrap()
+        .1;
+
+    assert_eq!(NMEA::Unsupported(input.to_string()), parsed);
+
+    let parsed = parser::message(input, &driver, ParseProfile::NMEA4, timestamp())
+        .unwrap()
+        .1;
+
+    assert!(matches!(parsed, NMEA::RMC(_)));
+
+    let input = "GPRMC,083559.00,A,4717.11437,N,00833.91522,E,0.004,77.52,091202,,,A";
 
-    assert_eq!(NMEA::InvalidChecksum(mismatch), result);
+    let parsed = parser::message(input, &driver, ParseProfile::NMEA3, timestamp())
+        .unwrap()
+        .1;
+
+    assert!(matches!(parsed, NMEA::RMC(_)));
 }
 
 #[test]
 fn test_incomplete() {
     let input = b"$EIG";
-    let result = parser::parse(input, &driver(), timestamp());
+    let result = parser::parse(input, &driver(), ParseProfile::default(), timestamp());
 
     match result {
         Err(Incomplete(Needed::Size(n))) => {
@@ -137,12 +206,48 @@ fn test_talker() {
     assert_eq!(Talker::GLONASS, parser::talker("GL").unwrap().1);
     assert_eq!(Talker::Combination, parser::talker("GN").unwrap().1);
     assert_eq!(Talker::GPS, parser::talker("GP").unwrap().1);
+    assert_eq!(Talker::NavIC, parser::talker("GI").unwrap().1);
+    assert_eq!(Talker::QZSS, parser::talker("GQ").unwrap().1);
+    assert_eq!(Talker::BeiDuo, parser::talker("BD").unwrap().1);
     assert_eq!(
         Talker::Unknown("AA".to_string()),
         parser::talker("AA").unwrap().1
     );
 }
 
+#[test]
+fn test_talker_system() {
+    assert_eq!(Some(System::QZSS), Talker::QZSS.system());
+    assert_eq!(Some(System::NavIC), Talker::NavIC.system());
+    assert_eq!(None, Talker::Combination.system());
+}
+
+#[test]
+fn test_system() {
+    assert_eq!(System::GPS, parser::system("1").unwrap().1);
+    assert_eq!(System::GLONASS, parser::system("2").unwrap().1);
+    assert_eq!(System::Galileo, parser::system("3").unwrap().1);
+    assert_eq!(System::BeiDuo, parser::system("4").unwrap().1);
+    assert_eq!(System::QZSS, parser::system("5").unwrap().1);
+    assert_eq!(System::NavIC, parser::system("6").unwrap().1);
+    assert_eq!(System::Unknown(7), parser::system("7").unwrap().1);
+}
+
+#[test]
+fn test_signal() {
+    assert_eq!(11, parser::signal_id("B").unwrap().1);
+
+    assert_eq!(Signal::All, Signal::new(Some(&System::GPS), 0));
+    assert_eq!(Signal::GPSL5Q, Signal::new(Some(&System::GPS), 8));
+    assert_eq!(Signal::GLONASSG2CA, Signal::new(Some(&System::GLONASS), 3));
+    assert_eq!(Signal::GalileoE1BC, Signal::new(Some(&System::Galileo), 7));
+    assert_eq!(Signal::BeiDuoB2I, Signal::new(Some(&System::BeiDuo), 11));
+    assert_eq!(Signal::QZSSL1S, Signal::new(Some(&System::QZSS), 4));
+    assert_eq!(Signal::NavICL5SPS, Signal::new(Some(&System::NavIC), 1));
+    assert_eq!(Signal::Unknown(5), Signal::new(Some(&System::GLONASS), 5));
+    assert_eq!(Signal::Unknown(1), Signal::new(None, 1));
+}
+
 #[test]
 fn test_dtm() {
     let parsed = parser::dtm("GPDTM,W84,,0.0,N,0.0,E,0.0,W84").unwrap().1;
@@ -217,7 +322,7 @@ fn test_gbs() {
     assert_eq!(Some(-21.4), parsed.bias);
     assert_eq!(Some(3.8), parsed.stddev);
     assert_eq!(Some(System::GPS), parsed.system);
-    assert_eq!(Some(Signal::Unknown), parsed.signal);
+    assert_eq!(Some(Signal::All), parsed.signal);
 }
 
 #[test]
@@ -350,7 +455,7 @@ fn test_grs() {
     assert_eq!(residuals[10], parsed.residuals[10]);
     assert_eq!(residuals[11], parsed.residuals[11]);
     assert_eq!(System::GPS, parsed.system);
-    assert_eq!(Some(Signal::L1), parsed.signal);
+    assert_eq!(Some(Signal::GPSL1CA), parsed.signal);
 
     let parsed = parser::grs("GNGRS,104148.00,1,,0.0,2.5,0.0,,2.8,,,,,,,1,5")
         .unwrap()
@@ -387,7 +492,7 @@ fn test_grs() {
     assert_eq!(residuals[10], parsed.residuals[10]);
     assert_eq!(residuals[11], parsed.residuals[11]);
     assert_eq!(System::GPS, parsed.system);
-    assert_eq!(Some(Signal::L2CM), parsed.signal);
+    assert_eq!(Some(Signal::GPSL2CM), parsed.signal);
 }
 
 #[test]
@@ -546,7 +651,7 @@ fn test_gsv() {
     assert_eq!(1, parsed.msg);
     assert_eq!(9, parsed.num_satellites);
     assert_eq!(satellites, parsed.satellites);
-    assert_eq!(Some(Signal::L1), parsed.signal);
+    assert_eq!(Some(Signal::GPSL1CA), parsed.signal);
 
     let parsed = parser::gsv("GPGSV,3,3,09,25,,,40,1").unwrap().1;
 
@@ -562,7 +667,7 @@ fn test_gsv() {
     assert_eq!(3, parsed.msg);
     assert_eq!(9, parsed.num_satellites);
     assert_eq!(satellites, parsed.satellites);
-    assert_eq!(Some(Signal::L1), parsed.signal);
+    assert_eq!(Some(Signal::GPSL1CA), parsed.signal);
 
     let parsed = parser::gsv("GPGSV,1,1,03,12,,,42,24,,,47,32,,,37,5")
         .unwrap()
@@ -594,7 +699,7 @@ fn test_gsv() {
     assert_eq!(1, parsed.msg);
     assert_eq!(3, parsed.num_satellites);
     assert_eq!(satellites, parsed.satellites);
-    assert_eq!(Some(Signal::L2CM), parsed.signal);
+    assert_eq!(Some(Signal::GPSL2CM), parsed.signal);
 
     let parsed = parser::gsv("GAGSV,1,1,00,2").unwrap().1;
 
@@ -605,7 +710,7 @@ fn test_gsv() {
     assert_eq!(1, parsed.msg);
     assert_eq!(0, parsed.num_satellites);
     assert_eq!(satellites, parsed.satellites);
-    assert_eq!(Some(Signal::E5), parsed.signal);
+    assert_eq!(Some(Signal::GalileoE5b), parsed.signal);
 }
 
 #[test]
@@ -664,6 +769,85 @@ fn test_gbgsv() {
     assert_eq!(None, parsed.signal);
 }
 
+#[test]
+fn test_gbgsv_b2i() {
+    let input = "GBGSV,1,1,01,11,01,341,30,B";
+    let parsed = p::<GSVData>(input, gsv(input));
+
+    assert_eq!(Some(System::BeiDuo), parsed.system);
+    assert_eq!(Some(Signal::BeiDuoB2I), parsed.signal);
+}
+
+#[test]
+fn test_gqgsv() {
+    let input = "GQGSV,1,1,01,02,45,120,38,8";
+    let parsed = p::<GSVData>(input, gsv(input));
+
+    let satellites = vec![GSVsatellite {
+        id: 2,
+        elevation: Some(45),
+        azimuth: Some(120),
+        cno: Some(38),
+    }];
+
+    assert_eq!(Talker::QZSS, parsed.talker);
+    assert_eq!(satellites, parsed.satellites);
+    assert_eq!(Some(System::QZSS), parsed.system);
+    assert_eq!(Some(Signal::QZSSL5Q), parsed.signal);
+}
+
+#[test]
+fn test_gigsv() {
+    let input = "GIGSV,1,1,01,05,30,200,41,1";
+    let parsed = p::<GSVData>(input, gsv(input));
+
+    assert_eq!(Talker::NavIC, parsed.talker);
+    assert_eq!(Some(System::NavIC), parsed.system);
+    assert_eq!(Some(Signal::NavICL5SPS), parsed.signal);
+}
+
+#[test]
+fn test_gns() {
+    let input = "GNGNS,103600.01,5114.51176,N,00012.29380,W,ANNN,07,1.18,111.5,45.6,,,V";
+    let parsed = p::<GNSData>(input, gns(input));
+
+    assert_eq!(Talker::Combination, parsed.talker);
+    assert_eq!(NaiveTime::from_hms_milli(10, 36, 0, 10), parsed.time);
+    assert_eq!(PositionMode::AutonomousGNSSFix, parsed.gps_position_mode);
+    assert_eq!(PositionMode::NoFix, parsed.glonass_position_mode);
+    assert_eq!(PositionMode::NoFix, parsed.galileo_position_mode);
+    assert_eq!(PositionMode::NoFix, parsed.beiduo_position_mode);
+    assert_eq!(None, parsed.qzss_position_mode);
+    assert_eq!(None, parsed.navic_position_mode);
+    assert_eq!(7, parsed.num_satellites);
+    assert_eq!(Status::Invalid, parsed.nav_status);
+
+    let input = "GNGNS,103600.01,5114.51176,N,00012.29380,W,AAANDA,12,0.78,111.5,45.6,,,V";
+    let parsed = p::<GNSData>(input, gns(input));
+
+    assert_eq!(
+        Some(PositionMode::DifferentialGNSSFix),
+        parsed.qzss_position_mode
+    );
+    assert_eq!(
+        Some(PositionMode::AutonomousGNSSFix),
+        parsed.navic_position_mode
+    );
+}
+
+#[test]
+fn test_hdt() {
+    let parsed = parser::hdt("GNHDT,274.07,T").unwrap().1;
+
+    assert_eq!(Talker::Combination, parsed.talker);
+    assert_approx_eq!(274.07, parsed.heading.unwrap());
+
+    let parsed = parser::hdt("HEHDT,,T").unwrap().1;
+
+    assert_eq!(Talker::Unknown("HE".to_string()), parsed.talker);
+    assert_eq!(None, parsed.heading);
+}
+
 #[test]
 fn test_rmc() {
     let parsed =
@@ -681,7 +865,7 @@ fn test_rmc() {
 
     assert_approx_eq!(0.004, parsed.speed);
     assert_approx_eq!(77.52, parsed.course_over_ground.unwrap());
-    assert_eq!(NaiveDate::from_ymd(02, 12, 9), parsed.date);
+    assert_eq!(NaiveDate::from_ymd(2002, 12, 9), parsed.date);
     assert_eq!(None, parsed.magnetic_variation);
     assert_eq!(None, parsed.magnetic_variation_east_west);
     assert_eq!(PositionMode::AutonomousGNSSFix, parsed.position_mode);
@@ -702,7 +886,7 @@ fn test_rmc_empty() {
 
     assert_approx_eq!(0.0, parsed.speed);
     assert_approx_eq!(0.0, parsed.course_over_ground.unwrap());
-    assert_eq!(NaiveDate::from_ymd(20, 9, 5), parsed.date);
+    assert_eq!(NaiveDate::from_ymd(2020, 9, 5), parsed.date);
     assert_eq!(None, parsed.magnetic_variation);
     assert_eq!(None, parsed.magnetic_variation_east_west);
     assert_eq!(PositionMode::NoFix, parsed.position_mode);
@@ -727,13 +911,41 @@ fn test_gnrmc() {
 
     assert_approx_eq!(0.015, parsed.speed);
     assert_eq!(None, parsed.course_over_ground);
-    assert_eq!(NaiveDate::from_ymd(02, 12, 9), parsed.date);
+    assert_eq!(NaiveDate::from_ymd(2002, 12, 9), parsed.date);
     assert_eq!(None, parsed.magnetic_variation);
     assert_eq!(None, parsed.magnetic_variation_east_west);
     assert_eq!(PositionMode::AutonomousGNSSFix, parsed.position_mode);
     assert_eq!(Some(Status::Invalid), parsed.nav_status);
 }
 
+#[test]
+fn test_rot() {
+    let parsed = parser::rot("GPROT,-12.5,A").unwrap().1;
+
+    assert_eq!(Talker::GPS, parsed.talker);
+    assert_approx_eq!(-12.5, parsed.rate_of_turn.unwrap());
+    assert_eq!(Status::Valid, parsed.status);
+
+    let parsed = parser::rot("GPROT,,V").unwrap().1;
+
+    assert_eq!(None, parsed.rate_of_turn);
+    assert_eq!(Status::Invalid, parsed.status);
+}
+
+#[test]
+fn test_ths() {
+    let parsed = parser::ths("GNTHS,77.52,A").unwrap().1;
+
+    assert_eq!(Talker::Combination, parsed.talker);
+    assert_approx_eq!(77.52, parsed.heading.unwrap());
+    assert_eq!(HeadingMode::Autonomous, parsed.mode);
+
+    let parsed = parser::ths("GNTHS,,V").unwrap().1;
+
+    assert_eq!(None, parsed.heading);
+    assert_eq!(HeadingMode::Invalid, parsed.mode);
+}
+
 #[test]
 fn test_txt() {
     let parsed = parser::txt("GPTXT,01,01,02,u-blox ag - www.u-blox.com")
@@ -833,3 +1045,27 @@ fn test_zda_time_only() {
     assert_eq!(0, parsed.local_tz_hour);
     assert_eq!(0, parsed.local_tz_minute);
 }
+
+#[test]
+fn test_error_kinds() {
+    let error = parser::standard_error("GPGGA,092725.00,4717.11399,N,00833.91590,E,X,08", "GGA");
+
+    assert_eq!(
+        ParseErrorKind::InvalidField {
+            field: 5,
+            expected: "oneof".to_string()
+        },
+        error.kind
+    );
+    assert_eq!(43, error.offset);
+
+    let error = parser::standard_error("GPGGA,092725.00,4717.11399", "GGA");
+
+    assert_eq!(ParseErrorKind::Truncated, error.kind);
+    assert_eq!(26, error.offset);
+
+    let error = parser::standard_error("GPXYZ,1,2,3", "XYZ");
+
+    assert_eq!(ParseErrorKind::UnknownSentence, error.kind);
+    assert_eq!("GPXYZ,1,2,3", error.sentence);
+}
@@ -0,0 +1,29 @@
+use crate::nmea::{quarantine, ParseError, ParseErrorKind};
+use std::time::Duration;
+
+#[test]
+fn test_line() {
+    let mut error = ParseError::new(
+        ParseErrorKind::ChecksumMismatch {
+            given: 44,
+            calculated: 43,
+        },
+        "EIGAQ,RMC",
+        9,
+    );
+
+    assert_eq!(
+        "-\tchecksum_mismatch\t9\tEIGAQ,RMC\n",
+        quarantine::line(&error)
+    );
+
+    error.received = Some(Duration::new(7, 500));
+    error.kind = ParseErrorKind::Garbage { skipped: 3 };
+    error.sentence = "x\t\u{fffd}".to_string();
+    error.offset = 0;
+
+    assert_eq!(
+        "7.000000500\tgarbage\t0\tx\\t\\u{fffd}\n",
+        quarantine::line(&error)
+    );
+}
@@ -1,6 +1,10 @@
 use crate::{
     gps::{Driver, Generic},
-    nmea::{parser, sentence_parser, NMEA},
+    nmea::{
+        parser,
+        sentence_parser::{self, NMEASentence},
+        ParseErrorKind, ParseProfile, NMEA,
+    },
 };
 use nom::{error::VerboseErrorKind::Context, Err};
 use std::time::Duration;
@@ -12,7 +16,9 @@ fn driver() -> Driver {
 fn parse<'a>(input: &'a [u8]) -> NMEA {
     let driver = driver();
 
-    parser::parse(input, &driver, timestamp()).unwrap().1
+    parser::parse(input, &driver, ParseProfile::default(), timestamp())
+        .unwrap()
+        .1
 }
 
 fn timestamp() -> Duration {
@@ -37,7 +43,7 @@ fn test_incomplete() {
     let driver = driver();
     let input = b"\r\n$EIGAQ,RMC*2B";
 
-    match parser::parse(input, &driver, timestamp()) {
+    match parser::parse(input, &driver, ParseProfile::default(), timestamp()) {
         Err(Err::Incomplete(nom::Needed::Size(needed))) => {
             assert_eq!(std::num::NonZeroUsize::new(1).unwrap(), needed)
         }
@@ -49,7 +55,21 @@ fn test_incomplete() {
 
 #[test]
 fn test_skip_garbage() {
-    let parsed = parse(b"stuff*AA\r\n$EIGAQ,RMC*2B\r\n$");
+    let driver = driver();
+    let input = b"stuff*AA\r\n$EIGAQ,RMC*2B\r\n$";
+
+    let (input, parsed) =
+        parser::parse(input, &driver, ParseProfile::default(), timestamp()).unwrap();
+
+    match parsed {
+        NMEA::ParseError(e) => {
+            assert_eq!(ParseErrorKind::Garbage { skipped: 10 }, e.kind);
+            assert_eq!("stuff*AA\r\n", e.sentence);
+        }
+        other => panic!("expected garbage, got {:?}", other),
+    }
+
+    let (_, parsed) = parser::parse(input, &driver, ParseProfile::default(), timestamp()).unwrap();
     let mut data = parser::gaq("EIGAQ,RMC").unwrap().1;
 
     data.received = Some(timestamp());
@@ -84,6 +104,18 @@ fn test_garbage() {
     assert_eq!(2, count);
     assert_eq!(b"$", input);
 
+    let input = b"\r\n\xB5\x62";
+    let (input, count) = sentence_parser::garbage(input).unwrap();
+
+    assert_eq!(2, count);
+    assert_eq!(b"\xB5\x62", input);
+
+    let input = b"\r\n\xD3\x00";
+    let (input, count) = sentence_parser::garbage(input).unwrap();
+
+    assert_eq!(2, count);
+    assert_eq!(b"\xD3\x00", input);
+
     let input = b"xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx$";
     let (input, count) = sentence_parser::garbage(input).unwrap();
 
@@ -99,3 +131,77 @@ fn test_garbage() {
         assert!(false, "Garbage limit not reached");
     }
 }
+
+fn sentence(input: &[u8], profile: ParseProfile) -> NMEASentence<'_> {
+    sentence_parser::parse_sentence(input, profile).unwrap().1
+}
+
+#[test]
+fn test_missing_checksum() {
+    let input = b"$EIGAQ,RMC\r\n$EIGAQ,RMC*2B\r\n";
+
+    let (rest, parsed) = sentence_parser::parse_sentence(input, ParseProfile::NMEA4).unwrap();
+
+    assert!(matches!(
+        parsed,
+        NMEASentence::Invalid {
+            data: b"EIGAQ,RMC",
+            kind: ParseErrorKind::MissingChecksum,
+            offset: 9,
+        }
+    ));
+    assert_eq!(b"$EIGAQ,RMC*2B\r\n", rest);
+
+    let (_, parsed) = sentence_parser::parse_sentence(input, ParseProfile::Lenient).unwrap();
+
+    assert!(matches!(parsed, NMEASentence::Valid("EIGAQ,RMC")));
+}
+
+#[test]
+fn test_lowercase_checksum() {
+    let input = b"$GPRMC,083559.00,A,4717.11437,N,00833.91522,E,0.004,77.52,091202,,,A,V*2d\r\n";
+
+    assert!(matches!(
+        sentence(input, ParseProfile::Strict),
+        NMEASentence::Invalid {
+            kind: ParseErrorKind::LowercaseChecksum,
+            ..
+        }
+    ));
+    assert!(matches!(
+        sentence(input, ParseProfile::NMEA4),
+        NMEASentence::Valid(_)
+    ));
+}
+
+#[test]
+fn test_max_length() {
+    // 82 bytes
+    let input =
+        b"$GPGSV,3,1,12,05,44,305,42,10,15,147,32,13,29,267,37,15,46,222,44,99,99,99,99*71\r\n";
+
+    assert!(matches!(
+        sentence(input, ParseProfile::Strict),
+        NMEASentence::Valid(_)
+    ));
+
+    // 83 bytes
+    let input =
+        b"$GPGSV,3,1,12,05,44,305,42,10,15,147,32,13,29,267,37,15,46,222,44,99,99,99,999*48\r\n";
+
+    assert!(matches!(
+        sentence(input, ParseProfile::Strict),
+        NMEASentence::Invalid {
+            kind: ParseErrorKind::TooLong {
+                length: 83,
+                max: 82
+            },
+            offset: 82,
+            ..
+        }
+    ));
+    assert!(matches!(
+        sentence(input, ParseProfile::NMEA4),
+        NMEASentence::Valid(_)
+    ));
+}
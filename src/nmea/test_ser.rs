@@ -1,5 +1,6 @@
 use crate::gps::*;
 use crate::nmea::*;
+use chrono::NaiveTime;
 
 #[test]
 fn test_position_poll() {
@@ -73,3 +74,203 @@ fn test_config() {
 
     assert_eq!(String::from("PUBX,41,1,0007,0003,19200,0"), nmea);
 }
+
+fn round_trip(driver: Driver, input: &str) {
+    let received = std::time::Duration::from_secs(7);
+
+    let parsed = parser::message(input, &driver, ParseProfile::default(), received)
+        .unwrap()
+        .1;
+    let serialized = ser::to_string(&parsed).unwrap();
+
+    assert_eq!(input, serialized);
+
+    let reparsed = parser::message(&serialized, &driver, ParseProfile::default(), received)
+        .unwrap()
+        .1;
+
+    assert_eq!(parsed, reparsed);
+}
+
+fn generic() -> Driver {
+    Driver::Generic(Generic::default())
+}
+
+#[test]
+fn test_round_trip() {
+    let sentences = [
+        "GPDTM,W84,,0,N,0,E,0,W84",
+        "EIGAQ,RMC",
+        "EIGBQ,RMC",
+        "GPGBS,235458.00,1.4,1.3,3.1,03,,-21.4,3.8,1,0",
+        "GPGBS,235503.00,1.6,1.4,3.2,,,,,,",
+        "GPGGA,092725.00,4730.00000,N,00815.00000,E,1,08,1.01,499.6,M,48,M,,",
+        "GPGGA,092725.00,,,,,0,00,,,M,,M,,",
+        "GPGLL,4730.00000,S,00815.00000,W,092725.00,A,A",
+        "EIGLQ,RMC",
+        "EIGNQ,RMC",
+        "GNGNS,103600.01,4730.00000,N,00815.00000,W,AANN,07,1.18,111.5,45.6,,,V",
+        "GNGNS,103600.015,4730.00000,N,00815.00000,W,AAANDA,12,0.78,111.5,45.6,,,V",
+        "EIGPQ,RMC",
+        "GNGRS,104148.00,1,2.5,0,-0.3,,,,,,,,,,1,1",
+        "GNGSA,A,3,02,05,,,,,,,,,,,1.5,0.9,1.2,1",
+        "GPGSA,A,1,,,,,,,,,,,,,99.99,99.99,99.99",
+        "GPGST,082356.00,1.8,,,,1.7,1.3,2.2",
+        "GPGSV,3,1,09,09,,,17,10,,,40,12,,,49,13,,,35,1",
+        "GQGSV,1,1,01,02,45,120,38,8",
+        "GPGSV,1,1,00",
+        "GNHDT,274.07,T",
+        "GPRMC,083559.00,A,4730.00000,N,00815.00000,E,0.004,77.52,091202,,,A,V",
+        "GPRMC,083559.00,V,,,,,0,,091202,,,N",
+        "GPROT,-12.5,A",
+        "GNTHS,77.52,A",
+        "GNTHS,,V",
+        "GPTXT,01,01,02,u-blox ag - www.u-blox.com",
+        "GNVLW,,N,,N,0,N,0,N",
+        "GPVTG,77.52,T,,M,0.004,N,0.008,K,A",
+        "GPZDA,082710.00,16,09,2002,00,00",
+        "GPZDA,,,,,00,00",
+    ];
+
+    for sentence in &sentences {
+        round_trip(generic(), sentence);
+    }
+}
+
+#[test]
+fn test_round_trip_pubx() {
+    let driver = Driver::UBloxNMEA(UBloxNMEA::default());
+
+    round_trip(
+        driver.clone(),
+        "PUBX,00,081350.00,4730.00000,N,00815.00000,E,546.589,G3,2.1,2,0.007,77.52,0.007,,0.92,1.19,0.77,9,0,0",
+    );
+    round_trip(driver.clone(), "PUBX,03,2,23,-,,,45,010,08,U,067,31,42,025");
+    round_trip(
+        driver,
+        "PUBX,04,073731.00,091202,113851,1196,15D,1930035,-2660.664,43,",
+    );
+}
+
+#[test]
+fn test_round_trip_pmtk() {
+    let driver = Driver::MKT(MKT::default());
+
+    round_trip(driver.clone(), "PMTK001,314,3");
+    round_trip(driver.clone(), "PMTK010,2");
+    round_trip(driver.clone(), "PMTK011,MTKGPS");
+    round_trip(
+        driver.clone(),
+        "PMTK705,AXN_2.10_3339_2012072601,5223,PA6H,1.0",
+    );
+    round_trip(driver, "PMTK705,AXN_1.30,0000");
+}
+
+#[test]
+fn test_round_trip_quectel() {
+    let driver = Driver::Quectel(Quectel::default());
+
+    round_trip(driver.clone(), "PAIR001,062,0");
+    round_trip(driver.clone(), "PQTMCFGPPS,OK");
+    round_trip(driver.clone(), "PQTMCFGPPS,ERROR,1");
+    round_trip(
+        driver.clone(),
+        "PQTMVERNO,LC29HDANR11A01S_RSA,2022/07/28,17:42:59",
+    );
+    round_trip(
+        driver,
+        "PQTMPVT,1,31075125,20220124,083737.125,,3,8,18,31.12738291,117.2637291,34.212,5.267,0.016,-0.065,0.098,0.067,0,0.93,1.34",
+    );
+}
+
+#[test]
+fn test_round_trip_psrf() {
+    let driver = Driver::SiRF(SiRF::default());
+
+    round_trip(driver.clone(), "PSRF150,1");
+    round_trip(driver.clone(), "PSRF151,3,1485,147873.5,0x00000080");
+    round_trip(driver, "PSRF156,26,4,0,1,2");
+}
+
+#[test]
+fn test_round_trip_pgrm() {
+    let driver = Driver::Garmin(Garmin::default());
+
+    round_trip(driver.clone(), "PGRME,15.3,M,45.8,M,25.1,M");
+    round_trip(driver.clone(), "PGRME,,M,,M,,M");
+    round_trip(
+        driver.clone(),
+        "PGRMF,290,293895,160305,093802,13,4730.00000,N,00815.00000,E,A,2,0,62,2,1",
+    );
+    round_trip(driver.clone(), "PGRMF,290,293895,,,13,,,,,A,0,,,,");
+    round_trip(driver, "PGRMT,GPS 16x-LVS Ver. 3.70,P,P,R,R,P,C,32,R");
+}
+
+#[test]
+fn test_to_sentence() {
+    let zda = ZDAData {
+        received: None,
+        talker: Talker::GPS,
+        time: Some(NaiveTime::from_hms_milli(8, 27, 10, 0)),
+        day: Some(16),
+        month: Some(9),
+        year: Some(2002),
+        local_tz_hour: 0,
+        local_tz_minute: 0,
+    };
+
+    let sentence = ser::to_sentence(&NMEA::ZDA(zda.clone())).unwrap();
+
+    assert_eq!("$GPZDA,082710.00,16,09,2002,00,00*64\r\n", sentence);
+
+    let received = std::time::Duration::from_secs(7);
+    let parsed = parser::parse(
+        sentence.as_bytes(),
+        &generic(),
+        ParseProfile::default(),
+        received,
+    )
+    .unwrap()
+    .1;
+
+    assert_eq!(
+        NMEA::ZDA(ZDAData {
+            received: Some(received),
+            ..zda
+        }),
+        parsed
+    );
+}
+
+#[test]
+fn test_unserializable() {
+    let error = ParseError::new(ParseErrorKind::Truncated, "GPGGA,", 6);
+
+    assert!(ser::to_string(&NMEA::ParseError(error)).is_err());
+}
+
+#[test]
+fn test_latlon() {
+    let lat_lon = LatLon {
+        latitude: -47.5,
+        longitude: 170.999999,
+    };
+
+    assert_eq!(
+        "4730.00000,S,17100.00000,E",
+        ser_util::latlon(&Some(lat_lon))
+    );
+    assert_eq!(",,,", ser_util::latlon(&None));
+}
+
+#[test]
+fn test_time() {
+    assert_eq!(
+        "010203.40",
+        ser_util::time(&NaiveTime::from_hms_milli(1, 2, 3, 400))
+    );
+    assert_eq!(
+        "010203.456",
+        ser_util::time(&NaiveTime::from_hms_milli(1, 2, 3, 456))
+    );
+}
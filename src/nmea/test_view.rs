@@ -0,0 +1,326 @@
+use crate::{
+    gps::{Driver, Generic},
+    nmea::{
+        parser,
+        parser::{MessageType, PositionMode},
+        view, GSAData, GSVData, NMEAView, NavigationMode, ParseErrorKind, ParseProfile, Quality,
+        Sentence, Talker, TypedSentence, NMEA,
+    },
+};
+use nom::Err;
+use std::time::Duration;
+
+// Reference station antenna reference point example from the RTCM 10403 standard
+const ARP: [u8; 25] = [
+    0xD3, 0x00, 0x13, 0x3E, 0xD7, 0xD3, 0x02, 0x02, 0x98, 0x0E, 0xDE, 0xEF, 0x34, 0xB4, 0xBD, 0x62,
+    0xAC, 0x09, 0x41, 0x98, 0x6F, 0x33, 0x36, 0x0B, 0x98,
+];
+
+const UBX_ACK: [u8; 10] = [0xB5, 0x62, 0x05, 0x01, 0x02, 0x00, 0x06, 0x8A, 0x98, 0xC1];
+
+fn driver() -> Driver {
+    Driver::Generic(Generic::default())
+}
+
+fn profile() -> ParseProfile {
+    ParseProfile::default()
+}
+
+fn timestamp() -> Duration {
+    Duration::from_secs(7)
+}
+
+#[test]
+fn test_sentence() {
+    let sentence = Sentence::new("GPGLL,4735.2887,N,12217.9631,W,025134.000,A,A");
+
+    assert_eq!("GPGLL", sentence.address());
+    assert_eq!(Some("GP"), sentence.talker());
+    assert_eq!("GLL", sentence.sentence_id());
+    assert!(!sentence.is_proprietary());
+    assert_eq!(Some("4735.2887"), sentence.field(0));
+    assert_eq!(Some("A"), sentence.field(6));
+    assert_eq!(None, sentence.field(7));
+    assert_eq!(7, sentence.fields().count());
+}
+
+#[test]
+fn test_sentence_empty_fields() {
+    let sentence = Sentence::new("GPGSA,A,1,,,,,,,,,,,,,,,");
+
+    assert_eq!(Some(""), sentence.field(2));
+    assert_eq!(17, sentence.fields().count());
+
+    let sentence = Sentence::new("GPGLL");
+
+    assert_eq!("GPGLL", sentence.address());
+    assert_eq!(0, sentence.fields().count());
+}
+
+#[test]
+fn test_sentence_proprietary() {
+    let sentence = Sentence::new("PUBX,00,081350.00");
+
+    assert_eq!("PUBX", sentence.address());
+    assert_eq!(None, sentence.talker());
+    assert_eq!("PUBX", sentence.sentence_id());
+    assert!(sentence.is_proprietary());
+    assert_eq!(Some("00"), sentence.field(0));
+}
+
+#[test]
+fn test_view_sentence() {
+    let input = b"$GPGLL,4735.2887,N,12217.9631,W,025134.000,A,A*40\r\n$GP";
+
+    let (rest, parsed) = view(input, profile()).unwrap();
+
+    let sentence = match parsed {
+        NMEAView::Sentence(s) => s,
+        other => panic!("expected sentence, got {:?}", other),
+    };
+
+    assert_eq!(
+        "GPGLL,4735.2887,N,12217.9631,W,025134.000,A,A",
+        sentence.data()
+    );
+    assert_eq!(b"$GP", rest);
+
+    // the view borrows from the input
+    let start = input.as_ptr() as usize;
+    let data = sentence.data().as_ptr() as usize;
+    assert_eq!(start + 1, data);
+}
+
+#[test]
+fn test_view_checksum() {
+    let (_, parsed) = view(b"$EIGAQ,RMC*2C\r\n", profile()).unwrap();
+
+    assert_eq!(
+        NMEAView::Invalid {
+            data: b"EIGAQ,RMC",
+            kind: ParseErrorKind::ChecksumMismatch {
+                given: 0x2C,
+                calculated: 0x2B
+            },
+            offset: 9,
+        },
+        parsed
+    );
+}
+
+#[test]
+fn test_view_garbage() {
+    let input = b"\r\n,A*4F\r\n$EIGAQ,RMC*2B\r\n";
+
+    let (rest, parsed) = view(input, profile()).unwrap();
+
+    assert_eq!(
+        NMEAView::Invalid {
+            data: b"\r\n,A*4F\r\n",
+            kind: ParseErrorKind::Garbage { skipped: 9 },
+            offset: 0,
+        },
+        parsed
+    );
+    assert_eq!(b"$EIGAQ,RMC*2B\r\n", rest);
+
+    // line endings are skipped quietly
+    let (_, parsed) = view(b"\r\n$EIGAQ,RMC*2B\r\n", profile()).unwrap();
+
+    assert!(matches!(parsed, NMEAView::Sentence(_)));
+}
+
+#[test]
+fn test_view_incomplete() {
+    assert!(matches!(
+        view(b"\r\n$EIGAQ,RMC*2B", profile()),
+        Err(Err::Incomplete(_))
+    ));
+    assert!(matches!(
+        view(&UBX_ACK[..7], profile()),
+        Err(Err::Incomplete(_))
+    ));
+    assert!(matches!(
+        view(&ARP[..10], profile()),
+        Err(Err::Incomplete(_))
+    ));
+}
+
+#[test]
+fn test_view_ubx() {
+    let mut input = UBX_ACK.to_vec();
+    input.extend_from_slice(b"$GP");
+
+    let (rest, parsed) = view(&input, profile()).unwrap();
+
+    match parsed {
+        NMEAView::UBX(frame) => {
+            assert_eq!((0x05, 0x01), (frame.class, frame.id));
+            assert_eq!(&[0x06, 0x8A], frame.payload);
+        }
+        other => panic!("expected UBX, got {:?}", other),
+    }
+
+    assert_eq!(b"$GP", rest);
+}
+
+#[test]
+fn test_view_rtcm() {
+    let mut input = b"\r\n".to_vec();
+    input.extend_from_slice(&ARP);
+
+    let (rest, parsed) = view(&input, profile()).unwrap();
+
+    match parsed {
+        NMEAView::RTCM(frame) => {
+            assert_eq!(1005, frame.message_type);
+            assert_eq!(Some(2003), frame.station_id);
+            assert_eq!(&ARP[3..22], frame.payload);
+        }
+        other => panic!("expected RTCM, got {:?}", other),
+    }
+
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn test_to_nmea() {
+    let driver = driver();
+    let inputs: [&[u8]; 5] = [
+        b"$GPGLL,4735.2887,N,12217.9631,W,025134.000,A,A*40\r\n",
+        b"$EIGAQ,RMC*2C\r\n",
+        b"$GPXXX,1*52\r\n",
+        &UBX_ACK,
+        &ARP,
+    ];
+
+    for input in inputs {
+        let (view_rest, viewed) = view(input, profile()).unwrap();
+        let (parse_rest, parsed) = parser::parse(input, &driver, profile(), timestamp()).unwrap();
+
+        assert_eq!(parse_rest, view_rest);
+        assert_eq!(parsed, viewed.to_nmea(&driver, profile(), timestamp()));
+    }
+}
+
+#[test]
+fn test_to_nmea_received() {
+    let (_, viewed) = view(b"$EIGAQ,RMC*2B\r\n", profile()).unwrap();
+
+    match viewed.to_nmea(&driver(), profile(), timestamp()) {
+        NMEA::GAQ(gaq) => assert_eq!(Some(timestamp()), gaq.received),
+        other => panic!("expected GAQ, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_typed_gga() {
+    let sentence =
+        Sentence::new("GNGGA,025134.00,4735.28870,N,12217.96310,W,2,12,0.67,27.1,M,-17.3,M,,0000");
+
+    let gga = match sentence.typed() {
+        Some(TypedSentence::GGA(gga)) => gga,
+        other => panic!("expected GGA, got {:?}", other),
+    };
+
+    assert_eq!(Talker::Combination, gga.talker);
+    assert_eq!(Quality::DifferentialGNSSFix, gga.quality);
+    assert_eq!(12, gga.num_satellites);
+    assert_eq!(Some(27.1), gga.alt);
+    assert_eq!("M", gga.alt_unit);
+    assert_eq!(Some(-17.3), gga.sep);
+
+    // units are borrowed from the sentence
+    let start = sentence.data().as_ptr() as usize;
+    let unit = gga.alt_unit.as_ptr() as usize;
+    assert_eq!(start + sentence.data().find(",M,").unwrap() + 1, unit);
+}
+
+#[test]
+fn test_typed_gsa() {
+    let sentence = Sentence::new("GNGSA,A,3,85,72,65,87,88,71,,,,,,,1.21,0.67,1.01,2");
+
+    let gsa = match sentence.typed() {
+        Some(TypedSentence::GSA(gsa)) => gsa,
+        other => panic!("expected GSA, got {:?}", other),
+    };
+
+    assert_eq!(NavigationMode::Fix3D, gsa.navigation_mode);
+    assert_eq!(Some(1.21), gsa.pdop);
+
+    let ids: Vec<Option<u32>> = gsa.satellite_ids().collect();
+    assert_eq!(12, ids.len());
+    assert_eq!(Some(85), ids[0]);
+    assert_eq!(Some(71), ids[5]);
+    assert_eq!(None, ids[6]);
+
+    let owned = parser::gsa(sentence.data()).unwrap().1;
+    assert_eq!(owned, GSAData::from(gsa));
+}
+
+#[test]
+fn test_typed_gsv() {
+    let sentence = Sentence::new("GPGSV,3,3,12,29,20,233,36,30,02,336,,45,33,203,40,46,31,206,39");
+
+    let gsv = match sentence.typed() {
+        Some(TypedSentence::GSV(gsv)) => gsv,
+        other => panic!("expected GSV, got {:?}", other),
+    };
+
+    assert_eq!((3, 3, 12), (gsv.num_msgs, gsv.msg, gsv.num_satellites));
+
+    let ids: Vec<u32> = gsv.satellites().map(|s| s.id).collect();
+    assert_eq!(vec![29, 30, 45, 46], ids);
+    assert_eq!(None, gsv.satellites().nth(1).unwrap().cno);
+
+    let owned = parser::gsv(sentence.data()).unwrap().1;
+    assert_eq!(owned, GSVData::from(gsv));
+}
+
+#[test]
+fn test_typed_txt() {
+    let sentence = Sentence::new("GPTXT,01,01,02,u-blox ag - www.u-blox.com");
+
+    match sentence.typed() {
+        Some(TypedSentence::TXT(txt)) => {
+            assert_eq!(MessageType::Notice, txt.msg_type);
+            assert_eq!("u-blox ag - www.u-blox.com", txt.text);
+        }
+        other => panic!("expected TXT, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_typed_vtg() {
+    let sentence = Sentence::new("GNVTG,,T,,M,0.012,N,0.022,K,D");
+
+    match sentence.typed() {
+        Some(TypedSentence::VTG(vtg)) => {
+            assert_eq!(None, vtg.course_over_ground_true);
+            assert_eq!("T", vtg.course_over_ground_true_unit);
+            assert_eq!(0.022, vtg.speed_over_ground_km);
+            assert_eq!("K", vtg.speed_over_ground_km_unit);
+            assert_eq!(PositionMode::DifferentialGNSSFix, vtg.position_mode);
+        }
+        other => panic!("expected VTG, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_typed_other() {
+    // no typed view
+    assert_eq!(None, Sentence::new("EIGAQ,RMC").typed());
+    assert_eq!(None, Sentence::new("PUBX,00,081350.00").typed());
+    // invalid
+    assert_eq!(None, Sentence::new("GPGGA,1").typed());
+    // NMEA 2.x layout
+    assert_eq!(
+        None,
+        Sentence::new("GPRMC,081836,A,3751.65,S,14507.36,E,000.0,360.0,130998,011.3,E").typed()
+    );
+
+    assert!(matches!(
+        Sentence::new("GPRMC,025134.00,A,4735.28870,N,12217.96310,W,0.012,,160322,,,D").typed(),
+        Some(TypedSentence::RMC(_))
+    ));
+}
@@ -0,0 +1,277 @@
+// Borrowed views of messages in the input buffer
+//
+// view() frames NMEA and encapsulated sentences, UBX frames, and RTCM 3 frames without allocating
+// or copying.
+// Sentence::typed() parses common sentences into fields borrowed from the input without
+// allocating.
+// NMEAView::to_nmea() builds the owned NMEA that Codec sends to subscribers.
+
+use crate::{
+    ais::fragment::fragment,
+    gps::Driver,
+    nmea::{
+        parser::{
+            gga_view, gll, gsa_view, gsv_view, message, rmc, standard_error, txt_view, vtg_view,
+            zda, GGAView, GSAView, GSVView, Result, TXTView, VTGView,
+        },
+        sentence_parser::{garbage, parse_sentence, NMEASentence},
+        GLLData, ParseError, ParseErrorKind, ParseProfile, RMCData, ZDAData, NMEA,
+    },
+    rtcm::{
+        self,
+        frame::{RTCMFrameView, PREAMBLE},
+    },
+    ubx::{
+        self,
+        frame::{UBXChecksumMismatch, UBXFrameResult, UBXFrameView, SYNC_CHAR_1},
+        UBXMessage,
+    },
+};
+use nom::Err;
+use std::time::Duration;
+
+/// A message borrowed from the input buffer
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum NMEAView<'a> {
+    /// A sentence with a valid checksum
+    Sentence(Sentence<'a>),
+    /// A `!` sentence with a valid checksum
+    Encapsulated(Sentence<'a>),
+    /// Sentence text between the `$` and `*` that couldn't be framed or failed its checksum, or
+    /// skipped garbage
+    Invalid {
+        data: &'a [u8],
+        kind: ParseErrorKind,
+        offset: usize,
+    },
+    UBX(UBXFrameView<'a>),
+    UBXInvalidChecksum(UBXChecksumMismatch),
+    RTCM(RTCMFrameView<'a>),
+}
+
+impl<'a> NMEAView<'a> {
+    /// Parses the viewed message into an owned NMEA
+    pub fn to_nmea(&self, driver: &Driver, profile: ParseProfile, received: Duration) -> NMEA {
+        match self {
+            NMEAView::Sentence(sentence) => sentence.to_nmea(driver, profile, received),
+            NMEAView::Encapsulated(sentence) => sentence.to_ais(received),
+            NMEAView::Invalid { data, kind, offset } => NMEA::ParseError(ParseError {
+                received: Some(received),
+                kind: kind.clone(),
+                sentence: String::from_utf8_lossy(data).to_string(),
+                offset: *offset,
+            }),
+            NMEAView::UBX(frame) => NMEA::UBX(ubx::message(frame.to_frame(), received)),
+            NMEAView::UBXInvalidChecksum(cm) => NMEA::UBX(UBXMessage::InvalidChecksum(cm.clone())),
+            NMEAView::RTCM(frame) => NMEA::RTCM(frame.to_frame(received)),
+        }
+    }
+}
+
+/// A sentence parsed into fields borrowed from the input buffer
+///
+/// Sentences whose owned form doesn't allocate are their owned form without `received`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypedSentence<'a> {
+    GGA(GGAView<'a>),
+    GLL(GLLData),
+    GSA(GSAView<'a>),
+    GSV(GSVView<'a>),
+    RMC(RMCData),
+    TXT(TXTView<'a>),
+    VTG(VTGView<'a>),
+    ZDA(ZDAData),
+}
+
+/// The text of a sentence between the `$` and `*` with its fields split on demand
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Sentence<'a> {
+    data: &'a str,
+}
+
+impl<'a> Sentence<'a> {
+    pub fn new(data: &'a str) -> Self {
+        Sentence { data }
+    }
+
+    pub fn data(&self) -> &'a str {
+        self.data
+    }
+
+    /// The talker and sentence ID, or the proprietary sentence name
+    pub fn address(&self) -> &'a str {
+        match self.data.find(',') {
+            Some(end) => &self.data[..end],
+            None => self.data,
+        }
+    }
+
+    pub fn is_proprietary(&self) -> bool {
+        self.data.starts_with('P')
+    }
+
+    /// The two-character talker, None for proprietary sentences
+    pub fn talker(&self) -> Option<&'a str> {
+        let address = self.address();
+
+        match self.is_proprietary() {
+            true => None,
+            false => address.get(..2),
+        }
+    }
+
+    /// The sentence ID, the whole address for proprietary sentences
+    pub fn sentence_id(&self) -> &'a str {
+        let address = self.address();
+
+        match self.is_proprietary() {
+            true => address,
+            false => address.get(2..).unwrap_or(""),
+        }
+    }
+
+    /// Fields following the address
+    pub fn fields(&self) -> impl Iterator<Item = &'a str> {
+        let fields = self
+            .data
+            .find(',')
+            .map(|start| self.data[start + 1..].split(','));
+
+        fields.into_iter().flatten()
+    }
+
+    /// The field at index, starting from zero after the address
+    pub fn field(&self, index: usize) -> Option<&'a str> {
+        self.fields().nth(index)
+    }
+
+    /// Parses this sentence into fields borrowed from the input without allocating.
+    ///
+    /// Returns None for sentences without a typed view, layouts other than the current NMEA
+    /// layout, and invalid sentences.  Use `to_nmea()` for those.
+    pub fn typed(&self) -> Option<TypedSentence<'a>> {
+        let data = self.data;
+
+        let typed = match self.sentence_id() {
+            "GGA" => gga_view(data).map(|(_, v)| TypedSentence::GGA(v)),
+            "GLL" => gll(data).map(|(_, v)| TypedSentence::GLL(v)),
+            "GSA" => gsa_view(data).map(|(_, v)| TypedSentence::GSA(v)),
+            "GSV" => gsv_view(data).map(|(_, v)| TypedSentence::GSV(v)),
+            "RMC" => rmc(data).map(|(_, v)| TypedSentence::RMC(v)),
+            "TXT" => txt_view(data).map(|(_, v)| TypedSentence::TXT(v)),
+            "VTG" => vtg_view(data).map(|(_, v)| TypedSentence::VTG(v)),
+            "ZDA" => zda(data).map(|(_, v)| TypedSentence::ZDA(v)),
+            _ => return None,
+        };
+
+        typed.ok()
+    }
+
+    /// Parses this sentence into an owned NMEA
+    pub fn to_nmea(&self, driver: &Driver, profile: ParseProfile, received: Duration) -> NMEA {
+        match message(self.data, driver, profile, received) {
+            Err(Err::Error(e)) | Err(Err::Failure(e)) => {
+                let mut error = match self.is_proprietary() {
+                    true => ParseError::from_verbose(self.data, &e),
+                    false => standard_error(self.data, self.sentence_id()),
+                };
+
+                error.received = Some(received);
+
+                NMEA::ParseError(error)
+            }
+            Err(Err::Incomplete(_)) => unreachable!(
+                "Got Incomplete when complete parsers were used on: {:?}",
+                self.data
+            ),
+            Ok((_, nmea)) => nmea,
+        }
+    }
+
+    /// Parses this encapsulated sentence into an owned AIS fragment
+    pub fn to_ais(&self, received: Duration) -> NMEA {
+        match fragment(self.data) {
+            Err(Err::Error(e)) | Err(Err::Failure(e)) => {
+                let mut error = ParseError::from_verbose(self.data, &e);
+
+                error.received = Some(received);
+
+                NMEA::ParseError(error)
+            }
+            Err(Err::Incomplete(_)) => unreachable!(
+                "Got Incomplete when complete parsers were used on: {:?}",
+                self.data
+            ),
+            Ok((_, mut fragment)) => {
+                fragment.received = Some(received);
+
+                NMEA::AIS(fragment)
+            }
+        }
+    }
+}
+
+/// Frames the next message in input without allocating.  Sentences are framed according to
+/// `profile`.
+///
+/// Returns Incomplete when input ends within a message.
+pub fn view(input: &[u8], profile: ParseProfile) -> Result<&[u8], NMEAView<'_>> {
+    let original = input;
+    let mut input = input;
+
+    // Receivers may interleave UBX and RTCM 3 frames with NMEA sentences
+    loop {
+        let start = match garbage(input) {
+            Ok((start, _)) => start,
+            Err(Err::Incomplete(n)) => return Err(Err::Incomplete(n)),
+            // let parse_sentence() report the garbage
+            Err(_) => break,
+        };
+
+        let skipped = &original[..original.len() - start.len()];
+
+        // line endings between messages aren't garbage
+        if !skipped.iter().all(u8::is_ascii_whitespace) {
+            let invalid = NMEAView::Invalid {
+                data: skipped,
+                kind: ParseErrorKind::Garbage {
+                    skipped: skipped.len(),
+                },
+                offset: 0,
+            };
+
+            return Ok((start, invalid));
+        }
+
+        match start.first() {
+            Some(&SYNC_CHAR_1) => match ubx::frame::frame_view(start) {
+                Ok((input, UBXFrameResult::Valid(frame))) => {
+                    return Ok((input, NMEAView::UBX(frame)));
+                }
+                Ok((input, UBXFrameResult::InvalidChecksum(cm))) => {
+                    return Ok((input, NMEAView::UBXInvalidChecksum(cm)));
+                }
+                Err(Err::Incomplete(n)) => return Err(Err::Incomplete(n)),
+                // not a UBX frame, skip the sync character and keep looking
+                Err(_) => input = &start[1..],
+            },
+            Some(&PREAMBLE) => match rtcm::frame::frame_view(start) {
+                Ok((input, frame)) => return Ok((input, NMEAView::RTCM(frame))),
+                Err(Err::Incomplete(n)) => return Err(Err::Incomplete(n)),
+                // not an RTCM frame, skip the preamble and keep looking
+                Err(_) => input = &start[1..],
+            },
+            _ => break,
+        }
+    }
+
+    let (input, sentence) = parse_sentence(input, profile)?;
+
+    let view = match sentence {
+        NMEASentence::Valid(data) => NMEAView::Sentence(Sentence::new(data)),
+        NMEASentence::Encapsulated(data) => NMEAView::Encapsulated(Sentence::new(data)),
+        NMEASentence::Invalid { data, kind, offset } => NMEAView::Invalid { data, kind, offset },
+    };
+
+    Ok((input, view))
+}
@@ -0,0 +1,130 @@
+use crate::nmea::parser::Result;
+use nom::{
+    bytes::streaming::{tag, take},
+    number::streaming::{be_u16, be_u24},
+    sequence::tuple,
+    Err,
+};
+use std::time::Duration;
+
+pub const PREAMBLE: u8 = 0xD3;
+
+const CRC24Q_POLY: u32 = 0x1864CFB;
+
+/// A CRC-checked RTCM 3 frame.
+///
+/// The payload is kept encoded so the frame can be forwarded unchanged, only the message type and
+/// reference station ID are decoded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RTCMFrame {
+    pub received: Option<Duration>,
+    pub message_type: u16,
+    pub station_id: Option<u16>,
+    pub payload: Vec<u8>,
+}
+
+impl RTCMFrame {
+    /// Encodes this frame with preamble, length, and CRC
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.payload.len() + 6);
+
+        bytes.push(PREAMBLE);
+        bytes.extend_from_slice(&(self.payload.len() as u16 & 0x3FF).to_be_bytes());
+        bytes.extend_from_slice(&self.payload);
+
+        let crc = crc24q(&bytes);
+
+        bytes.extend_from_slice(&crc.to_be_bytes()[1..]);
+
+        bytes
+    }
+}
+
+/// A CRC-checked RTCM 3 frame borrowing its payload from the input buffer
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RTCMFrameView<'a> {
+    pub message_type: u16,
+    pub station_id: Option<u16>,
+    pub payload: &'a [u8],
+}
+
+impl<'a> RTCMFrameView<'a> {
+    pub fn to_frame(&self, received: Duration) -> RTCMFrame {
+        RTCMFrame {
+            received: Some(received),
+            message_type: self.message_type,
+            station_id: self.station_id,
+            payload: self.payload.to_vec(),
+        }
+    }
+}
+
+/// Parses one RTCM 3 frame beginning with the preamble without copying the payload.
+///
+/// A single preamble byte is common in other data so frames with non-zero reserved bits or a CRC
+/// mismatch are errors, the caller skips the preamble and keeps looking.
+pub(crate) fn frame_view(input: &[u8]) -> Result<&[u8], RTCMFrameView<'_>> {
+    let start = input;
+
+    let (input, (_, header)) = tuple((tag([PREAMBLE]), be_u16))(input)?;
+
+    if header & 0xFC00 != 0 {
+        return Err(error(start, "RTCM reserved bits set"));
+    }
+
+    let length = (header & 0x3FF) as usize;
+
+    let (input, (payload, given)) = tuple((take(length), be_u24))(input)?;
+
+    if given != crc24q(&start[..3 + length]) {
+        return Err(error(start, "RTCM CRC mismatch"));
+    }
+
+    if payload.len() < 2 {
+        return Err(error(start, "RTCM message type missing"));
+    }
+
+    let message_type = (u16::from(payload[0]) << 4) | (u16::from(payload[1]) >> 4);
+
+    let station_id = if has_station_id(message_type) && payload.len() >= 3 {
+        Some((u16::from(payload[1] & 0x0F) << 8) | u16::from(payload[2]))
+    } else {
+        None
+    };
+
+    let frame = RTCMFrameView {
+        message_type,
+        station_id,
+        payload,
+    };
+
+    Ok((input, frame))
+}
+
+// Messages where the reference station ID immediately follows the message type
+fn has_station_id(message_type: u16) -> bool {
+    matches!(message_type, 1001..=1013 | 1029..=1033 | 1071..=1137 | 1230)
+}
+
+fn error<'a>(input: &'a [u8], message: &'static str) -> Err<nom::error::VerboseError<&'a [u8]>> {
+    Err::Error(nom::error::VerboseError {
+        errors: vec![(input, nom::error::VerboseErrorKind::Context(message))],
+    })
+}
+
+/// Calculates the CRC-24Q over the preamble, length, and payload
+pub fn crc24q(input: &[u8]) -> u32 {
+    input.iter().fold(0, |crc, byte| {
+        let mut crc = crc ^ (u32::from(*byte) << 16);
+
+        for _ in 0..8 {
+            crc <<= 1;
+
+            if crc & 0x1000000 != 0 {
+                crc ^= CRC24Q_POLY;
+            }
+        }
+
+        crc
+    })
+}
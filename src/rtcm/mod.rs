@@ -0,0 +1,11 @@
+// For RTCM 3 correction frames sent by receivers configured as a base station
+
+pub(crate) mod frame;
+
+pub use frame::crc24q;
+pub use frame::RTCMFrame;
+pub use frame::RTCMFrameView;
+pub use frame::PREAMBLE;
+
+#[cfg(test)]
+mod test_frame;
@@ -0,0 +1,93 @@
+use crate::nmea::parser::Result;
+use crate::rtcm::frame::*;
+use nom::{Err, Needed};
+use std::time::Duration;
+
+// Reference station antenna reference point example from the RTCM 10403 standard
+const ARP: [u8; 25] = [
+    0xD3, 0x00, 0x13, 0x3E, 0xD7, 0xD3, 0x02, 0x02, 0x98, 0x0E, 0xDE, 0xEF, 0x34, 0xB4, 0xBD, 0x62,
+    0xAC, 0x09, 0x41, 0x98, 0x6F, 0x33, 0x36, 0x0B, 0x98,
+];
+
+fn timestamp() -> Duration {
+    Duration::from_secs(7)
+}
+
+fn frame(input: &[u8], received: Duration) -> Result<&[u8], RTCMFrame> {
+    let (input, view) = frame_view(input)?;
+
+    Ok((input, view.to_frame(received)))
+}
+
+#[test]
+fn test_crc24q() {
+    assert_eq!(0x360B98, crc24q(&ARP[..22]));
+}
+
+#[test]
+fn test_frame() {
+    let mut input = ARP.to_vec();
+    input.extend_from_slice(b"$GP");
+
+    let (rest, parsed) = frame(&input, timestamp()).unwrap();
+
+    assert_eq!(Some(timestamp()), parsed.received);
+    assert_eq!(1005, parsed.message_type);
+    assert_eq!(Some(2003), parsed.station_id);
+    assert_eq!(&ARP[3..22], parsed.payload.as_slice());
+    assert_eq!(b"$GP", rest);
+}
+
+#[test]
+fn test_frame_msm() {
+    let input = [
+        0xD3, 0x00, 0x08, 0x43, 0x50, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x7B, 0x82,
+    ];
+
+    let (_, parsed) = frame(&input, timestamp()).unwrap();
+
+    assert_eq!(1077, parsed.message_type);
+    assert_eq!(Some(0), parsed.station_id);
+}
+
+#[test]
+fn test_frame_no_station_id() {
+    let input = [0xD3, 0x00, 0x03, 0x3F, 0xB1, 0x20, 0x31, 0xF1, 0x1B];
+
+    let (_, parsed) = frame(&input, timestamp()).unwrap();
+
+    assert_eq!(1019, parsed.message_type);
+    assert_eq!(None, parsed.station_id);
+}
+
+#[test]
+fn test_frame_incomplete() {
+    match frame(&ARP[..20], timestamp()) {
+        Err(Err::Incomplete(Needed::Size(n))) => {
+            assert_eq!(std::num::NonZeroUsize::new(2).unwrap(), n)
+        }
+        r => panic!("Expected Incomplete, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_frame_invalid_crc() {
+    let mut input = ARP.to_vec();
+    input[24] = 0x00;
+
+    assert!(matches!(frame(&input, timestamp()), Err(Err::Error(_))));
+}
+
+#[test]
+fn test_frame_reserved() {
+    let input = [0xD3, 0x24, 0x47, 0x50];
+
+    assert!(matches!(frame(&input, timestamp()), Err(Err::Error(_))));
+}
+
+#[test]
+fn test_to_bytes() {
+    let (_, parsed) = frame(&ARP, timestamp()).unwrap();
+
+    assert_eq!(ARP.to_vec(), parsed.to_bytes());
+}
@@ -21,11 +21,10 @@ impl NtpShm {
         Ok(NtpShm { shm_time })
     }
 
-    // TODO make leap a watch::Receiver<i32>
     pub async fn update(
         &mut self,
         current_precision: &watch::Receiver<i32>,
-        leap: i32,
+        current_leap: &watch::Receiver<i32>,
         current_timestamp: &mut watch::Receiver<Timestamp>,
     ) {
         if let Err(_) = current_timestamp.changed().await {
@@ -35,6 +34,7 @@ impl NtpShm {
         }
 
         let precision = *current_precision.borrow().deref();
+        let leap = *current_leap.borrow().deref();
 
         let ts = current_timestamp.borrow();
         let mut time_guard = self.shm_time.lock().unwrap();
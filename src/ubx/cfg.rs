@@ -0,0 +1,289 @@
+// Configuration interface for u-blox generation 9 receivers
+//
+// Each configuration item is a 32-bit key whose bits 28-30 encode the storage size of the value.
+// See the "Configuration interface" section of the u-blox ZED-F9P interface description.
+
+use crate::{nmea::parser::Result, ubx::UBXFrame};
+use nom::{
+    bytes::complete::take,
+    combinator::{all_consuming, map},
+    error::context,
+    multi::many0,
+    number::complete::{le_u16, le_u32, le_u64, u8 as byte},
+    sequence::tuple,
+};
+use serde::Deserialize;
+use std::time::Duration;
+
+pub const CLASS_CFG: u8 = 0x06;
+
+pub const CFG_VALSET: (u8, u8) = (CLASS_CFG, 0x8A);
+pub const CFG_VALGET: (u8, u8) = (CLASS_CFG, 0x8B);
+pub const CFG_VALDEL: (u8, u8) = (CLASS_CFG, 0x8C);
+
+/// Maximum number of items in a single VALSET, VALGET, or VALDEL message
+pub const MAX_ITEMS: usize = 64;
+
+pub const RATE_MEAS: u32 = 0x30210001;
+pub const RATE_NAV: u32 = 0x30210002;
+pub const NAVSPG_DYNMODEL: u32 = 0x20110021;
+
+pub const SIGNAL_GPS_ENA: u32 = 0x1031001F;
+pub const SIGNAL_SBAS_ENA: u32 = 0x10310020;
+pub const SIGNAL_GAL_ENA: u32 = 0x10310021;
+pub const SIGNAL_BDS_ENA: u32 = 0x10310022;
+pub const SIGNAL_QZSS_ENA: u32 = 0x10310024;
+pub const SIGNAL_GLO_ENA: u32 = 0x10310025;
+
+/// CFG-INFMSG-NMEA key for the I2C port, TXT output is set per information message type.  The keys
+/// for the other ports follow it like the CFG-MSGOUT keys.
+pub const INFMSG_NMEA_I2C: u32 = 0x20920006;
+
+/// CFG-INFMSG value enabling ERROR, WARNING, and NOTICE TXT messages
+pub const INFMSG_TXT: u64 = 0x07;
+
+/// CFG-MSGOUT keys for the I2C port.
+///
+/// The keys for the other ports follow the I2C key in `UBXPort` order, so add the port number to
+/// get the key for that port.
+pub const MSGOUT_I2C: [(&str, u32); 22] = [
+    ("DTM", 0x209100A6),
+    ("GBS", 0x209100DD),
+    ("GGA", 0x209100BA),
+    ("GLL", 0x209100C9),
+    ("GNS", 0x209100B5),
+    ("GRS", 0x209100CE),
+    ("GSA", 0x209100BF),
+    ("GST", 0x209100D3),
+    ("GSV", 0x209100C4),
+    ("RLM", 0x20910400),
+    ("RMC", 0x209100AB),
+    ("THS", 0x209100E2),
+    ("VLW", 0x209100E7),
+    ("VTG", 0x209100B0),
+    ("ZDA", 0x209100D8),
+    ("UBX-NAV-PVT", 0x20910006),
+    ("UBX-NAV-RELPOSNED", 0x2091008D),
+    ("UBX-NAV-SAT", 0x20910015),
+    ("UBX-NAV-STATUS", 0x2091001A),
+    ("UBX-NAV-TIMELS", 0x20910060),
+    ("UBX-NAV-TIMEUTC", 0x2091005B),
+    ("UBX-TIM-TP", 0x2091017D),
+];
+
+/// The CFG-MSGOUT key for `message` on the I2C port
+pub fn msgout_key(message: &str) -> Option<u32> {
+    MSGOUT_I2C
+        .iter()
+        .find(|(name, _)| *name == message)
+        .map(|(_, key)| *key)
+}
+
+/// Size in bytes of the value stored for `key`
+pub fn value_size(key: u32) -> usize {
+    match (key >> 28) & 0x07 {
+        0x01 | 0x02 => 1,
+        0x03 => 2,
+        0x04 => 4,
+        _ => 8,
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[serde(rename_all = "lowercase")]
+pub enum UBXDynamicModel {
+    Portable,
+    Stationary,
+    Pedestrian,
+    Automotive,
+    Sea,
+    Airborne1G,
+    Airborne2G,
+    Airborne4G,
+    Wrist,
+    Bike,
+}
+
+impl UBXDynamicModel {
+    /// Value for CFG-NAVSPG-DYNMODEL
+    pub fn value(&self) -> u64 {
+        match self {
+            UBXDynamicModel::Portable => 0,
+            UBXDynamicModel::Stationary => 2,
+            UBXDynamicModel::Pedestrian => 3,
+            UBXDynamicModel::Automotive => 4,
+            UBXDynamicModel::Sea => 5,
+            UBXDynamicModel::Airborne1G => 6,
+            UBXDynamicModel::Airborne2G => 7,
+            UBXDynamicModel::Airborne4G => 8,
+            UBXDynamicModel::Wrist => 9,
+            UBXDynamicModel::Bike => 10,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[serde(rename_all = "lowercase")]
+pub enum UBXConstellation {
+    BeiDou,
+    GLONASS,
+    GPS,
+    Galileo,
+    QZSS,
+    SBAS,
+}
+
+pub const CONSTELLATIONS: [UBXConstellation; 6] = [
+    UBXConstellation::BeiDou,
+    UBXConstellation::GLONASS,
+    UBXConstellation::GPS,
+    UBXConstellation::Galileo,
+    UBXConstellation::QZSS,
+    UBXConstellation::SBAS,
+];
+
+impl UBXConstellation {
+    /// The CFG-SIGNAL key enabling this constellation
+    pub fn key(&self) -> u32 {
+        match self {
+            UBXConstellation::BeiDou => SIGNAL_BDS_ENA,
+            UBXConstellation::GLONASS => SIGNAL_GLO_ENA,
+            UBXConstellation::GPS => SIGNAL_GPS_ENA,
+            UBXConstellation::Galileo => SIGNAL_GAL_ENA,
+            UBXConstellation::QZSS => SIGNAL_QZSS_ENA,
+            UBXConstellation::SBAS => SIGNAL_SBAS_ENA,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[serde(rename_all = "lowercase")]
+pub enum UBXLayer {
+    RAM,
+    BBR,
+    Flash,
+}
+
+impl UBXLayer {
+    /// Bit for this layer in the VALSET and VALDEL layers mask
+    pub fn mask(&self) -> u8 {
+        match self {
+            UBXLayer::RAM => 0x01,
+            UBXLayer::BBR => 0x02,
+            UBXLayer::Flash => 0x04,
+        }
+    }
+
+    /// Layer number for VALGET
+    pub fn number(&self) -> u8 {
+        match self {
+            UBXLayer::RAM => 0,
+            UBXLayer::BBR => 1,
+            UBXLayer::Flash => 2,
+        }
+    }
+}
+
+fn layer_mask(layers: &[UBXLayer]) -> u8 {
+    layers.iter().fold(0, |mask, layer| mask | layer.mask())
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UBXConfigItem {
+    pub key: u32,
+    pub value: u64,
+}
+
+impl UBXConfigItem {
+    pub fn new(key: u32, value: u64) -> Self {
+        UBXConfigItem { key, value }
+    }
+
+    fn encode(&self, payload: &mut Vec<u8>) {
+        payload.extend_from_slice(&self.key.to_le_bytes());
+        payload.extend_from_slice(&self.value.to_le_bytes()[..value_size(self.key)]);
+    }
+}
+
+/// Builds a CFG-VALSET message setting `items` in each of `layers`
+pub fn valset(layers: &[UBXLayer], items: &[UBXConfigItem]) -> UBXFrame {
+    let mut payload = vec![0x00, layer_mask(layers), 0x00, 0x00];
+
+    for item in items {
+        item.encode(&mut payload);
+    }
+
+    UBXFrame {
+        class: CFG_VALSET.0,
+        id: CFG_VALSET.1,
+        payload,
+    }
+}
+
+/// Builds a CFG-VALGET poll for `keys` in `layer`
+pub fn valget(layer: &UBXLayer, keys: &[u32]) -> UBXFrame {
+    let mut payload = vec![0x00, layer.number(), 0x00, 0x00];
+
+    for key in keys {
+        payload.extend_from_slice(&key.to_le_bytes());
+    }
+
+    UBXFrame {
+        class: CFG_VALGET.0,
+        id: CFG_VALGET.1,
+        payload,
+    }
+}
+
+/// Builds a CFG-VALDEL message removing `keys` from each of `layers`.
+///
+/// Only the BBR and Flash layers can be deleted from, the RAM layer is ignored by the receiver.
+pub fn valdel(layers: &[UBXLayer], keys: &[u32]) -> UBXFrame {
+    let mut payload = vec![0x00, layer_mask(layers), 0x00, 0x00];
+
+    for key in keys {
+        payload.extend_from_slice(&key.to_le_bytes());
+    }
+
+    UBXFrame {
+        class: CFG_VALDEL.0,
+        id: CFG_VALDEL.1,
+        payload,
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UBXCfgValGet {
+    pub received: Option<Duration>,
+    pub layer: u8,
+    pub position: u16,
+    pub items: Vec<UBXConfigItem>,
+}
+
+pub(crate) fn config_item(input: &[u8]) -> Result<&[u8], UBXConfigItem> {
+    let (input, key) = le_u32(input)?;
+
+    let (input, value) = match value_size(key) {
+        1 => map(byte, u64::from)(input)?,
+        2 => map(le_u16, u64::from)(input)?,
+        4 => map(le_u32, u64::from)(input)?,
+        _ => le_u64(input)?,
+    };
+
+    Ok((input, UBXConfigItem { key, value }))
+}
+
+pub(crate) fn cfg_valget(input: &[u8]) -> Result<&[u8], UBXCfgValGet> {
+    context(
+        "CFG-VALGET",
+        all_consuming(map(
+            tuple((take(1usize), byte, le_u16, many0(config_item))),
+            |(_version, layer, position, items)| UBXCfgValGet {
+                received: None,
+                layer,
+                position,
+                items,
+            },
+        )),
+    )(input)
+}
@@ -0,0 +1,140 @@
+use crate::nmea::parser::Result;
+use nom::{
+    bytes::streaming::{tag, take},
+    number::streaming::{le_u16, u8 as byte},
+    sequence::tuple,
+    Err,
+};
+
+pub const SYNC_CHAR_1: u8 = 0xB5;
+pub const SYNC_CHAR_2: u8 = 0x62;
+
+/// Largest payload we are willing to wait for.
+///
+/// A corrupted length field would otherwise stall the stream until 64KiB of data arrived.  The
+/// largest messages a ZED-F9P sends (RXM-RAWX, NAV-SAT) fit comfortably in this limit.
+pub const MAX_PAYLOAD: u16 = 8192;
+
+/// A checksummed UBX frame with its payload still encoded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UBXFrame {
+    pub class: u8,
+    pub id: u8,
+    pub payload: Vec<u8>,
+}
+
+impl UBXFrame {
+    /// Encodes this frame with sync characters, length, and checksum
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.payload.len() + 8);
+
+        bytes.extend_from_slice(&[SYNC_CHAR_1, SYNC_CHAR_2, self.class, self.id]);
+        bytes.extend_from_slice(&(self.payload.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(&self.payload);
+
+        let checksum = checksum(&bytes[2..]);
+
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+
+        bytes
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UBXChecksumMismatch {
+    pub class: u8,
+    pub id: u8,
+    pub given: u16,
+    pub calculated: u16,
+}
+
+/// A checksummed UBX frame borrowing its payload from the input buffer
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UBXFrameView<'a> {
+    pub class: u8,
+    pub id: u8,
+    pub payload: &'a [u8],
+}
+
+impl<'a> UBXFrameView<'a> {
+    pub fn to_frame(&self) -> UBXFrame {
+        UBXFrame {
+            class: self.class,
+            id: self.id,
+            payload: self.payload.to_vec(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UBXFrameResult<F = UBXFrame> {
+    InvalidChecksum(UBXChecksumMismatch),
+    Valid(F),
+}
+
+/// Parses one UBX frame beginning with the sync characters.
+///
+/// When the checksum does not match only the sync characters are consumed so the caller can
+/// resynchronize on the following bytes, a corrupted length field would otherwise swallow valid
+/// data.
+#[cfg(test)]
+pub(crate) fn frame(input: &[u8]) -> Result<&[u8], UBXFrameResult> {
+    let (input, result) = frame_view(input)?;
+
+    let result = match result {
+        UBXFrameResult::InvalidChecksum(mismatch) => UBXFrameResult::InvalidChecksum(mismatch),
+        UBXFrameResult::Valid(view) => UBXFrameResult::Valid(view.to_frame()),
+    };
+
+    Ok((input, result))
+}
+
+/// Parses one UBX frame like `frame()` without copying the payload
+pub(crate) fn frame_view(input: &[u8]) -> Result<&[u8], UBXFrameResult<UBXFrameView<'_>>> {
+    let start = input;
+
+    let (input, (_, class, id, length)) =
+        tuple((tag([SYNC_CHAR_1, SYNC_CHAR_2]), byte, byte, le_u16))(input)?;
+
+    if length > MAX_PAYLOAD {
+        return Err(Err::Error(nom::error::VerboseError {
+            errors: vec![(
+                start,
+                nom::error::VerboseErrorKind::Context("UBX payload too long"),
+            )],
+        }));
+    }
+
+    let (input, (payload, given)) = tuple((take(length), le_u16))(input)?;
+
+    let calculated = checksum(&start[2..6 + length as usize]);
+
+    if given != calculated {
+        let mismatch = UBXChecksumMismatch {
+            class,
+            id,
+            given,
+            calculated,
+        };
+
+        return Ok((&start[2..], UBXFrameResult::InvalidChecksum(mismatch)));
+    }
+
+    let frame = UBXFrameView { class, id, payload };
+
+    Ok((input, UBXFrameResult::Valid(frame)))
+}
+
+/// Calculates the 8-bit Fletcher checksum over the class, id, length, and payload.
+///
+/// The returned value has CK_A in the low byte and CK_B in the high byte so it compares equal to
+/// the two checksum bytes read as a little-endian u16.
+pub fn checksum(input: &[u8]) -> u16 {
+    let (a, b) = input.iter().fold((0u8, 0u8), |(a, b), c| {
+        let a = a.wrapping_add(*c);
+
+        (a, b.wrapping_add(a))
+    });
+
+    u16::from_le_bytes([a, b])
+}
@@ -0,0 +1,40 @@
+// For the u-blox UBX binary protocol
+
+pub mod cfg;
+pub(crate) mod frame;
+pub mod parser;
+
+pub use cfg::UBXCfgValGet;
+pub use cfg::UBXConfigItem;
+pub use cfg::UBXLayer;
+pub use frame::checksum;
+pub use frame::UBXChecksumMismatch;
+pub use frame::UBXFrame;
+pub use frame::UBXFrameView;
+pub use parser::message;
+pub use parser::UBXAck;
+pub use parser::UBXCarrierSolution;
+pub use parser::UBXFixType;
+pub use parser::UBXGnssId;
+pub use parser::UBXHealth;
+pub use parser::UBXMessage;
+pub use parser::UBXNavPVT;
+pub use parser::UBXNavRelPosNED;
+pub use parser::UBXNavSat;
+pub use parser::UBXNavSatellite;
+pub use parser::UBXNavStatus;
+pub use parser::UBXNavTimeLS;
+pub use parser::UBXNavTimeUTC;
+pub use parser::UBXSignalQuality;
+pub use parser::UBXTimTP;
+pub use parser::UBXTimeBase;
+pub use parser::UBXUTCStandard;
+
+#[cfg(test)]
+mod test_cfg;
+
+#[cfg(test)]
+mod test_frame;
+
+#[cfg(test)]
+mod test_parser;
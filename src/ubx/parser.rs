@@ -0,0 +1,848 @@
+use crate::{
+    nmea::parser::Result,
+    ubx::{
+        cfg::{cfg_valget, UBXCfgValGet, CFG_VALGET},
+        frame::{UBXChecksumMismatch, UBXFrame},
+    },
+};
+use chrono::naive::{NaiveDate, NaiveDateTime};
+use nom::{
+    bytes::complete::take,
+    combinator::{all_consuming, map},
+    error::context,
+    multi::count,
+    number::complete::{le_i16, le_i32, le_i8, le_u16, le_u32, u8 as byte},
+    sequence::tuple,
+    Err,
+};
+use std::time::Duration;
+
+pub const CLASS_NAV: u8 = 0x01;
+pub const CLASS_ACK: u8 = 0x05;
+pub const CLASS_TIM: u8 = 0x0D;
+
+pub const ACK_ACK: (u8, u8) = (CLASS_ACK, 0x01);
+pub const ACK_NAK: (u8, u8) = (CLASS_ACK, 0x00);
+
+pub const NAV_PVT: (u8, u8) = (CLASS_NAV, 0x07);
+pub const NAV_RELPOSNED: (u8, u8) = (CLASS_NAV, 0x3C);
+pub const NAV_SAT: (u8, u8) = (CLASS_NAV, 0x35);
+pub const NAV_STATUS: (u8, u8) = (CLASS_NAV, 0x03);
+pub const NAV_TIMELS: (u8, u8) = (CLASS_NAV, 0x26);
+pub const NAV_TIMEUTC: (u8, u8) = (CLASS_NAV, 0x21);
+pub const TIM_TP: (u8, u8) = (CLASS_TIM, 0x01);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum UBXMessage {
+    Ack(UBXAck),
+    CfgValGet(UBXCfgValGet),
+    Nak(UBXAck),
+    NavPVT(UBXNavPVT),
+    NavRelPosNED(UBXNavRelPosNED),
+    NavSat(UBXNavSat),
+    NavStatus(UBXNavStatus),
+    NavTimeLS(UBXNavTimeLS),
+    NavTimeUTC(UBXNavTimeUTC),
+    TimTP(UBXTimTP),
+    InvalidChecksum(UBXChecksumMismatch),
+    ParseError(UBXFrame),
+    Unsupported(UBXFrame),
+}
+
+/// Decodes the payload of `frame` into a typed message.
+///
+/// Frames for messages we don't know how to decode are returned as `Unsupported`, frames for
+/// known messages whose payload doesn't decode are returned as `ParseError`.
+pub fn message(frame: UBXFrame, received: Duration) -> UBXMessage {
+    let payload = frame.payload.as_slice();
+
+    let result = match (frame.class, frame.id) {
+        ACK_ACK => ack(payload).map(|(_, mut m)| {
+            m.received = Some(received);
+            UBXMessage::Ack(m)
+        }),
+        ACK_NAK => ack(payload).map(|(_, mut m)| {
+            m.received = Some(received);
+            UBXMessage::Nak(m)
+        }),
+        CFG_VALGET => cfg_valget(payload).map(|(_, mut m)| {
+            m.received = Some(received);
+            UBXMessage::CfgValGet(m)
+        }),
+        NAV_PVT => nav_pvt(payload).map(|(_, mut m)| {
+            m.received = Some(received);
+            UBXMessage::NavPVT(m)
+        }),
+        NAV_RELPOSNED => nav_relposned(payload).map(|(_, mut m)| {
+            m.received = Some(received);
+            UBXMessage::NavRelPosNED(m)
+        }),
+        NAV_SAT => nav_sat(payload).map(|(_, mut m)| {
+            m.received = Some(received);
+            UBXMessage::NavSat(m)
+        }),
+        NAV_STATUS => nav_status(payload).map(|(_, mut m)| {
+            m.received = Some(received);
+            UBXMessage::NavStatus(m)
+        }),
+        NAV_TIMELS => nav_timels(payload).map(|(_, mut m)| {
+            m.received = Some(received);
+            UBXMessage::NavTimeLS(m)
+        }),
+        NAV_TIMEUTC => nav_timeutc(payload).map(|(_, mut m)| {
+            m.received = Some(received);
+            UBXMessage::NavTimeUTC(m)
+        }),
+        TIM_TP => tim_tp(payload).map(|(_, mut m)| {
+            m.received = Some(received);
+            UBXMessage::TimTP(m)
+        }),
+        _ => return UBXMessage::Unsupported(frame),
+    };
+
+    match result {
+        Ok(message) => message,
+        Err(Err::Error(_)) | Err(Err::Failure(_)) | Err(Err::Incomplete(_)) => {
+            UBXMessage::ParseError(frame)
+        }
+    }
+}
+
+/// Acknowledgement of an input message, or rejection for ACK-NAK
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UBXAck {
+    pub received: Option<Duration>,
+    pub class: u8,
+    pub id: u8,
+}
+
+pub(crate) fn ack(input: &[u8]) -> Result<&[u8], UBXAck> {
+    context(
+        "ACK",
+        all_consuming(map(tuple((byte, byte)), |(class, id)| UBXAck {
+            received: None,
+            class,
+            id,
+        })),
+    )(input)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UBXFixType {
+    NoFix,
+    DeadReckoning,
+    Fix2D,
+    Fix3D,
+    GNSSDeadReckoning,
+    TimeOnly,
+    Unknown(u8),
+}
+
+impl From<u8> for UBXFixType {
+    fn from(fix_type: u8) -> Self {
+        match fix_type {
+            0 => UBXFixType::NoFix,
+            1 => UBXFixType::DeadReckoning,
+            2 => UBXFixType::Fix2D,
+            3 => UBXFixType::Fix3D,
+            4 => UBXFixType::GNSSDeadReckoning,
+            5 => UBXFixType::TimeOnly,
+            u => UBXFixType::Unknown(u),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UBXCarrierSolution {
+    None,
+    Float,
+    Fixed,
+}
+
+impl From<u8> for UBXCarrierSolution {
+    fn from(flags: u8) -> Self {
+        match (flags >> 6) & 0x03 {
+            1 => UBXCarrierSolution::Float,
+            2 => UBXCarrierSolution::Fixed,
+            _ => UBXCarrierSolution::None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UBXNavPVT {
+    pub received: Option<Duration>,
+    pub time_of_week: u32,
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub valid_date: bool,
+    pub valid_time: bool,
+    pub fully_resolved: bool,
+    /// Time accuracy estimate in nanoseconds
+    pub time_accuracy: u32,
+    /// Fraction of a second in nanoseconds, may be negative
+    pub nano: i32,
+    pub fix_type: UBXFixType,
+    pub gnss_fix_ok: bool,
+    pub differential: bool,
+    pub carrier_solution: UBXCarrierSolution,
+    pub num_satellites: u8,
+    /// Degrees
+    pub longitude: f64,
+    /// Degrees
+    pub latitude: f64,
+    /// Height above the ellipsoid in meters
+    pub height: f64,
+    /// Height above mean sea level in meters
+    pub height_msl: f64,
+    /// Horizontal accuracy estimate in meters
+    pub horizontal_accuracy: f64,
+    /// Vertical accuracy estimate in meters
+    pub vertical_accuracy: f64,
+    /// North velocity in m/s
+    pub velocity_north: f64,
+    /// East velocity in m/s
+    pub velocity_east: f64,
+    /// Down velocity in m/s
+    pub velocity_down: f64,
+    /// Ground speed in m/s
+    pub ground_speed: f64,
+    /// Heading of motion in degrees
+    pub heading_of_motion: f64,
+    /// Speed accuracy estimate in m/s
+    pub speed_accuracy: f64,
+    /// Heading accuracy estimate in degrees
+    pub heading_accuracy: f64,
+    pub pdop: f32,
+    pub invalid_llh: bool,
+    /// Heading of vehicle in degrees, only valid when `heading_of_vehicle_valid` is set
+    pub heading_of_vehicle: f64,
+    pub heading_of_vehicle_valid: bool,
+    /// Magnetic declination in degrees
+    pub magnetic_declination: f32,
+    /// Magnetic declination accuracy in degrees
+    pub magnetic_declination_accuracy: f32,
+}
+
+impl UBXNavPVT {
+    /// The UTC time of this solution if the receiver has valid date and time
+    pub fn date_time(&self) -> Option<NaiveDateTime> {
+        if !(self.valid_date && self.valid_time) {
+            return None;
+        }
+
+        let date = NaiveDate::from_ymd_opt(self.year.into(), self.month.into(), self.day.into())?;
+        let date_time =
+            date.and_hms_opt(self.hour.into(), self.minute.into(), self.second.into())?;
+
+        Some(date_time + chrono::Duration::nanoseconds(self.nano.into()))
+    }
+}
+
+pub(crate) fn nav_pvt(input: &[u8]) -> Result<&[u8], UBXNavPVT> {
+    context(
+        "NAV-PVT",
+        all_consuming(map(
+            tuple((
+                tuple((
+                    le_u32, le_u16, byte, byte, byte, byte, byte, byte, le_u32, le_i32, byte, byte,
+                    byte, byte,
+                )),
+                tuple((
+                    le_i32, le_i32, le_i32, le_i32, le_u32, le_u32, le_i32, le_i32, le_i32, le_i32,
+                    le_i32, le_u32, le_u32, le_u16,
+                )),
+                tuple((byte, take(5usize), le_i32, le_i16, le_u16)),
+            )),
+            |(
+                (
+                    time_of_week,
+                    year,
+                    month,
+                    day,
+                    hour,
+                    minute,
+                    second,
+                    valid,
+                    time_accuracy,
+                    nano,
+                    fix_type,
+                    flags,
+                    _flags2,
+                    num_satellites,
+                ),
+                (
+                    lon,
+                    lat,
+                    height,
+                    height_msl,
+                    h_acc,
+                    v_acc,
+                    vel_n,
+                    vel_e,
+                    vel_d,
+                    g_speed,
+                    head_mot,
+                    s_acc,
+                    head_acc,
+                    pdop,
+                ),
+                (flags3, _reserved, head_veh, mag_dec, mag_acc),
+            )| UBXNavPVT {
+                received: None,
+                time_of_week,
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                second,
+                valid_date: valid & 0x01 != 0,
+                valid_time: valid & 0x02 != 0,
+                fully_resolved: valid & 0x04 != 0,
+                time_accuracy,
+                nano,
+                fix_type: fix_type.into(),
+                gnss_fix_ok: flags & 0x01 != 0,
+                differential: flags & 0x02 != 0,
+                carrier_solution: flags.into(),
+                num_satellites,
+                longitude: f64::from(lon) * 1e-7,
+                latitude: f64::from(lat) * 1e-7,
+                height: f64::from(height) * 1e-3,
+                height_msl: f64::from(height_msl) * 1e-3,
+                horizontal_accuracy: f64::from(h_acc) * 1e-3,
+                vertical_accuracy: f64::from(v_acc) * 1e-3,
+                velocity_north: f64::from(vel_n) * 1e-3,
+                velocity_east: f64::from(vel_e) * 1e-3,
+                velocity_down: f64::from(vel_d) * 1e-3,
+                ground_speed: f64::from(g_speed) * 1e-3,
+                heading_of_motion: f64::from(head_mot) * 1e-5,
+                speed_accuracy: f64::from(s_acc) * 1e-3,
+                heading_accuracy: f64::from(head_acc) * 1e-5,
+                pdop: f32::from(pdop) * 0.01,
+                invalid_llh: flags3 & 0x01 != 0,
+                heading_of_vehicle: f64::from(head_veh) * 1e-5,
+                heading_of_vehicle_valid: flags & 0x20 != 0,
+                magnetic_declination: f32::from(mag_dec) * 0.01,
+                magnetic_declination_accuracy: f32::from(mag_acc) * 0.01,
+            },
+        )),
+    )(input)
+}
+
+/// Position of a rover relative to its reference station, or of a moving base rover relative to
+/// the moving base.  With two antennas this gives the heading and pitch of the baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UBXNavRelPosNED {
+    pub received: Option<Duration>,
+    pub reference_station_id: u16,
+    pub time_of_week: u32,
+    /// North component of the baseline in meters
+    pub north: f64,
+    /// East component of the baseline in meters
+    pub east: f64,
+    /// Down component of the baseline in meters
+    pub down: f64,
+    /// Length of the baseline in meters
+    pub length: f64,
+    /// Heading of the baseline in degrees, only valid when `heading_valid` is set
+    pub heading: f64,
+    /// North accuracy estimate in meters
+    pub north_accuracy: f64,
+    /// East accuracy estimate in meters
+    pub east_accuracy: f64,
+    /// Down accuracy estimate in meters
+    pub down_accuracy: f64,
+    /// Baseline length accuracy estimate in meters
+    pub length_accuracy: f64,
+    /// Heading accuracy estimate in degrees
+    pub heading_accuracy: f64,
+    pub gnss_fix_ok: bool,
+    pub differential: bool,
+    pub relative_position_valid: bool,
+    pub carrier_solution: UBXCarrierSolution,
+    pub moving_base: bool,
+    pub reference_position_missing: bool,
+    pub reference_observations_missing: bool,
+    pub heading_valid: bool,
+    pub normalized: bool,
+}
+
+impl UBXNavRelPosNED {
+    /// Pitch of the baseline in degrees, positive when the rover antenna is above the base
+    pub fn pitch(&self) -> f64 {
+        let horizontal = self.north.hypot(self.east);
+
+        (-self.down).atan2(horizontal).to_degrees()
+    }
+}
+
+// Components are reported in centimeters with an additional high-precision part in 0.1 mm
+fn high_precision(cm: i32, hp: i8) -> f64 {
+    f64::from(cm) * 1e-2 + f64::from(hp) * 1e-4
+}
+
+pub(crate) fn nav_relposned(input: &[u8]) -> Result<&[u8], UBXNavRelPosNED> {
+    context(
+        "NAV-RELPOSNED",
+        all_consuming(map(
+            tuple((
+                tuple((
+                    byte,
+                    take(1usize),
+                    le_u16,
+                    le_u32,
+                    le_i32,
+                    le_i32,
+                    le_i32,
+                    le_i32,
+                    le_i32,
+                    take(4usize),
+                )),
+                tuple((le_i8, le_i8, le_i8, le_i8)),
+                tuple((le_u32, le_u32, le_u32, le_u32, le_u32, take(4usize), le_u32)),
+            )),
+            |(
+                (
+                    _version,
+                    _reserved1,
+                    reference_station_id,
+                    time_of_week,
+                    n,
+                    e,
+                    d,
+                    length,
+                    heading,
+                    _reserved2,
+                ),
+                (hp_n, hp_e, hp_d, hp_length),
+                (acc_n, acc_e, acc_d, acc_length, acc_heading, _reserved3, flags),
+            )| UBXNavRelPosNED {
+                received: None,
+                reference_station_id,
+                time_of_week,
+                north: high_precision(n, hp_n),
+                east: high_precision(e, hp_e),
+                down: high_precision(d, hp_d),
+                length: high_precision(length, hp_length),
+                heading: f64::from(heading) * 1e-5,
+                north_accuracy: f64::from(acc_n) * 1e-4,
+                east_accuracy: f64::from(acc_e) * 1e-4,
+                down_accuracy: f64::from(acc_d) * 1e-4,
+                length_accuracy: f64::from(acc_length) * 1e-4,
+                heading_accuracy: f64::from(acc_heading) * 1e-5,
+                gnss_fix_ok: flags & 0x01 != 0,
+                differential: flags & 0x02 != 0,
+                relative_position_valid: flags & 0x04 != 0,
+                // carrSoln is in bits 3-4 here rather than bits 6-7 as in NAV-PVT
+                carrier_solution: UBXCarrierSolution::from((((flags >> 3) & 0x03) as u8) << 6),
+                moving_base: flags & 0x20 != 0,
+                reference_position_missing: flags & 0x40 != 0,
+                reference_observations_missing: flags & 0x80 != 0,
+                heading_valid: flags & 0x100 != 0,
+                normalized: flags & 0x200 != 0,
+            },
+        )),
+    )(input)
+}
+
+/// GNSS identifiers used by UBX messages
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UBXGnssId {
+    GPS,
+    SBAS,
+    Galileo,
+    BeiDou,
+    IMES,
+    QZSS,
+    GLONASS,
+    NavIC,
+    Unknown(u8),
+}
+
+impl From<u8> for UBXGnssId {
+    fn from(gnss_id: u8) -> Self {
+        match gnss_id {
+            0 => UBXGnssId::GPS,
+            1 => UBXGnssId::SBAS,
+            2 => UBXGnssId::Galileo,
+            3 => UBXGnssId::BeiDou,
+            4 => UBXGnssId::IMES,
+            5 => UBXGnssId::QZSS,
+            6 => UBXGnssId::GLONASS,
+            7 => UBXGnssId::NavIC,
+            u => UBXGnssId::Unknown(u),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UBXSignalQuality {
+    NoSignal,
+    Searching,
+    Acquired,
+    Unusable,
+    CodeLocked,
+    CarrierLocked,
+}
+
+impl From<u32> for UBXSignalQuality {
+    fn from(flags: u32) -> Self {
+        match flags & 0x07 {
+            0 => UBXSignalQuality::NoSignal,
+            1 => UBXSignalQuality::Searching,
+            2 => UBXSignalQuality::Acquired,
+            3 => UBXSignalQuality::Unusable,
+            4 => UBXSignalQuality::CodeLocked,
+            _ => UBXSignalQuality::CarrierLocked,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UBXHealth {
+    Unknown,
+    Healthy,
+    Unhealthy,
+}
+
+impl From<u32> for UBXHealth {
+    fn from(flags: u32) -> Self {
+        match (flags >> 4) & 0x03 {
+            1 => UBXHealth::Healthy,
+            2 => UBXHealth::Unhealthy,
+            _ => UBXHealth::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UBXNavSatellite {
+    pub gnss_id: UBXGnssId,
+    pub sv_id: u8,
+    /// Carrier to noise ratio in dBHz
+    pub cno: u8,
+    /// Degrees, -91 when unknown
+    pub elevation: i8,
+    /// Degrees
+    pub azimuth: i16,
+    /// Pseudorange residual in meters
+    pub pseudorange_residual: f32,
+    pub quality: UBXSignalQuality,
+    pub used: bool,
+    pub health: UBXHealth,
+    pub differential: bool,
+}
+
+pub(crate) fn nav_satellite(input: &[u8]) -> Result<&[u8], UBXNavSatellite> {
+    map(
+        tuple((byte, byte, byte, le_i8, le_i16, le_i16, le_u32)),
+        |(gnss_id, sv_id, cno, elevation, azimuth, pr_res, flags)| UBXNavSatellite {
+            gnss_id: gnss_id.into(),
+            sv_id,
+            cno,
+            elevation,
+            azimuth,
+            pseudorange_residual: f32::from(pr_res) * 0.1,
+            quality: flags.into(),
+            used: flags & 0x08 != 0,
+            health: flags.into(),
+            differential: flags & 0x40 != 0,
+        },
+    )(input)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UBXNavSat {
+    pub received: Option<Duration>,
+    pub time_of_week: u32,
+    pub version: u8,
+    pub satellites: Vec<UBXNavSatellite>,
+}
+
+pub(crate) fn nav_sat(input: &[u8]) -> Result<&[u8], UBXNavSat> {
+    let (input, (time_of_week, version, num_svs, _)) =
+        context("NAV-SAT", tuple((le_u32, byte, byte, take(2usize))))(input)?;
+
+    context(
+        "NAV-SAT",
+        all_consuming(map(
+            count(nav_satellite, num_svs.into()),
+            move |satellites| UBXNavSat {
+                received: None,
+                time_of_week,
+                version,
+                satellites,
+            },
+        )),
+    )(input)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UBXNavStatus {
+    pub received: Option<Duration>,
+    pub time_of_week: u32,
+    pub fix_type: UBXFixType,
+    pub gnss_fix_ok: bool,
+    pub differential: bool,
+    pub week_valid: bool,
+    pub time_of_week_valid: bool,
+    pub differential_corrections: bool,
+    pub carrier_solution: UBXCarrierSolution,
+    /// Time to first fix in milliseconds
+    pub ttff: u32,
+    /// Milliseconds since startup or reset
+    pub msss: u32,
+}
+
+pub(crate) fn nav_status(input: &[u8]) -> Result<&[u8], UBXNavStatus> {
+    context(
+        "NAV-STATUS",
+        all_consuming(map(
+            tuple((le_u32, byte, byte, byte, byte, le_u32, le_u32)),
+            |(time_of_week, fix_type, flags, fix_stat, flags2, ttff, msss)| UBXNavStatus {
+                received: None,
+                time_of_week,
+                fix_type: fix_type.into(),
+                gnss_fix_ok: flags & 0x01 != 0,
+                differential: flags & 0x02 != 0,
+                week_valid: flags & 0x04 != 0,
+                time_of_week_valid: flags & 0x08 != 0,
+                differential_corrections: fix_stat & 0x01 != 0,
+                carrier_solution: flags2.into(),
+                ttff,
+                msss,
+            },
+        )),
+    )(input)
+}
+
+/// Leap second information
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UBXNavTimeLS {
+    pub received: Option<Duration>,
+    pub time_of_week: u32,
+    pub version: u8,
+    /// Source of `current`: 0 firmware default, 1 GPS and GLONASS time difference, 2 GPS, 3 SBAS,
+    /// 4 BeiDou, 5 Galileo, 6 aiding data, 7 configured, 255 unknown
+    pub current_source: u8,
+    /// Leap seconds since the GPS epoch, the difference between GPS time and UTC
+    pub current: i8,
+    /// Source of `change`: 0 none, 2 GPS, 3 SBAS, 4 BeiDou, 5 Galileo, 6 GLONASS
+    pub change_source: u8,
+    /// The leap second change at the next event, -1, 0, or 1
+    pub change: i8,
+    /// Seconds until the next leap second event, negative after the event
+    pub time_to_event: i32,
+    /// GPS week number of the next leap second event
+    pub event_week: u16,
+    /// GPS day of the week of the next leap second event
+    pub event_day: u16,
+    pub valid_current: bool,
+    pub valid_time_to_event: bool,
+}
+
+pub(crate) fn nav_timels(input: &[u8]) -> Result<&[u8], UBXNavTimeLS> {
+    context(
+        "NAV-TIMELS",
+        all_consuming(map(
+            tuple((
+                le_u32,
+                byte,
+                take(3usize),
+                byte,
+                le_i8,
+                byte,
+                le_i8,
+                le_i32,
+                le_u16,
+                le_u16,
+                take(3usize),
+                byte,
+            )),
+            |(
+                time_of_week,
+                version,
+                _,
+                current_source,
+                current,
+                change_source,
+                change,
+                time_to_event,
+                event_week,
+                event_day,
+                _,
+                valid,
+            )| UBXNavTimeLS {
+                received: None,
+                time_of_week,
+                version,
+                current_source,
+                current,
+                change_source,
+                change,
+                time_to_event,
+                event_week,
+                event_day,
+                valid_current: valid & 0x01 != 0,
+                valid_time_to_event: valid & 0x02 != 0,
+            },
+        )),
+    )(input)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UBXUTCStandard {
+    Unknown,
+    CRL,
+    NIST,
+    USNO,
+    BIPM,
+    EU,
+    SU,
+    NTSC,
+    NPLI,
+    Unhandled(u8),
+}
+
+impl From<u8> for UBXUTCStandard {
+    fn from(standard: u8) -> Self {
+        match standard {
+            0 => UBXUTCStandard::Unknown,
+            1 => UBXUTCStandard::CRL,
+            2 => UBXUTCStandard::NIST,
+            3 => UBXUTCStandard::USNO,
+            4 => UBXUTCStandard::BIPM,
+            5 => UBXUTCStandard::EU,
+            6 => UBXUTCStandard::SU,
+            7 => UBXUTCStandard::NTSC,
+            8 => UBXUTCStandard::NPLI,
+            u => UBXUTCStandard::Unhandled(u),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UBXNavTimeUTC {
+    pub received: Option<Duration>,
+    pub time_of_week: u32,
+    /// Time accuracy estimate in nanoseconds
+    pub time_accuracy: u32,
+    /// Fraction of a second in nanoseconds, may be negative
+    pub nano: i32,
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub valid_time_of_week: bool,
+    pub valid_week: bool,
+    pub valid_utc: bool,
+    pub utc_standard: UBXUTCStandard,
+}
+
+impl UBXNavTimeUTC {
+    /// The UTC time if the receiver has a valid UTC time
+    pub fn date_time(&self) -> Option<NaiveDateTime> {
+        if !self.valid_utc {
+            return None;
+        }
+
+        let date = NaiveDate::from_ymd_opt(self.year.into(), self.month.into(), self.day.into())?;
+        let date_time =
+            date.and_hms_opt(self.hour.into(), self.minute.into(), self.second.into())?;
+
+        Some(date_time + chrono::Duration::nanoseconds(self.nano.into()))
+    }
+}
+
+pub(crate) fn nav_timeutc(input: &[u8]) -> Result<&[u8], UBXNavTimeUTC> {
+    context(
+        "NAV-TIMEUTC",
+        all_consuming(map(
+            tuple((
+                le_u32, le_u32, le_i32, le_u16, byte, byte, byte, byte, byte, byte,
+            )),
+            |(time_of_week, time_accuracy, nano, year, month, day, hour, minute, second, valid)| {
+                UBXNavTimeUTC {
+                    received: None,
+                    time_of_week,
+                    time_accuracy,
+                    nano,
+                    year,
+                    month,
+                    day,
+                    hour,
+                    minute,
+                    second,
+                    valid_time_of_week: valid & 0x01 != 0,
+                    valid_week: valid & 0x02 != 0,
+                    valid_utc: valid & 0x04 != 0,
+                    utc_standard: (valid >> 4).into(),
+                }
+            },
+        )),
+    )(input)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UBXTimeBase {
+    GNSS,
+    UTC,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UBXTimTP {
+    pub received: Option<Duration>,
+    /// Time of week of the next time pulse in milliseconds
+    pub time_of_week_ms: u32,
+    /// Sub-millisecond part of the time of week, scaled by 2^-32
+    pub time_of_week_sub_ms: u32,
+    /// Quantization error of the time pulse in picoseconds
+    pub quantization_error: i32,
+    pub week: u16,
+    pub time_base: UBXTimeBase,
+    pub utc_available: bool,
+    pub quantization_error_valid: bool,
+    pub utc_standard: UBXUTCStandard,
+}
+
+impl UBXTimTP {
+    /// Time of week of the next time pulse in seconds
+    pub fn time_of_week(&self) -> f64 {
+        (f64::from(self.time_of_week_ms) + f64::from(self.time_of_week_sub_ms) / 2f64.powi(32))
+            / 1e3
+    }
+}
+
+pub(crate) fn tim_tp(input: &[u8]) -> Result<&[u8], UBXTimTP> {
+    context(
+        "TIM-TP",
+        all_consuming(map(
+            tuple((le_u32, le_u32, le_i32, le_u16, byte, byte)),
+            |(time_of_week_ms, time_of_week_sub_ms, quantization_error, week, flags, ref_info)| {
+                UBXTimTP {
+                    received: None,
+                    time_of_week_ms,
+                    time_of_week_sub_ms,
+                    quantization_error,
+                    week,
+                    time_base: if flags & 0x01 == 0 {
+                        UBXTimeBase::GNSS
+                    } else {
+                        UBXTimeBase::UTC
+                    },
+                    utc_available: flags & 0x02 != 0,
+                    quantization_error_valid: flags & 0x10 == 0,
+                    utc_standard: (ref_info >> 4).into(),
+                }
+            },
+        )),
+    )(input)
+}
@@ -0,0 +1,88 @@
+use crate::ubx::{cfg::*, frame::frame, frame::UBXFrameResult, message, UBXMessage};
+use std::time::Duration;
+
+#[test]
+fn test_value_size() {
+    assert_eq!(1, value_size(SIGNAL_GPS_ENA));
+    assert_eq!(1, value_size(NAVSPG_DYNMODEL));
+    assert_eq!(2, value_size(RATE_MEAS));
+    assert_eq!(4, value_size(0x40520001));
+    assert_eq!(8, value_size(0x50360006));
+}
+
+#[test]
+fn test_msgout_key() {
+    assert_eq!(Some(0x209100AB), msgout_key("RMC"));
+    assert_eq!(Some(0x20910006), msgout_key("UBX-NAV-PVT"));
+    assert_eq!(None, msgout_key("TXT"));
+}
+
+#[test]
+fn test_valset() {
+    let items = vec![
+        UBXConfigItem::new(RATE_MEAS, 500),
+        UBXConfigItem::new(NAVSPG_DYNMODEL, UBXDynamicModel::Stationary.value()),
+    ];
+
+    let frame = valset(&[UBXLayer::RAM, UBXLayer::BBR], &items);
+
+    let expected = vec![
+        0xB5, 0x62, 0x06, 0x8A, 0x0F, 0x00, 0x00, 0x03, 0x00, 0x00, 0x01, 0x00, 0x21, 0x30, 0xF4,
+        0x01, 0x21, 0x00, 0x11, 0x20, 0x02, 0x3D, 0xCF,
+    ];
+
+    assert_eq!(expected, frame.to_bytes());
+}
+
+#[test]
+fn test_valget() {
+    let frame = valget(&UBXLayer::RAM, &[RATE_MEAS, NAVSPG_DYNMODEL]);
+
+    let expected = vec![
+        0xB5, 0x62, 0x06, 0x8B, 0x0C, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x21, 0x30, 0x21,
+        0x00, 0x11, 0x20, 0x41, 0xB1,
+    ];
+
+    assert_eq!(expected, frame.to_bytes());
+}
+
+#[test]
+fn test_valdel() {
+    let frame = valdel(&[UBXLayer::BBR, UBXLayer::Flash], &[RATE_MEAS]);
+
+    let expected = vec![
+        0xB5, 0x62, 0x06, 0x8C, 0x08, 0x00, 0x00, 0x06, 0x00, 0x00, 0x01, 0x00, 0x21, 0x30, 0xF2,
+        0x3C,
+    ];
+
+    assert_eq!(expected, frame.to_bytes());
+}
+
+#[test]
+fn test_cfg_valget() {
+    let input = [
+        0xB5, 0x62, 0x06, 0x8B, 0x14, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x21, 0x30, 0xF4,
+        0x01, 0x21, 0x00, 0x11, 0x20, 0x02, 0xAC, 0x00, 0x91, 0x20, 0x01, 0x9F, 0xB0,
+    ];
+
+    let frame = match frame(&input).unwrap().1 {
+        UBXFrameResult::Valid(f) => f,
+        r => panic!("invalid frame {:?}", r),
+    };
+
+    let expected = UBXCfgValGet {
+        received: Some(Duration::from_secs(7)),
+        layer: 0,
+        position: 0,
+        items: vec![
+            UBXConfigItem::new(RATE_MEAS, 500),
+            UBXConfigItem::new(NAVSPG_DYNMODEL, 2),
+            UBXConfigItem::new(0x209100AC, 1),
+        ],
+    };
+
+    assert_eq!(
+        UBXMessage::CfgValGet(expected),
+        message(frame, Duration::from_secs(7))
+    );
+}
@@ -0,0 +1,68 @@
+use crate::ubx::frame::*;
+use nom::{Err, Needed};
+
+const ACK: [u8; 10] = [0xB5, 0x62, 0x05, 0x01, 0x02, 0x00, 0x06, 0x8A, 0x98, 0xC1];
+
+#[test]
+fn test_checksum() {
+    assert_eq!(0xC198, checksum(&ACK[2..8]));
+}
+
+#[test]
+fn test_frame() {
+    let mut input = ACK.to_vec();
+    input.extend_from_slice(b"$GP");
+
+    let (rest, parsed) = frame(&input).unwrap();
+
+    let expected = UBXFrame {
+        class: 0x05,
+        id: 0x01,
+        payload: vec![0x06, 0x8A],
+    };
+
+    assert_eq!(UBXFrameResult::Valid(expected), parsed);
+    assert_eq!(b"$GP", rest);
+}
+
+#[test]
+fn test_frame_incomplete() {
+    match frame(&ACK[..7]) {
+        Err(Err::Incomplete(Needed::Size(n))) => {
+            assert_eq!(std::num::NonZeroUsize::new(1).unwrap(), n)
+        }
+        r => panic!("Expected Incomplete, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_frame_invalid_checksum() {
+    let mut input = ACK.to_vec();
+    input[9] = 0x00;
+
+    let (rest, parsed) = frame(&input).unwrap();
+
+    let expected = UBXChecksumMismatch {
+        class: 0x05,
+        id: 0x01,
+        given: 0x0098,
+        calculated: 0xC198,
+    };
+
+    assert_eq!(UBXFrameResult::InvalidChecksum(expected), parsed);
+    assert_eq!(&ACK[2..8], &rest[..6]);
+}
+
+#[test]
+fn test_frame_too_long() {
+    let input = [0xB5, 0x62, 0x01, 0x07, 0xFF, 0xFF];
+
+    assert!(matches!(frame(&input), Err(Err::Error(_))));
+}
+
+#[test]
+fn test_frame_not_sync() {
+    let input = [0xB5, 0x24, 0x01, 0x07, 0x00, 0x00];
+
+    assert!(matches!(frame(&input), Err(Err::Error(_))));
+}
@@ -0,0 +1,298 @@
+use crate::ubx::{
+    frame::{frame, UBXFrameResult},
+    parser::*,
+    UBXFrame,
+};
+use std::time::Duration;
+
+fn decode(input: &[u8]) -> UBXMessage {
+    match frame(input).unwrap().1 {
+        UBXFrameResult::Valid(f) => message(f, timestamp()),
+        r => panic!("invalid frame {:?}", r),
+    }
+}
+
+fn timestamp() -> Duration {
+    Duration::from_secs(7)
+}
+
+#[test]
+fn test_nav_pvt() {
+    let input = [
+        0xB5, 0x62, 0x01, 0x07, 0x5C, 0x00, 0x68, 0x48, 0x51, 0x1C, 0xE6, 0x07, 0x08, 0x01, 0x0B,
+        0x3A, 0x00, 0x37, 0x15, 0x00, 0x00, 0x00, 0xC0, 0x1D, 0xFE, 0xFF, 0x03, 0x81, 0xEA, 0x15,
+        0x40, 0x80, 0x1A, 0xB7, 0x40, 0x68, 0x31, 0x1C, 0x20, 0xCB, 0x00, 0x00, 0xA8, 0x61, 0x00,
+        0x00, 0xBC, 0x02, 0x00, 0x00, 0x4C, 0x04, 0x00, 0x00, 0x0A, 0x00, 0x00, 0x00, 0xEC, 0xFF,
+        0xFF, 0xFF, 0x05, 0x00, 0x00, 0x00, 0x16, 0x00, 0x00, 0x00, 0x87, 0xD6, 0x12, 0x00, 0x96,
+        0x00, 0x00, 0x00, 0x80, 0x84, 0x1E, 0x00, 0x7D, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0xD2, 0x04, 0x64, 0x00, 0xD9, 0x93,
+    ];
+
+    let pvt = match decode(&input) {
+        UBXMessage::NavPVT(pvt) => pvt,
+        m => panic!("expected NAV-PVT, got {:?}", m),
+    };
+
+    assert_eq!(Some(timestamp()), pvt.received);
+    assert_eq!(475089000, pvt.time_of_week);
+    assert_eq!(
+        chrono::NaiveDate::from_ymd(2022, 8, 1).and_hms_nano(11, 57, 59, 999_876_544),
+        pvt.date_time().unwrap()
+    );
+    assert!(pvt.fully_resolved);
+    assert_eq!(21, pvt.time_accuracy);
+    assert_eq!(UBXFixType::Fix3D, pvt.fix_type);
+    assert!(pvt.gnss_fix_ok);
+    assert!(!pvt.differential);
+    assert_eq!(UBXCarrierSolution::Fixed, pvt.carrier_solution);
+    assert_eq!(21, pvt.num_satellites);
+    assert_approx_eq!(-122.3, pvt.longitude);
+    assert_approx_eq!(47.3, pvt.latitude);
+    assert_approx_eq!(52.0, pvt.height);
+    assert_approx_eq!(25.0, pvt.height_msl);
+    assert_approx_eq!(0.7, pvt.horizontal_accuracy);
+    assert_approx_eq!(1.1, pvt.vertical_accuracy);
+    assert_approx_eq!(0.01, pvt.velocity_north);
+    assert_approx_eq!(-0.02, pvt.velocity_east);
+    assert_approx_eq!(0.005, pvt.velocity_down);
+    assert_approx_eq!(0.022, pvt.ground_speed);
+    assert_approx_eq!(12.34567, pvt.heading_of_motion);
+    assert_approx_eq!(20.0, pvt.heading_accuracy);
+    assert_approx_eq!(1.25, pvt.pdop);
+    assert!(!pvt.heading_of_vehicle_valid);
+    assert_approx_eq!(12.34, pvt.magnetic_declination);
+    assert_approx_eq!(1.0, pvt.magnetic_declination_accuracy);
+}
+
+#[test]
+fn test_nav_pvt_short() {
+    let frame = UBXFrame {
+        class: 0x01,
+        id: 0x07,
+        payload: vec![0; 40],
+    };
+
+    assert_eq!(
+        UBXMessage::ParseError(frame.clone()),
+        message(frame, timestamp())
+    );
+}
+
+#[test]
+fn test_nav_relposned() {
+    let input = [
+        0xB5, 0x62, 0x01, 0x3C, 0x40, 0x00, 0x01, 0x00, 0x00, 0x00, 0x68, 0x48, 0x51, 0x1C, 0x2C,
+        0x01, 0x00, 0x00, 0x90, 0x01, 0x00, 0x00, 0xF4, 0xFF, 0xFF, 0xFF, 0xF4, 0x01, 0x00, 0x00,
+        0xF2, 0x11, 0x51, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFD, 0x0E, 0x64, 0x00, 0x00,
+        0x00, 0x64, 0x00, 0x00, 0x00, 0xC8, 0x00, 0x00, 0x00, 0x96, 0x00, 0x00, 0x00, 0xA8, 0x61,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x37, 0x01, 0x00, 0x00, 0x05, 0xC4,
+    ];
+
+    let relposned = match decode(&input) {
+        UBXMessage::NavRelPosNED(r) => r,
+        m => panic!("expected NAV-RELPOSNED, got {:?}", m),
+    };
+
+    assert_eq!(Some(timestamp()), relposned.received);
+    assert_eq!(0, relposned.reference_station_id);
+    assert_eq!(475089000, relposned.time_of_week);
+    assert_approx_eq!(3.0, relposned.north);
+    assert_approx_eq!(4.0, relposned.east);
+    assert_approx_eq!(-0.1203, relposned.down);
+    assert_approx_eq!(5.0014, relposned.length);
+    assert_approx_eq!(53.1301, relposned.heading);
+    assert_approx_eq!(0.01, relposned.north_accuracy);
+    assert_approx_eq!(0.02, relposned.down_accuracy);
+    assert_approx_eq!(0.015, relposned.length_accuracy);
+    assert_approx_eq!(0.25, relposned.heading_accuracy);
+    assert!(relposned.gnss_fix_ok);
+    assert!(relposned.differential);
+    assert!(relposned.relative_position_valid);
+    assert_eq!(UBXCarrierSolution::Fixed, relposned.carrier_solution);
+    assert!(relposned.moving_base);
+    assert!(!relposned.reference_position_missing);
+    assert!(!relposned.reference_observations_missing);
+    assert!(relposned.heading_valid);
+    assert!(!relposned.normalized);
+    assert_approx_eq!(1.3782, relposned.pitch(), 1e-4);
+}
+
+#[test]
+fn test_nav_sat() {
+    let input = [
+        0xB5, 0x62, 0x01, 0x35, 0x20, 0x00, 0x68, 0x48, 0x51, 0x1C, 0x01, 0x02, 0x00, 0x00, 0x00,
+        0x0C, 0x2D, 0x3C, 0x0E, 0x01, 0xF1, 0xFF, 0x1F, 0x00, 0x00, 0x00, 0x06, 0x03, 0x1E, 0xFB,
+        0x5A, 0x00, 0x00, 0x00, 0x21, 0x00, 0x00, 0x00, 0xA6, 0xED,
+    ];
+
+    let sat = match decode(&input) {
+        UBXMessage::NavSat(sat) => sat,
+        m => panic!("expected NAV-SAT, got {:?}", m),
+    };
+
+    assert_eq!(475089000, sat.time_of_week);
+    assert_eq!(1, sat.version);
+
+    let satellites = vec![
+        UBXNavSatellite {
+            gnss_id: UBXGnssId::GPS,
+            sv_id: 12,
+            cno: 45,
+            elevation: 60,
+            azimuth: 270,
+            pseudorange_residual: -1.5,
+            quality: UBXSignalQuality::CarrierLocked,
+            used: true,
+            health: UBXHealth::Healthy,
+            differential: false,
+        },
+        UBXNavSatellite {
+            gnss_id: UBXGnssId::GLONASS,
+            sv_id: 3,
+            cno: 30,
+            elevation: -5,
+            azimuth: 90,
+            pseudorange_residual: 0.0,
+            quality: UBXSignalQuality::Searching,
+            used: false,
+            health: UBXHealth::Unhealthy,
+            differential: false,
+        },
+    ];
+
+    assert_eq!(satellites, sat.satellites);
+}
+
+#[test]
+fn test_nav_status() {
+    let input = [
+        0xB5, 0x62, 0x01, 0x03, 0x10, 0x00, 0x68, 0x48, 0x51, 0x1C, 0x03, 0x0D, 0x01, 0x80, 0xA8,
+        0x61, 0x00, 0x00, 0x80, 0xEE, 0x36, 0x00, 0x6F, 0x59,
+    ];
+
+    let expected = UBXNavStatus {
+        received: Some(timestamp()),
+        time_of_week: 475089000,
+        fix_type: UBXFixType::Fix3D,
+        gnss_fix_ok: true,
+        differential: false,
+        week_valid: true,
+        time_of_week_valid: true,
+        differential_corrections: true,
+        carrier_solution: UBXCarrierSolution::Fixed,
+        ttff: 25000,
+        msss: 3600000,
+    };
+
+    assert_eq!(UBXMessage::NavStatus(expected), decode(&input));
+}
+
+#[test]
+fn test_nav_timels() {
+    let input = [
+        0xB5, 0x62, 0x01, 0x26, 0x18, 0x00, 0x50, 0x4C, 0x51, 0x1C, 0x00, 0x00, 0x00, 0x00, 0x02,
+        0x12, 0x02, 0x01, 0x00, 0x2F, 0x0D, 0x00, 0xFC, 0x08, 0x07, 0x00, 0x00, 0x00, 0x00, 0x03,
+        0xA9, 0x47,
+    ];
+
+    let expected = UBXNavTimeLS {
+        received: Some(timestamp()),
+        time_of_week: 475090000,
+        version: 0,
+        current_source: 2,
+        current: 18,
+        change_source: 2,
+        change: 1,
+        time_to_event: 864000,
+        event_week: 2300,
+        event_day: 7,
+        valid_current: true,
+        valid_time_to_event: true,
+    };
+
+    assert_eq!(UBXMessage::NavTimeLS(expected), decode(&input));
+}
+
+#[test]
+fn test_nav_timeutc() {
+    let input = [
+        0xB5, 0x62, 0x01, 0x21, 0x14, 0x00, 0x68, 0x48, 0x51, 0x1C, 0x19, 0x00, 0x00, 0x00, 0x0C,
+        0xFE, 0xFF, 0xFF, 0xE6, 0x07, 0x08, 0x01, 0x0B, 0x3A, 0x00, 0x37, 0xE6, 0x6B,
+    ];
+
+    let time = match decode(&input) {
+        UBXMessage::NavTimeUTC(time) => time,
+        m => panic!("expected NAV-TIMEUTC, got {:?}", m),
+    };
+
+    assert_eq!(25, time.time_accuracy);
+    assert!(time.valid_time_of_week);
+    assert!(time.valid_week);
+    assert!(time.valid_utc);
+    assert_eq!(UBXUTCStandard::USNO, time.utc_standard);
+    assert_eq!(
+        chrono::NaiveDate::from_ymd(2022, 8, 1).and_hms_nano(11, 57, 59, 999_999_500),
+        time.date_time().unwrap()
+    );
+}
+
+#[test]
+fn test_tim_tp() {
+    let input = [
+        0xB5, 0x62, 0x0D, 0x01, 0x10, 0x00, 0x50, 0x4C, 0x51, 0x1C, 0x00, 0x00, 0x00, 0x80, 0x50,
+        0xFB, 0xFF, 0xFF, 0xAD, 0x08, 0x03, 0x30, 0xD8, 0x59,
+    ];
+
+    let tp = match decode(&input) {
+        UBXMessage::TimTP(tp) => tp,
+        m => panic!("expected TIM-TP, got {:?}", m),
+    };
+
+    assert_eq!(475090000, tp.time_of_week_ms);
+    assert_approx_eq!(475090.0005, tp.time_of_week());
+    assert_eq!(-1200, tp.quantization_error);
+    assert_eq!(2221, tp.week);
+    assert_eq!(UBXTimeBase::UTC, tp.time_base);
+    assert!(tp.utc_available);
+    assert!(tp.quantization_error_valid);
+    assert_eq!(UBXUTCStandard::USNO, tp.utc_standard);
+}
+
+#[test]
+fn test_ack() {
+    let input = [0xB5, 0x62, 0x05, 0x01, 0x02, 0x00, 0x06, 0x8A, 0x98, 0xC1];
+
+    let expected = UBXAck {
+        received: Some(timestamp()),
+        class: 0x06,
+        id: 0x8A,
+    };
+
+    assert_eq!(UBXMessage::Ack(expected), decode(&input));
+}
+
+#[test]
+fn test_nak() {
+    let input = [0xB5, 0x62, 0x05, 0x00, 0x02, 0x00, 0x06, 0x8A, 0x97, 0xBC];
+
+    let expected = UBXAck {
+        received: Some(timestamp()),
+        class: 0x06,
+        id: 0x8A,
+    };
+
+    assert_eq!(UBXMessage::Nak(expected), decode(&input));
+}
+
+#[test]
+fn test_unsupported() {
+    let input = [0xB5, 0x62, 0x0A, 0x04, 0x00, 0x00, 0x0E, 0x34];
+
+    let expected = UBXFrame {
+        class: 0x0A,
+        id: 0x04,
+        payload: vec![],
+    };
+
+    assert_eq!(UBXMessage::Unsupported(expected), decode(&input));
+}